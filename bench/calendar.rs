@@ -1,8 +1,10 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use dayendar::binary::*;
-use dayendar::utils::*;
+use dayendar::calendar::DaysCalendar;
 use dayendar::types::*;
-use dayendar::calendar::resume;
+use dayendar::calendar::{resume, merge_join};
+use dayendar::bitcalendar::BitCalendar;
+use dayendar::index::CalendarIndex;
 
 fn or_benchmark(c: &mut Criterion) {
     let v1 = black_box(vec![BiDay::One; 1000]);
@@ -46,12 +48,70 @@ fn resume_dc_benchmark(c: &mut Criterion) {
     
     c.bench_function("resume", |b| {
       b.iter(|| {
-        let res = resume(black_box(&calendar), |a, b| and_biday_operation(a, b));
+        let res = resume(black_box(&calendar), and_biday_operation);
         black_box(res); 
       })
     });
   }
   
+/// Builds a calendar of every month between two years with a repeating pattern
+fn decades_calendar(from: Year, to: Year, pattern: &[BiDay]) -> DaysCalendar<BiDay> {
+    let months = (from..=to)
+        .flat_map(|year| (1..=12).map(move |month| DaysCalendar::singleton(year, Month::from_index(month).unwrap()).unwrap()))
+        .fold(DaysCalendar::empty(), |acc, month| acc.append(&month));
+    dayendar::calendar::replicate::<BiDay>(pattern, months)
+}
+
+fn bitcalendar_benchmark(c: &mut Criterion) {
+    let calendar1 = decades_calendar(1990, 2040, &[BiDay::One, BiDay::Zero, BiDay::Zero]);
+    let calendar2 = decades_calendar(1990, 2040, &[BiDay::Zero, BiDay::One]);
+    let packed1 = BitCalendar::from(&calendar1);
+    let packed2 = BitCalendar::from(&calendar2);
+
+    c.bench_function("50 years days calendar or", |b| b.iter(|| black_box(&calendar1).or(black_box(&calendar2))));
+    c.bench_function("50 years bit calendar or", |b| b.iter(|| black_box(&packed1).or(black_box(&packed2))));
+    c.bench_function("50 years bit calendar count", |b| b.iter(|| black_box(&packed1).count()));
+}
+
+fn merge_join_benchmark(c: &mut Criterion) {
+    let calendar1 = decades_calendar(1990, 2040, &[BiDay::One, BiDay::Zero, BiDay::Zero]);
+    let calendar2 = decades_calendar(1990, 2040, &[BiDay::Zero, BiDay::One]);
+    let calendars: Vec<DaysCalendar<BiDay>> = (0..8)
+        .map(|shift| decades_calendar(1990 + shift, 2040, &[BiDay::One, BiDay::Zero]))
+        .collect();
+    let refs: Vec<&DaysCalendar<BiDay>> = calendars.iter().collect();
+
+    c.bench_function("50 years append + resume or", |b| {
+        b.iter(|| resume(&black_box(&calendar1).append(black_box(&calendar2)), or_biday_operation))
+    });
+    c.bench_function("50 years merge join or", |b| {
+        b.iter(|| merge_join(&[black_box(&calendar1), black_box(&calendar2)], or_biday_operation))
+    });
+    c.bench_function("8 calendars folded or", |b| {
+        b.iter(|| refs[1..].iter().fold(refs[0].clone(), |acc, calendar| resume(&acc.append(calendar), or_biday_operation)))
+    });
+    c.bench_function("8 calendars merge join or", |b| b.iter(|| DaysCalendar::or_all(black_box(&refs))));
+}
+
+fn navigation_benchmark(c: &mut Criterion) {
+    let calendar = decades_calendar(1990, 2040, &[BiDay::One, BiDay::Zero, BiDay::Zero]);
+
+    c.bench_function("50 years next day", |b| {
+        b.iter(|| black_box(&calendar).next_day(2020, Month::June, 15))
+    });
+    c.bench_function("50 years seek 10th previous day", |b| {
+        b.iter(|| black_box(&calendar).seek_nth_day(2020, Month::June, 15, -10))
+    });
+
+    let index = CalendarIndex::from(&calendar);
+    let reference = date!(2020 - 06 - 15);
+    c.bench_function("50 years indexed next day", |b| {
+        b.iter(|| black_box(&index).next_day(black_box(reference)))
+    });
+    c.bench_function("50 years indexed seek 1000th day", |b| {
+        b.iter(|| black_box(&index).seek_nth_day(black_box(reference), 1000))
+    });
+}
 
 criterion_group!(
     benches,
@@ -59,6 +119,9 @@ criterion_group!(
     and_benchmark,
     match_benchmark,
     calendar_operations_benchmark,
-    resume_dc_benchmark
+    resume_dc_benchmark,
+    bitcalendar_benchmark,
+    merge_join_benchmark,
+    navigation_benchmark
 );
 criterion_main!(benches);
@@ -1,33 +1,28 @@
-use std::fmt;
-use dayendar::types::{Year, Month, Weekday, BiDay};
-use dayendar::abstracto::{AbstractCalendar, CalendarOperation, CalendarPattern};
-
-///Output:
-/// Not(
-///   CustomDays([1, 25])
-///   Or
-///   Weekdays([Saturday, Sunday])
-///   Or
-///   CronPattern("* * * ? * *")
-///)
-//
+use dayendar::types::{Weekday, DateSpan, YearMonthSpec, date};
+use dayendar::abstracto::{AbstractCalendar, CalendarPattern};
+use dayendar::calendar::biday_to_vec_day;
 
+/// Working days of January 2024, output:
+/// [(2024, January, [2, 3, 4, 5, 8, 9, 10, 11, 12, 16, 17, 18, 19, 22, 23, 24, 26, 29, 30, 31])]
 fn main() {
 
     // Usando las funciones auxiliares
     let weekends: AbstractCalendar = AbstractCalendar::Pattern(CalendarPattern::Weekdays(vec![Weekday::Saturday, Weekday::Sunday]));
     let fixed_holidays: AbstractCalendar = AbstractCalendar::Pattern(CalendarPattern::CustomDays(vec![1, 25]));
-    let floating_holidays: AbstractCalendar = AbstractCalendar::Pattern(CalendarPattern::CronPattern(String::from("* * * ? * *")));
+    let floating_holidays: AbstractCalendar = AbstractCalendar::Pattern(CalendarPattern::FloatingHolidays(vec![date!(2024 - 01 - 15)]));
 
     // Combina los patrones
     let combined_holidays: AbstractCalendar = fixed_holidays.or(weekends).or(floating_holidays);
 
     // Negar para obtener días hábiles
     let workdays: AbstractCalendar = combined_holidays.invert();
-    
-    let as_string: String = format!("{}", workdays);
 
     println!("{:?}", workdays);
-    println!("\n {:?}\n", as_string);
 
-}
\ No newline at end of file
+    // Materializa el calendario abstracto para enero de 2024
+    let span: DateSpan = DateSpan::YearMonth(YearMonthSpec::parse("2024-January").expect("Failed to parse YearMonthSpec"));
+    let calendar = workdays.evaluate(&span).expect("Failed to evaluate the calendar");
+
+    println!("\n {:?}\n", biday_to_vec_day(calendar));
+
+}
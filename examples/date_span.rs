@@ -60,7 +60,7 @@ fn main() {
             ].into_iter().collect();
             
     let ds1: DateSpan = DateSpan::YearMonth(YearMonthSpec(ym_set1));
-    let ds2: DateSpan = DateSpan::YearMonth(YearMonthSpec(ym_set2));
+    let _ds2: DateSpan = DateSpan::YearMonth(YearMonthSpec(ym_set2));
     
     println!("\n{:?}", ds1.to_year_month());
     println!("{:?}", date_span_from_year.to_year_month());
@@ -22,14 +22,14 @@ fn main() {
         DaysCalendar {
             days_calendar: boss_meeting_days
         }
-    );
+    ).expect("Invalid meeting days");
 
     // Hackathon days in January 2023.
     let hackathon_days = from_day(
         DaysCalendar {
             days_calendar: vec![(2023, Month::January, vec![30, 31])]
         }
-    );
+    ).expect("Invalid hackathon days");
 
     // Determine John's office days considering his workdays, boss's meeting days, and hackathon days.
     let john_office_days = DaysCalendar::singleton(2023, Month::January).unwrap()
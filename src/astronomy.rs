@@ -0,0 +1,245 @@
+/// The `astro` module computes the new moons and the apparent solar longitude needed by lunisolar calendars.
+///
+/// Moments are Julian days in Universal Time. New moons follow the algorithm of Meeus, *Astronomical
+/// Algorithms*, chapter 49, and the solar longitude the 49 term series of Reingold and Dershowitz,
+/// *Calendrical Calculations*. Both are accurate to a minute or two from 1900 to 2100.
+pub mod astro {
+
+    use std::f64::consts::PI;
+
+    /// Julian day of the epoch J2000.0
+    const J2000: f64 = 2_451_545.0;
+
+    /// Mean length of a lunar month in days
+    pub const MEAN_SYNODIC_MONTH: f64 = 29.530_588_861;
+
+    /// Mean length of a tropical year in days
+    pub const MEAN_TROPICAL_YEAR: f64 = 365.242_189;
+
+    /// Julian day, in Terrestrial Time, of the new moon of 6 January 2000
+    const NEW_MOON_EPOCH: f64 = 2_451_550.097_66;
+
+    fn sin_degrees(degrees: f64) -> f64 {
+        (degrees * PI / 180.0).sin()
+    }
+
+    fn cos_degrees(degrees: f64) -> f64 {
+        (degrees * PI / 180.0).cos()
+    }
+
+    /// Difference between Terrestrial Time and Universal Time in seconds, polynomials of Espenak and Meeus
+    fn delta_t(year: f64) -> f64 {
+        match year {
+            y if y < 1900.0 => {
+                let t = y - 1860.0;
+                7.62 + 0.5737 * t - 0.251754 * t.powi(2) + 0.01680668 * t.powi(3) - 0.0004473624 * t.powi(4) + t.powi(5) / 233174.0
+            },
+            y if y < 1920.0 => {
+                let t = y - 1900.0;
+                -2.79 + 1.494119 * t - 0.0598939 * t.powi(2) + 0.0061966 * t.powi(3) - 0.000197 * t.powi(4)
+            },
+            y if y < 1941.0 => {
+                let t = y - 1920.0;
+                21.20 + 0.84493 * t - 0.076100 * t.powi(2) + 0.0020936 * t.powi(3)
+            },
+            y if y < 1961.0 => {
+                let t = y - 1950.0;
+                29.07 + 0.407 * t - t.powi(2) / 233.0 + t.powi(3) / 2547.0
+            },
+            y if y < 1986.0 => {
+                let t = y - 1975.0;
+                45.45 + 1.067 * t - t.powi(2) / 260.0 - t.powi(3) / 718.0
+            },
+            y if y < 2005.0 => {
+                let t = y - 2000.0;
+                63.86 + 0.3345 * t - 0.060374 * t.powi(2) + 0.0017275 * t.powi(3) + 0.000651814 * t.powi(4)
+                    + 0.00002373599 * t.powi(5)
+            },
+            y if y < 2050.0 => {
+                let t = y - 2000.0;
+                62.92 + 0.32217 * t + 0.005589 * t.powi(2)
+            },
+            y if y < 2150.0 => -20.0 + 32.0 * ((y - 1820.0) / 100.0).powi(2) - 0.5628 * (2150.0 - y),
+            y => -20.0 + 32.0 * ((y - 1820.0) / 100.0).powi(2),
+        }
+    }
+
+    /// Converts a moment in Universal Time to Terrestrial Time
+    fn dynamical(moment: f64) -> f64 {
+        moment + delta_t(2000.0 + (moment - J2000) / 365.25) / 86400.0
+    }
+
+    /// Converts a moment in Terrestrial Time to Universal Time
+    fn universal(moment: f64) -> f64 {
+        moment - delta_t(2000.0 + (moment - J2000) / 365.25) / 86400.0
+    }
+
+    /// (amplitude, phase, frequency) terms of the solar longitude series
+    const SOLAR_TERMS: [(f64, f64, f64); 49] = [
+        (403406.0, 270.54861, 0.9287892), (195207.0, 340.19128, 35999.1376958),
+        (119433.0, 63.91854, 35999.4089666), (112392.0, 331.26220, 35998.7287385),
+        (3891.0, 317.843, 71998.20261), (2819.0, 86.631, 71998.4403),
+        (1721.0, 240.052, 36000.35726), (660.0, 310.26, 71997.4812),
+        (350.0, 247.23, 32964.4678), (334.0, 260.87, -19.4410),
+        (314.0, 297.82, 445267.1117), (268.0, 343.14, 45036.8840),
+        (242.0, 166.79, 3.1008), (234.0, 81.53, 22518.4434),
+        (158.0, 3.50, -19.9739), (132.0, 132.75, 65928.9345),
+        (129.0, 182.95, 9038.0293), (114.0, 162.03, 3034.7684),
+        (99.0, 29.8, 33718.148), (93.0, 266.4, 3034.448),
+        (86.0, 249.2, -2280.773), (78.0, 157.6, 29929.992),
+        (72.0, 257.8, 31556.493), (68.0, 185.1, 149.588),
+        (64.0, 69.9, 9037.750), (46.0, 8.0, 107997.405),
+        (38.0, 197.1, -4444.176), (37.0, 250.4, 151.771),
+        (32.0, 65.3, 67555.316), (29.0, 162.7, 31556.080),
+        (28.0, 341.5, -4561.540), (27.0, 291.6, 107996.706),
+        (27.0, 98.5, 1221.655), (25.0, 146.7, 62894.167),
+        (24.0, 110.0, 31437.369), (21.0, 5.2, 14578.298),
+        (21.0, 342.6, -31931.757), (20.0, 230.9, 34777.243),
+        (18.0, 256.1, 1221.999), (17.0, 45.3, 62894.511),
+        (14.0, 242.9, -4442.039), (13.0, 115.2, 107997.909),
+        (13.0, 151.8, 119.066), (13.0, 285.3, 16859.071),
+        (12.0, 53.3, -4.578), (10.0, 126.6, 26895.292),
+        (10.0, 205.7, -39.127), (10.0, 85.9, 12297.536),
+        (10.0, 146.1, 90073.778),
+    ];
+
+    /// Apparent longitude of the sun in degrees, from 0 to 360, at a moment
+    pub fn solar_longitude(moment: f64) -> f64 {
+        let c = (dynamical(moment) - J2000) / 36525.0;
+        let series: f64 = SOLAR_TERMS.iter().map(|&(x, y, z)| x * sin_degrees(y + z * c)).sum();
+        let longitude = 282.7771834 + 36000.76953744 * c + 0.000005729577951308232 * series;
+        let aberration = 0.0000974 * cos_degrees(177.63 + 35999.01848 * c) - 0.005575;
+        let a = 124.90 - 1934.134 * c + 0.002063 * c * c;
+        let b = 201.11 + 72001.5377 * c + 0.00057 * c * c;
+        let nutation = -0.004778 * sin_degrees(a) - 0.0003667 * sin_degrees(b);
+        (longitude + aberration + nutation).rem_euclid(360.0)
+    }
+
+    /// First moment after `moment` at which the apparent solar longitude is `longitude` degrees
+    pub fn solar_longitude_after(longitude: f64, moment: f64) -> f64 {
+        let rate = MEAN_TROPICAL_YEAR / 360.0;
+        let estimate = moment + (longitude - solar_longitude(moment)).rem_euclid(360.0) * rate;
+        let (mut low, mut high) = ((estimate - 5.0).max(moment), estimate + 5.0);
+        // Bisection down to a fraction of a second
+        while high - low > 1e-6 {
+            let middle = (low + high) / 2.0;
+            if (solar_longitude(middle) - longitude + 180.0).rem_euclid(360.0) < 180.0 {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        (low + high) / 2.0
+    }
+
+    /// Moment of the new moon `k` lunations after the new moon of 6 January 2000
+    pub fn nth_new_moon(k: i64) -> f64 {
+        let k = k as f64;
+        let t = k / 1236.85;
+        let jde = NEW_MOON_EPOCH + MEAN_SYNODIC_MONTH * k + 0.00015437 * t.powi(2) - 0.000000150 * t.powi(3)
+            + 0.00000000073 * t.powi(4);
+        let e = 1.0 - 0.002516 * t - 0.0000074 * t.powi(2);
+        let sun = 2.5534 + 29.10535670 * k - 0.0000014 * t.powi(2) - 0.00000011 * t.powi(3);
+        let moon = 201.5643 + 385.81693528 * k + 0.0107582 * t.powi(2) + 0.00001238 * t.powi(3)
+            - 0.000000058 * t.powi(4);
+        let latitude = 160.7108 + 390.67050284 * k - 0.0016118 * t.powi(2) - 0.00000227 * t.powi(3)
+            + 0.000000011 * t.powi(4);
+        let node = 124.7746 - 1.56375588 * k + 0.0020672 * t.powi(2) + 0.00000215 * t.powi(3);
+
+        // (coefficient, power of e, multiples of the moon anomaly, sun anomaly, latitude and node)
+        const PERIODIC: [(f64, i32, f64, f64, f64, f64); 25] = [
+            (-0.40720, 0, 1.0, 0.0, 0.0, 0.0), (0.17241, 1, 0.0, 1.0, 0.0, 0.0),
+            (0.01608, 0, 2.0, 0.0, 0.0, 0.0), (0.01039, 0, 0.0, 0.0, 2.0, 0.0),
+            (0.00739, 1, 1.0, -1.0, 0.0, 0.0), (-0.00514, 1, 1.0, 1.0, 0.0, 0.0),
+            (0.00208, 2, 0.0, 2.0, 0.0, 0.0), (-0.00111, 0, 1.0, 0.0, -2.0, 0.0),
+            (-0.00057, 0, 1.0, 0.0, 2.0, 0.0), (0.00056, 1, 2.0, 1.0, 0.0, 0.0),
+            (-0.00042, 0, 3.0, 0.0, 0.0, 0.0), (0.00042, 1, 0.0, 1.0, 2.0, 0.0),
+            (0.00038, 1, 0.0, 1.0, -2.0, 0.0), (-0.00024, 1, 2.0, -1.0, 0.0, 0.0),
+            (-0.00017, 0, 0.0, 0.0, 0.0, 1.0), (-0.00007, 0, 1.0, 2.0, 0.0, 0.0),
+            (0.00004, 0, 2.0, 0.0, -2.0, 0.0), (0.00004, 0, 0.0, 3.0, 0.0, 0.0),
+            (0.00003, 0, 1.0, 1.0, -2.0, 0.0), (0.00003, 0, 2.0, 0.0, 2.0, 0.0),
+            (-0.00003, 0, 1.0, 1.0, 2.0, 0.0), (0.00003, 0, 1.0, -1.0, 2.0, 0.0),
+            (-0.00002, 0, 1.0, -1.0, -2.0, 0.0), (-0.00002, 0, 3.0, 1.0, 0.0, 0.0),
+            (0.00002, 0, 4.0, 0.0, 0.0, 0.0),
+        ];
+        let periodic: f64 = PERIODIC.iter()
+            .map(|&(c, power, m1, m, f, o)| c * e.powi(power) * sin_degrees(m1 * moon + m * sun + f * latitude + o * node))
+            .sum();
+
+        const PLANETARY: [(f64, f64, f64); 14] = [
+            (0.000325, 299.77, 0.107408), (0.000165, 251.88, 0.016321), (0.000164, 251.83, 26.651886),
+            (0.000126, 349.42, 36.412478), (0.000110, 84.66, 18.206239), (0.000062, 141.74, 53.303771),
+            (0.000060, 207.14, 2.453732), (0.000056, 154.84, 7.306860), (0.000047, 34.52, 27.261239),
+            (0.000042, 207.19, 0.121824), (0.000040, 291.34, 1.844379), (0.000037, 161.72, 24.198154),
+            (0.000035, 239.56, 25.513099), (0.000023, 331.55, 3.592518),
+        ];
+        let planetary: f64 = PLANETARY.iter()
+            .enumerate()
+            .map(|(i, &(w, a, b))| {
+                // Only the first argument has a secular term
+                let argument = if i == 0 { a + b * k - 0.009173 * t.powi(2) } else { a + b * k };
+                w * sin_degrees(argument)
+            })
+            .sum();
+
+        universal(jde + periodic + planetary)
+    }
+
+    /// First new moon at or after a moment
+    pub fn new_moon_at_or_after(moment: f64) -> f64 {
+        let mut k = ((moment - NEW_MOON_EPOCH) / MEAN_SYNODIC_MONTH).floor() as i64 - 1;
+        while nth_new_moon(k) < moment {
+            k += 1;
+        }
+        nth_new_moon(k)
+    }
+
+    /// Last new moon before a moment
+    pub fn new_moon_before(moment: f64) -> f64 {
+        let mut k = ((moment - NEW_MOON_EPOCH) / MEAN_SYNODIC_MONTH).ceil() as i64 + 1;
+        while nth_new_moon(k) >= moment {
+            k -= 1;
+        }
+        nth_new_moon(k)
+    }
+
+}
+
+// =========================================
+// SECTION FOR UNIT TEST CODE ...
+// =========================================
+#[cfg(test)]
+mod tests_astro {
+
+    use crate::astro::*;
+
+    /// Julian day of a UTC date and time
+    fn moment(year: i32, month: u8, day: u8, hour: u8, minute: u8) -> f64 {
+        let date = time::Date::from_calendar_date(year, time::Month::try_from(month).unwrap(), day).unwrap();
+        f64::from(date.to_julian_day()) - 0.5 + (f64::from(hour) + f64::from(minute) / 60.0) / 24.0
+    }
+
+    fn assert_near(actual: f64, expected: f64, minutes: f64) {
+        assert!((actual - expected).abs() * 1440.0 <= minutes, "{} is not {}", actual, expected);
+    }
+
+    #[test]
+    fn test_new_moons() {
+        // Meeus, example 49.a, the new moon of February 1977
+        assert_near(new_moon_at_or_after(moment(1977, 2, 1, 0, 0)), moment(1977, 2, 18, 3, 37), 2.0);
+        assert_near(new_moon_at_or_after(moment(2024, 1, 1, 0, 0)), moment(2024, 1, 11, 11, 57), 2.0);
+        assert_near(new_moon_before(moment(2024, 1, 11, 11, 0)), moment(2023, 12, 12, 23, 32), 2.0);
+        assert!(nth_new_moon(1) - nth_new_moon(0) > 29.0);
+    }
+
+    #[test]
+    fn test_solar_longitude() {
+        // Equinoxes and solstices of 2024
+        let start = moment(2024, 1, 1, 0, 0);
+        assert_near(solar_longitude_after(0.0, start), moment(2024, 3, 20, 3, 6), 2.0);
+        assert_near(solar_longitude_after(90.0, start), moment(2024, 6, 20, 20, 51), 2.0);
+        assert_near(solar_longitude_after(180.0, start), moment(2024, 9, 22, 12, 44), 2.0);
+        assert_near(solar_longitude_after(270.0, start), moment(2024, 12, 21, 9, 20), 2.0);
+        assert!(((solar_longitude(moment(2000, 3, 20, 7, 35)) + 180.0).rem_euclid(360.0) - 180.0).abs() < 0.01);
+    }
+}
@@ -1,13 +1,15 @@
 
 use std::collections::HashSet;
 use std::collections::HashMap;
+use std::collections::BTreeSet;
 use std::collections::BTreeMap;
 
 /// Module for advanced and efficient calendar operations
 pub mod calendar {
 
-    
-    use crate::utils::{days_in_month, generate_vec_days};
+    use std::hash::{Hash, Hasher};
+    use crate::error::DayendarError;
+    use crate::utils::{days_in_month, checked_days_in_month, checked_date, generate_vec_days, nth_weekday_of_month};
     use crate::binary::{
         or_biday_operation, and_biday_operation,
         match_biday_operation, nomatch_biday_operation,
@@ -16,7 +18,7 @@ pub mod calendar {
     };
     use crate::types::{
         Year, Month, Day, BiDay, Weekday,
-        Date, Duration
+        Date, Duration, MonthSpec, MIN_DATE, MAX_DATE
     };
 
     use itertools::Itertools;
@@ -24,27 +26,178 @@ pub mod calendar {
     /// The `DaysCalendar` base type to handle calendar related operations.
     /// provides a set of methods and functions for working
     /// with dates and perform efficient calendar-related operations.
-    #[derive(Debug, Clone, Eq)]
+    ///
+    /// A calendar is in canonical form when its months are sorted by year and month,
+    /// there are no duplicated months and every month has exactly `days_in_month` days.
+    /// Equality and hashing compare canonical forms.
+    #[derive(Debug, Clone)]
     pub struct DaysCalendar<T> {
         pub days_calendar: Vec<(Year, Month, Vec<T>)>,
     }
 
+    impl<T> DaysCalendar<T> {
+        /// Determines whether the months are sorted by year and month without duplicates
+        pub fn is_sorted(&self) -> bool {
+            self.days_calendar
+                .windows(2)
+                .all(|pair| (pair[0].0, pair[0].1) < (pair[1].0, pair[1].1))
+        }
+
+        /// Iterates over the months of the calendar and their days
+        pub fn months(&self) -> impl DoubleEndedIterator<Item = (Year, Month, &[T])> + ExactSizeIterator {
+            self.days_calendar.iter().map(|(year, month, days)| (*year, *month, days.as_slice()))
+        }
+    }
+
+    /// Lazy iterator over the dates included in a `DaysCalendar<BiDay>`.
+    ///
+    /// Dates are produced in the order of the months of the calendar, which is
+    /// chronological when the calendar is sorted. Days that are not valid dates are skipped.
+    #[derive(Debug, Clone)]
+    pub struct Dates<'a> {
+        months: &'a [(Year, Month, Vec<BiDay>)],
+        /// Position (month, day) of the next date from the front
+        front: (usize, usize),
+        /// Position (month, day) just after the next date from the back
+        back: (usize, usize),
+        /// Inclusive lower bound of the dates
+        from: Option<Date>,
+        /// Exclusive upper bound of the dates
+        before: Option<Date>,
+    }
+
+    impl<'a> Dates<'a> {
+        fn new(calendar: &'a DaysCalendar<BiDay>) -> Self {
+            Dates {
+                months: &calendar.days_calendar,
+                front: (0, 0),
+                back: (calendar.days_calendar.len(), 0),
+                from: None,
+                before: None,
+            }
+        }
+
+        /// Position of the first day of the month of `date` or of the first month after it,
+        /// only meaningful for sorted calendars
+        fn position(&self, date: Date) -> (usize, usize) {
+            let key = (date.year(), date.month() as u8);
+            let index = self.months.partition_point(|(year, month, _)| (i32::from(*year), month.to_index()) < key);
+            match self.months.get(index) {
+                Some((year, month, days)) if (i32::from(*year), month.to_index()) == key => {
+                    (index, days.len().min(date.day() as usize - 1))
+                },
+                _ => (index, 0),
+            }
+        }
+
+        /// Builds the date at a position when it is included and inside the bounds
+        fn date_at(&self, (month_index, day_index): (usize, usize)) -> Option<Date> {
+            let (year, month, days) = &self.months[month_index];
+            if days[day_index] != BiDay::One {
+                return None;
+            }
+            let date = checked_date(*year, *month, day_index as Day + 1).ok()?;
+            let after_from = self.from.is_none_or(|from| date >= from);
+            let before_end = self.before.is_none_or(|before| date < before);
+            (after_from && before_end).then_some(date)
+        }
+    }
+
+    impl Iterator for Dates<'_> {
+        type Item = Date;
+
+        fn next(&mut self) -> Option<Date> {
+            while self.front < self.back {
+                let (month_index, day_index) = self.front;
+                if day_index >= self.months[month_index].2.len() {
+                    self.front = (month_index + 1, 0);
+                    continue;
+                }
+                self.front = (month_index, day_index + 1);
+                if let Some(date) = self.date_at((month_index, day_index)) {
+                    return Some(date);
+                }
+            }
+            None
+        }
+    }
+
+    impl DoubleEndedIterator for Dates<'_> {
+        fn next_back(&mut self) -> Option<Date> {
+            while self.back > self.front {
+                let (month_index, day_index) = self.back;
+                if day_index == 0 {
+                    self.back = (month_index - 1, self.months[month_index - 1].2.len());
+                    continue;
+                }
+                self.back = (month_index, day_index - 1);
+                if let Some(date) = self.date_at(self.back) {
+                    return Some(date);
+                }
+            }
+            None
+        }
+    }
+
+    impl std::iter::FusedIterator for Dates<'_> {}
+
     impl DaysCalendar<BiDay> {
         /// Create a DaysCalendar of a single year month
-        pub fn singleton(year: Year, month: Month) -> Option<DaysCalendar<BiDay>> {
-            if let Some(n_days) = days_in_month(year, month) {
-                let mut days_calendar: Vec<(Year, Month, Vec<BiDay>)> = Vec::new();
-                let vec: Vec<BiDay> = generate_vec_days(n_days as usize, BiDay::One);
-                days_calendar.push((year, month, vec));
-                Some(DaysCalendar { days_calendar })
-            } else {
-                None
-            }
+        pub fn singleton(year: Year, month: Month) -> Result<DaysCalendar<BiDay>, DayendarError> {
+            let n_days: u8 = checked_days_in_month(year, month)?;
+            let mut days_calendar: Vec<(Year, Month, Vec<BiDay>)> = Vec::new();
+            let vec: Vec<BiDay> = generate_vec_days(n_days as usize, BiDay::One);
+            days_calendar.push((year, month, vec));
+            Ok(DaysCalendar { days_calendar })
         }
-        /// Creates a new DaysCalendar from raw data
+        /// Creates a new DaysCalendar from raw data, without any validation
         pub fn new(data: Vec<(Year, Month, Vec<BiDay>)>) -> Self {
             Self { days_calendar: data }
         }
+
+        /// Creates a new DaysCalendar from raw data that must already be in canonical form
+        pub fn try_new(data: Vec<(Year, Month, Vec<BiDay>)>) -> Result<Self, DayendarError> {
+            let calendar = Self { days_calendar: data };
+            calendar.validate()?;
+            Ok(calendar)
+        }
+
+        /// Checks that the calendar is in canonical form
+        pub fn validate(&self) -> Result<(), DayendarError> {
+            for (year, month, days) in &self.days_calendar {
+                let n_days = checked_days_in_month(*year, *month)?;
+                if days.len() != n_days as usize {
+                    return Err(DayendarError::InvalidMonthLength {
+                        year: *year, month: *month, expected: n_days, found: days.len(),
+                    });
+                }
+            }
+            for pair in self.days_calendar.windows(2) {
+                match (pair[0].0, pair[0].1).cmp(&(pair[1].0, pair[1].1)) {
+                    std::cmp::Ordering::Equal => {
+                        return Err(DayendarError::DuplicatedMonth { year: pair[0].0, month: pair[0].1 });
+                    },
+                    std::cmp::Ordering::Greater => return Err(DayendarError::UnsortedCalendar),
+                    std::cmp::Ordering::Less => {},
+                }
+            }
+            Ok(())
+        }
+
+        /// Determines whether the calendar is in canonical form
+        pub fn is_canonical(&self) -> bool {
+            self.validate().is_ok()
+        }
+
+        /// Brings the calendar to canonical form: months are sorted, duplicated months are
+        /// merged with the OR operator and days are padded or truncated to the month length.
+        /// Months with an invalid year are dropped.
+        pub fn normalize(&self) -> DaysCalendar<BiDay> {
+            if self.is_canonical() {
+                return self.clone();
+            }
+            resume(self, or_biday_operation)
+        }
         /// Create an empty DaysCalendar
         pub fn empty() -> DaysCalendar<BiDay> {
             DaysCalendar {
@@ -66,36 +219,39 @@ pub mod calendar {
 
         /// Combines two DaysCalendar types based on the OR operator
         pub fn or(&self, other: &Self) -> DaysCalendar<BiDay> {
-
-            let combined_days: DaysCalendar<BiDay> = self.append(other);
-            resume(&combined_days, or_biday_operation) 
-        
+            merge_join(&[self, other], or_biday_operation)
         }
 
         /// Combines two DaysCalendar types based on the AND operator
         pub fn and(&self, other: &Self) -> DaysCalendar<BiDay> {
-            let combined_days: DaysCalendar<BiDay> = self.append(other);
-            resume(&combined_days, and_biday_operation)
+            merge_join(&[self, other], and_biday_operation)
         }
 
         /// Combines two DaysCalendar types based on the SUSTRACT operator
         pub fn minus(&self, other: &Self) -> DaysCalendar<BiDay> {
-            let combined_days: DaysCalendar<BiDay> = self.append(other);
-            resume(&combined_days, minus_biday_operation)
+            merge_join(&[self, other], minus_biday_operation)
         }
 
         /// Combines two DaysCalendar types based on calendar matches
         pub fn r#match(&self, other: &Self) -> DaysCalendar<BiDay> {
-            let combined_days: DaysCalendar<BiDay> = self.append(other);
-            resume(&combined_days, match_biday_operation)
+            merge_join(&[self, other], match_biday_operation)
         }
 
         /// Combines two DaysCalendar types based on calendar mismatches
         pub fn nomatch(&self, other: &Self) -> DaysCalendar<BiDay> {
-            let combined_days: DaysCalendar<BiDay> = self.append(other);
-            resume(&combined_days, nomatch_biday_operation)
+            merge_join(&[self, other], nomatch_biday_operation)
         }
         
+        /// Combines any number of DaysCalendar types based on the OR operator
+        pub fn or_all(calendars: &[&Self]) -> DaysCalendar<BiDay> {
+            merge_join(calendars, or_biday_operation)
+        }
+
+        /// Combines any number of DaysCalendar types based on the AND operator
+        pub fn and_all(calendars: &[&Self]) -> DaysCalendar<BiDay> {
+            merge_join(calendars, and_biday_operation)
+        }
+
         /// Gets the days of a given month as BiDay
         pub fn get_days(&self, year: Year, month: Month) -> Option<&Vec<BiDay>> {
             self.days_calendar.iter().find_map(|(y, m, days)| {
@@ -107,74 +263,53 @@ pub mod calendar {
             })
         }
         
-        /// Finds the next day on the calendar after a given date
-        pub fn next_day(&self, year: Year, month: Month, day: Day) -> Option<Date> {
-            let month_time = month.to_time_month().ok()?;
-            let reference_date: Date = Date::from_calendar_date(year.into(), month_time, day).ok()?;
+        /// Iterates lazily over the dates included in the calendar
+        pub fn dates(&self) -> Dates<'_> {
+            Dates::new(self)
+        }
 
-            let dates: Vec<Date> = to_date(self.clone());
+        /// Iterates lazily over the dates included in the calendar on or after a given date
+        pub fn dates_from(&self, date: Date) -> Dates<'_> {
+            let mut dates = Dates::new(self);
+            if self.is_sorted() {
+                dates.front = dates.position(date);
+            }
+            dates.from = Some(date);
+            dates
+        }
 
-            let mut next_date: Option<Date> = None;
-            for date in dates.into_iter() {
-                if date > reference_date {
-                    next_date = Some(date);
-                    break;
-                }
+        /// Iterates lazily over the dates included in the calendar strictly before a given date
+        pub fn dates_before(&self, date: Date) -> Dates<'_> {
+            let mut dates = Dates::new(self);
+            if self.is_sorted() {
+                dates.back = dates.position(date);
             }
+            dates.before = Some(date);
+            dates
+        }
 
-            next_date
+        /// Finds the next day on the calendar after a given date
+        pub fn next_day(&self, year: Year, month: Month, day: Day) -> Option<Date> {
+            let reference_date: Date = checked_date(year, month, day).ok()?;
+            self.dates_from(reference_date.next_day()?).next()
         }
 
         /// Finds the previous day in the calendar before a given date 
         pub fn previous_day(&self, year: Year, month: Month, day: Day) -> Option<Date> {
-            let month_time = month.to_time_month().ok()?;
-            let reference_date: Date = Date::from_calendar_date(year.into(), month_time, day).ok()?;
-
-            let dates: Vec<Date> = to_date(self.clone());
-
-            let mut previous_date = None;
-            for date in dates.into_iter().rev() {
-                if date < reference_date {
-                    previous_date = Some(date);
-                    break;
-                }
-            }
-
-            previous_date
+            let reference_date: Date = checked_date(year, month, day).ok()?;
+            self.dates_before(reference_date).next_back()
         }
 
         /// Searches for the n-th day after/before a given date
-        pub fn seek_nth_day(&self, year: Year, month: Month, day: Day, mut n: isize) -> Result<Option<Date>, String> {
-            let month_time = month.to_time_month().map_err(|e| e.to_string())?;
-            let reference_date: Date = Date::from_calendar_date(year.into(), month_time, day)
-                .map_err(|e| e.to_string())?;
-        
-            let dates: Vec<Date> = to_date(self.clone());
+        pub fn seek_nth_day(&self, year: Year, month: Month, day: Day, n: isize) -> Result<Option<Date>, DayendarError> {
+            let reference_date: Date = checked_date(year, month, day)?;
         
             let target_date = match n.cmp(&0) {
-                std::cmp::Ordering::Greater => {
-                    dates.into_iter()
-                        .find(|&date| {
-                            if date > reference_date {
-                                n -= 1;
-                                n == 0
-                            } else {
-                                false
-                            }
-                        })
-                },
-                std::cmp::Ordering::Less => {
-                    dates.into_iter()
-                        .rev()
-                        .find(|&date| {
-                            if date < reference_date {
-                                n += 1;
-                                n == 0
-                            } else {
-                                false
-                            }
-                        })
+                std::cmp::Ordering::Greater => match reference_date.next_day() {
+                    Some(next_date) => self.dates_from(next_date).nth(n.unsigned_abs() - 1),
+                    None => None,
                 },
+                std::cmp::Ordering::Less => self.dates_before(reference_date).nth_back(n.unsigned_abs() - 1),
                 std::cmp::Ordering::Equal => Some(reference_date),
             };
         
@@ -182,7 +317,7 @@ pub mod calendar {
         }
 
         /// Filter a calendar by keeping only the specified days of the week
-        pub fn and_weekdays(&self, weekdays: super::HashSet<Weekday>) -> Result<DaysCalendar<BiDay>, &'static str> {
+        pub fn and_weekdays(&self, weekdays: super::HashSet<Weekday>) -> Result<DaysCalendar<BiDay>, DayendarError> {
             let mut filtered_days_calendar: Vec<(u16, Month, Vec<BiDay>)> = vec![];
         
             for (year, month, days) in &self.days_calendar {
@@ -190,9 +325,7 @@ pub mod calendar {
         
                 for (day_index, bit) in days.iter().enumerate() {
                     let day_num = day_index as u8 + 1;
-                    let month_time = month.to_time_month()?;
-                    let date = Date::from_calendar_date((*year).into(), month_time, day_num)
-                        .map_err(|_| "Invalid date")?;
+                    let date = checked_date(*year, *month, day_num)?;
                     let weekday: Weekday = date.weekday();
         
                     if *bit == BiDay::One && weekdays.contains(&weekday) {
@@ -212,16 +345,14 @@ pub mod calendar {
         
 
         /// Add specified days of the `week` to a `DaysCalendar`
-        pub fn or_weekdays(&self, weekdays: super::HashSet<Weekday>) -> Result<DaysCalendar<BiDay>, &'static str> {
+        pub fn or_weekdays(&self, weekdays: super::HashSet<Weekday>) -> Result<DaysCalendar<BiDay>, DayendarError> {
             let mut filtered_days_calendar: Vec<(u16, Month, Vec<BiDay>)> = vec![];
         
             for (year, month, days) in &self.days_calendar {
                 let mut filtered_days = vec![];
                 for (day_index, bit) in days.iter().enumerate() {
                     let day_num = day_index as u8 + 1;
-                    let month_time = month.to_time_month()?;
-                    let date: Date = Date::from_calendar_date((*year).into(), month_time, day_num)
-                        .map_err(|_| "Invalid date")?;
+                    let date: Date = checked_date(*year, *month, day_num)?;
                     let weekday: Weekday = date.weekday();
         
                     if *bit == BiDay::One || (*bit == BiDay::Zero && weekdays.contains(&weekday)) {
@@ -241,12 +372,11 @@ pub mod calendar {
         
 
         /// Filters a calendar by keeping only the specified ISO weeks
-        pub fn and_iso_weeks(&self, weeks: Vec<u32>) -> Result<DaysCalendar<BiDay>, &'static str> {
+        pub fn and_iso_weeks(&self, weeks: Vec<u32>) -> Result<DaysCalendar<BiDay>, DayendarError> {
             let mut new_calendar: DaysCalendar<BiDay> = self.clone();
         
             for (year, month, days) in new_calendar.days_calendar.iter_mut() {
-                let first_day_of_month = Date::from_calendar_date(*year as i32, month.to_time_month()?, 1)
-                    .map_err(|_| "Invalid date")?;
+                let first_day_of_month = checked_date(*year, *month, 1)?;
         
                 for (day_index, day) in days.iter_mut().enumerate() {
                     let date = first_day_of_month + Duration::days(day_index as i64);
@@ -263,12 +393,11 @@ pub mod calendar {
         
 
         /// Adds specified ISO `weeks` to a `DaysCalendar` type
-        pub fn or_iso_weeks(&self, weeks: Vec<u32>) -> Result<DaysCalendar<BiDay>, &'static str> {
+        pub fn or_iso_weeks(&self, weeks: Vec<u32>) -> Result<DaysCalendar<BiDay>, DayendarError> {
             let mut new_calendar: DaysCalendar<BiDay> = self.clone();
         
             for (year, month, days) in new_calendar.days_calendar.iter_mut() {
-                let first_day_of_month = Date::from_calendar_date(*year as i32, month.to_time_month()?, 1)
-                    .map_err(|_| "Invalid date")?;
+                let first_day_of_month = checked_date(*year, *month, 1)?;
         
                 for (day_index, day) in days.iter_mut().enumerate() {
                     let date = first_day_of_month + Duration::days(day_index as i64);
@@ -284,12 +413,11 @@ pub mod calendar {
         }        
         
         /// Excludes specific ISO `weeks` of type `DaysCalendar`
-        pub fn not_iso_weeks(&self, weeks: Vec<u32>) -> Result<DaysCalendar<BiDay>, &'static str> {
+        pub fn not_iso_weeks(&self, weeks: Vec<u32>) -> Result<DaysCalendar<BiDay>, DayendarError> {
             let mut new_calendar: DaysCalendar<BiDay> = self.clone();
         
             for (year, month, days) in new_calendar.days_calendar.iter_mut() {
-                let first_day_of_month = Date::from_calendar_date(*year as i32, month.to_time_month()?, 1)
-                    .map_err(|_| "Invalid date")?;
+                let first_day_of_month = checked_date(*year, *month, 1)?;
         
                 for (day_index, day) in days.iter_mut().enumerate() {
                     let date: Date = first_day_of_month + Duration::days(day_index as i64);
@@ -306,17 +434,74 @@ pub mod calendar {
             Ok(new_calendar)
         }        
         
+        /// Filter a calendar by keeping only the n-th occurrence of a weekday in the given months,
+        /// negative `n` counts from the end of the month
+        pub fn and_nth_weekday(&self, n: i8, weekday: Weekday, months: &MonthSpec) -> Result<DaysCalendar<BiDay>, DayendarError> {
+            let mut new_calendar: DaysCalendar<BiDay> = self.clone();
+
+            for (year, month, days) in new_calendar.days_calendar.iter_mut() {
+                let nth_day: Option<Day> = if months.contains(*month) {
+                    nth_weekday_of_month(*year, *month, n, weekday)?
+                } else {
+                    None
+                };
+                for (day_index, day) in days.iter_mut().enumerate() {
+                    if nth_day != Some(day_index as Day + 1) {
+                        *day = BiDay::Zero;
+                    }
+                }
+            }
+
+            Ok(new_calendar)
+        }
+
+        /// Moves the holidays of the calendar that fall on a `weekend` day to the day where they are observed.
+        /// Holidays on weekdays are kept, and observed days may fall in months that are not in the calendar.
+        pub fn observe(&self, weekend: super::HashSet<Weekday>, rule: ObservanceRule) -> DaysCalendar<BiDay> {
+            let holidays: super::BTreeSet<Date> = self.dates().collect();
+            let is_weekday = |date: &Date| !weekend.contains(&date.weekday());
+            let forward = |date: Date| std::iter::successors(date.next_day(), |date| date.next_day());
+            let backward = |date: Date| std::iter::successors(date.previous_day(), |date| date.previous_day());
+
+            let mut observed: super::BTreeSet<Date> = holidays.iter().copied().filter(is_weekday).collect();
+            for &holiday in holidays.iter().filter(|date| !is_weekday(date)) {
+                let target: Option<Date> = match rule {
+                    ObservanceRule::NearestWeekday => {
+                        let next = forward(holiday).take(7).position(|date| is_weekday(&date));
+                        let previous = backward(holiday).take(7).position(|date| is_weekday(&date));
+                        match (next, previous) {
+                            (Some(next), Some(previous)) if previous < next => backward(holiday).nth(previous),
+                            (Some(next), _) => forward(holiday).nth(next),
+                            (None, _) => None,
+                        }
+                    },
+                    ObservanceRule::NextWeekday => forward(holiday).take(7).find(is_weekday),
+                    ObservanceRule::PreviousWeekday => backward(holiday).take(7).find(is_weekday),
+                    ObservanceRule::NextNonHoliday => forward(holiday)
+                        .take(holidays.len() + observed.len() + 7)
+                        .find(|date| is_weekday(date) && !holidays.contains(date) && !observed.contains(date)),
+                };
+                observed.extend(target);
+            }
+
+            let observed: Vec<Date> = observed.into_iter()
+                .filter(|date| (MIN_DATE..=MAX_DATE).contains(date))
+                .collect();
+            let observed: DaysCalendar<BiDay> = from_date(observed)
+                .and_then(from_day)
+                .unwrap_or_else(|_| DaysCalendar::empty());
+            self.clone().zeros().or(&observed)
+        }
+
         /// Excludes specific `weekdays` of type `DaysCalendar`
-        pub fn not_weekdays(&self, weekdays: super::HashSet<Weekday>) -> Result<DaysCalendar<BiDay>, &'static str> {
+        pub fn not_weekdays(&self, weekdays: super::HashSet<Weekday>) -> Result<DaysCalendar<BiDay>, DayendarError> {
             let mut filtered_days_calendar: Vec<(u16, Month, Vec<BiDay>)> = vec![];
         
             for (year, month, days) in &self.days_calendar {
                 let mut filtered_days = vec![];
                 for (day_index, bit) in days.iter().enumerate() {
                     let day_num = day_index as u8 + 1;
-                    let month_time = month.to_time_month()?;
-                    let date = Date::from_calendar_date((*year).into(), month_time, day_num)
-                        .map_err(|_| "Invalid date")?;
+                    let date = checked_date(*year, *month, day_num)?;
                     let weekday = date.weekday();
         
                     if *bit == BiDay::One && !weekdays.contains(&weekday) {
@@ -404,6 +589,20 @@ pub mod calendar {
 
     }
 
+    /// Rules to move a holiday that falls on a weekend day to the day where it is observed
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum ObservanceRule {
+        /// The closest weekday, e.g., Saturday moves to Friday and Sunday to Monday
+        NearestWeekday,
+        /// The next weekday
+        NextWeekday,
+        /// The next weekday that is not a holiday or an already observed holiday
+        NextNonHoliday,
+        /// The previous weekday
+        PreviousWeekday,
+    }
+
     /// Replicates a `pattern` of days in a `DaysCalendar`
     pub fn replicate<T>(pattern: &[BiDay], calendar: DaysCalendar<BiDay>) -> DaysCalendar<BiDay>
     where T: Clone
@@ -427,10 +626,44 @@ pub mod calendar {
         }
     }
 
-    impl<T: PartialEq> PartialEq for DaysCalendar<T> {
-        /// Determines if two DaysCalendar types are the same
+    impl PartialEq for DaysCalendar<BiDay> {
+        /// Determines if two DaysCalendar types describe the same months and days,
+        /// regardless of the order, duplication or length of their entries
+        fn eq(&self, other: &Self) -> bool {
+            if self.is_canonical() && other.is_canonical() {
+                self.days_calendar == other.days_calendar
+            } else {
+                self.normalize().days_calendar == other.normalize().days_calendar
+            }
+        }
+    }
+
+    impl Eq for DaysCalendar<BiDay> {}
+
+    impl Hash for DaysCalendar<BiDay> {
+        /// Hashes the canonical form, so that equal calendars have the same hash
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            if self.is_canonical() {
+                self.days_calendar.hash(state);
+            } else {
+                self.normalize().days_calendar.hash(state);
+            }
+        }
+    }
+
+    impl PartialEq for DaysCalendar<Day> {
+        /// Determines if two DaysCalendar types describe the same months and days,
+        /// regardless of the order or duplication of their entries
         fn eq(&self, other: &Self) -> bool {
-            self.days_calendar == other.days_calendar
+            self.normalize_days().days_calendar == other.normalize_days().days_calendar
+        }
+    }
+
+    impl Eq for DaysCalendar<Day> {}
+
+    impl Hash for DaysCalendar<Day> {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.normalize_days().days_calendar.hash(state);
         }
     }
 
@@ -471,10 +704,7 @@ pub mod calendar {
 
     /// Extracts the years and months present in a calendar
     #[allow(dead_code)]
-    pub fn extract_year_month_calendar<T: Clone>(dc: &DaysCalendar<T>) -> Vec<(Year, Month)>
-    where 
-        T: Clone,
-    {
+    pub fn extract_year_month_calendar<T: Clone>(dc: &DaysCalendar<T>) -> Vec<(Year, Month)> {
         let mut result: Vec<(u16, Month)> = Vec::new();
         for (y, m, _) in &dc.days_calendar {
             result.push((*y as Year, *m as Month));
@@ -491,6 +721,13 @@ pub mod calendar {
         month: Month,
         dc: &DaysCalendar<T>,
     ) -> Option<Vec<T>> {
+        // Binary search is only valid on sorted calendars
+        if !dc.is_sorted() {
+            return dc.days_calendar
+                .iter()
+                .find(|(y, m, _)| *y == year && *m == month)
+                .map(|(_, _, days)| days.clone());
+        }
         // Finds the index of the element matching the given year and month
         let index: Result<usize, usize> = dc.days_calendar.binary_search_by_key(&(year, month), |(y, m, _)| (*y, *m));
         // If the element is found, the corresponding vector of days is returned.
@@ -501,7 +738,8 @@ pub mod calendar {
         }
     }
 
-    /// Summarises or consolidates two DaysCalendar types based on a given operator
+    /// Summarises or consolidates two DaysCalendar types based on a given operator.
+    /// Months with a year out of range can not be normalized and are dropped.
     pub fn resume<F>(calendar: &DaysCalendar<BiDay>, op: F) -> DaysCalendar<BiDay>
     where
         F: Fn(BiDay, BiDay) -> BiDay,
@@ -510,30 +748,89 @@ pub mod calendar {
         days_calendar.sort_by_key(|&(year, month, _)| (year, month));
 
         let mut res: Vec<(u16, Month, Vec<BiDay>)> = Vec::new();
-        let mut curr_year: Year = 0;
-        let mut curr_month: Option<Month> = None;
+        let mut curr: Option<(Year, Month)> = None;
         let mut curr_vec: Vec<BiDay> = Vec::new();
-        let mut curr_val: &Vec<BiDay> = &curr_vec;
 
         for &(year, month, ref vec) in &days_calendar {
-            if year != curr_year || month != curr_month.unwrap() {
-                if !curr_vec.is_empty() {
-                    res.push((curr_year, curr_month.unwrap(), curr_val.to_vec()));
-                }
-                curr_year = year;
-                curr_month = Some(month);
-                curr_vec = normalize_biday(vec, curr_year, curr_month.unwrap()).clone();
-                curr_val = &curr_vec;
+            let days: Vec<BiDay> = match normalize_biday(vec, year, month) {
+                Ok(days) => days,
+                Err(_) => continue,
+            };
+            if curr == Some((year, month)) {
+                curr_vec = curr_vec.iter().zip(days.iter()).map(|(a, b)| op(*a, *b)).collect::<Vec<BiDay>>();
             } else {
-                let curr_val_norm: &Vec<BiDay> = &normalize_biday(curr_val, curr_year, curr_month.unwrap());
-                let new_val: Vec<BiDay> = curr_val_norm.iter().zip(normalize_biday(vec, curr_year, curr_month.unwrap()).iter()).map(|(a, b)| op(*a, *b)).collect::<Vec<BiDay>>();
-                curr_vec = new_val;
-                curr_val = &curr_vec;
+                if let Some((curr_year, curr_month)) = curr {
+                    res.push((curr_year, curr_month, curr_vec));
+                }
+                curr = Some((year, month));
+                curr_vec = days;
             }
         }
 
-        if !curr_vec.is_empty() {
-            res.push((curr_year, curr_month.unwrap(), curr_val.to_vec()));
+        if let Some((curr_year, curr_month)) = curr {
+            res.push((curr_year, curr_month, curr_vec));
+        }
+
+        DaysCalendar { days_calendar: res }
+    }
+
+    /// Combines several DaysCalendar types month by month with a linear merge-join.
+    ///
+    /// The entries of a month are combined in the order of the calendars, months present
+    /// in only one calendar are kept, and every month is normalized to its number of days.
+    /// Months with a year out of range are dropped.
+    /// The result is the same as `resume` on the appended calendars, but sorted calendars are
+    /// combined in a single pass without cloning or sorting. Calendars that are not sorted
+    /// fall back to `resume`.
+    pub fn merge_join<F>(calendars: &[&DaysCalendar<BiDay>], op: F) -> DaysCalendar<BiDay>
+    where
+        F: Fn(BiDay, BiDay) -> BiDay,
+    {
+        if !calendars.iter().all(|calendar| calendar.is_sorted()) {
+            let combined_days: DaysCalendar<BiDay> = calendars
+                .iter()
+                .fold(DaysCalendar::empty(), |acc, calendar| acc.append(calendar));
+            return resume(&combined_days, op);
+        }
+
+        let total: usize = calendars.iter().map(|calendar| calendar.days_calendar.len()).sum();
+        let mut res: Vec<(Year, Month, Vec<BiDay>)> = Vec::with_capacity(total);
+        let mut cursors: Vec<usize> = vec![0; calendars.len()];
+
+        loop {
+            // Next month to emit is the smallest month at the head of any calendar
+            let next: Option<(Year, Month)> = calendars
+                .iter()
+                .zip(cursors.iter())
+                .filter_map(|(calendar, &cursor)| calendar.days_calendar.get(cursor).map(|(y, m, _)| (*y, *m)))
+                .min();
+            let (year, month) = match next {
+                Some(year_month) => year_month,
+                None => break,
+            };
+
+            let mut days: Option<Vec<BiDay>> = None;
+            for (calendar, cursor) in calendars.iter().zip(cursors.iter_mut()) {
+                match calendar.days_calendar.get(*cursor) {
+                    Some((y, m, other)) if *y == year && *m == month => {
+                        days = match days {
+                            None => normalize_biday(other, year, month).ok(),
+                            Some(mut acc) => {
+                                for (index, day) in acc.iter_mut().enumerate() {
+                                    *day = op(*day, other.get(index).copied().unwrap_or(BiDay::Zero));
+                                }
+                                Some(acc)
+                            },
+                        };
+                        *cursor += 1;
+                    },
+                    _ => {},
+                }
+            }
+
+            if let Some(days) = days {
+                res.push((year, month, days));
+            }
         }
 
         DaysCalendar { days_calendar: res }
@@ -566,28 +863,31 @@ pub mod calendar {
         result
     }
 
-    /// Convert a DaysCalendar from Day days to BiDay days
+    /// Convert a DaysCalendar from Day days to BiDay days.
+    /// Fails when a year is out of range or a day does not exist in its month.
     #[allow(dead_code)]
-    pub fn from_day(calendar: DaysCalendar<Day>) -> DaysCalendar<BiDay> {
+    pub fn from_day(calendar: DaysCalendar<Day>) -> Result<DaysCalendar<BiDay>, DayendarError> {
         let mut result: DaysCalendar<BiDay> = DaysCalendar {
             days_calendar: Vec::new(),
         };
     
         for (year, month, days) in calendar.days_calendar {
-            let n_days = days_in_month(year, month).unwrap_or(0);
+            let n_days = checked_days_in_month(year, month)?;
           
             let mut bi_days: Vec<BiDay> = vec![BiDay::Zero; n_days as usize];
           
             for day in days {
-              let idx = day - 1;
+              if day == 0 || day > n_days {
+                  return Err(DayendarError::InvalidDate { year: year.into(), month: month.to_index(), day });
+              }
               
-              bi_days[idx as usize] = BiDay::One;
+              bi_days[day as usize - 1] = BiDay::One;
             }
           
             result.days_calendar.push((year, month, bi_days)); 
           }
 
-        result
+        Ok(result)
     }
 
     impl DaysCalendar<Day> {
@@ -598,6 +898,30 @@ pub mod calendar {
             }
         }
 
+        /// Brings the calendar to canonical form: months are sorted, duplicated months are
+        /// merged, days are sorted without duplicates and days outside the month are dropped.
+        pub fn normalize_days(&self) -> DaysCalendar<Day> {
+            let mut year_month_map: super::BTreeMap<(Year, Month), Vec<Day>> = super::BTreeMap::new();
+            for (year, month, days) in &self.days_calendar {
+                if let Some(n_days) = days_in_month(*year, *month) {
+                    year_month_map
+                        .entry((*year, *month))
+                        .or_default()
+                        .extend(days.iter().filter(|&&day| day >= 1 && day <= n_days));
+                }
+            }
+
+            let days_calendar = year_month_map
+                .into_iter()
+                .map(|((year, month), mut days)| {
+                    days.sort_unstable();
+                    days.dedup();
+                    (year, month, days)
+                })
+                .collect();
+            DaysCalendar { days_calendar }
+        }
+
         /// Sort days_calendar by Year and Month
         pub fn sort(&mut self) {
             self.days_calendar.sort_unstable_by(|(y1, m1, _), (y2, m2, _)| {
@@ -638,42 +962,27 @@ pub mod calendar {
 
     //use std::convert::TryInto;
 
-    /// Converts a `DaysCalendar` into a `Date` vector
+    /// Converts a `DaysCalendar` into a `Date` vector, see `DaysCalendar::dates` for a lazy alternative
     #[allow(dead_code)]
     pub fn to_date(calendar: DaysCalendar<BiDay>) -> Vec<Date> {
-        let mut dates: Vec<Date> = Vec::new();
-    
-        for (year, month, days) in calendar.days_calendar {
-            let year_num: Year = year;
-            let month_num = match month.to_time_month() {
-                Ok(m) => m,
-                Err(_) => continue, // If month conversion fails, skip this month
-            };
-    
-            for (day_index, day) in days.iter().enumerate() {
-                if *day == BiDay::One {
-                    let day_num = day_index as u8 + 1;
-                    if let Ok(date) = Date::from_calendar_date(year_num.into(), month_num, day_num) {
-                        dates.push(date);
-                    }
-                    // If date creation fails, just skip this day
-                }
-            }
-        }
-    
-        dates
-    }    
+        calendar.dates().collect()
+    }
 
-    /// Converts a vector of `Date` to a `DaysCalendar` type
+    /// Converts a vector of `Date` to a `DaysCalendar` type.
+    /// Fails when a date is outside the supported years, from 1 to 9999.
     #[allow(dead_code)]
-    pub fn from_date(dates: Vec<Date>) -> DaysCalendar<Day> {
+    pub fn from_date(dates: Vec<Date>) -> Result<DaysCalendar<Day>, DayendarError> {
         // Create an orderly map for storing years, months and days
         let mut year_month_map: super::BTreeMap<(Year, u8), Vec<Day>> = super::BTreeMap::new();
     
         // Iterate over the given dates and add them to the map
         for date in dates {
+            let year: Year = match Year::try_from(date.year()) {
+                Ok(year) if year >= 1 => year,
+                _ => return Err(DayendarError::YearOutOfRange(date.year())),
+            };
             let entry = year_month_map
-                .entry((date.year().try_into().unwrap(), date.month().into()))
+                .entry((year, date.month().into()))
                 .or_default(); //.or_insert_with(Vec::new);
             let day = date.day() as Day;
             if !entry.contains(&day) {
@@ -690,11 +999,15 @@ pub mod calendar {
         // Convert map to a vector of tuples (Year, Month, Vec<Day>)
         let days_calendar: Vec<(Year, Month, Vec<Day>)> = year_month_map
             .into_iter()
-            .map(|((year, month_number), days)| (year, Month::from_index(month_number).unwrap(), days))
-            .collect();
+            .map(|((year, month_number), days)| {
+                Month::from_index(month_number)
+                    .map(|month| (year, month, days))
+                    .ok_or(DayendarError::InvalidMonth(month_number))
+            })
+            .collect::<Result<_, _>>()?;
     
         // Return DaysCalendar<Day>
-        DaysCalendar { days_calendar }
+        Ok(DaysCalendar { days_calendar })
     }
 
     /// Groups calendar days by year and month
@@ -748,8 +1061,17 @@ pub mod abstracto {
 
     use crate::types::{
         Year, Month, Day, BiDay, Weekday,
-        Date
+        Date, DateSpan, Duration, Computus, CalendarSystem, MonthSpec, YearSpec
     };
+    use crate::error::DayendarError;
+    use crate::utils::{checked_days_in_month, checked_date, easter_sunday, month_bounds, nth_weekday_of_month};
+    use crate::binary::replicate_pattern;
+    use crate::calendar::{DaysCalendar, ObservanceRule};
+    use crate::cron::CronExpression;
+    use crate::rrule::RRule;
+    use crate::hijri::{self, HijriAdjustments};
+    use crate::hebrew;
+    use crate::chinese::ChineseFestival;
    
     /// Enumeration of abstract calendar patterns
     #[derive(Debug, Clone)]
@@ -804,24 +1126,59 @@ pub mod abstracto {
     
         /// A pattern defined by a "cron" expression
         CronPattern(String),
+
+        /// A pattern defined by an RFC 5545 recurrence rule with its DTSTART, e.g.,
+        /// `DTSTART:20240109 RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU`
+        RRule(String),
+
+        /// Moveable feasts relative to Easter Sunday, e.g., Good Friday is the offset -2
+        EasterOffset { offset: i16, computus: Computus },
+
+        /// The n-th occurrence of a weekday in the given months, negative `n` counts from the end
+        /// of the month, e.g., Memorial Day is the weekday Monday with `n` -1 in May
+        NthWeekdayOfMonth { n: i8, weekday: Weekday, months: MonthSpec },
+
+        /// The same day of a month every year, e.g., Christmas is December 25, February 29 only
+        /// falls in leap years
+        AnnualDate { month: Month, day: Day },
+
+        /// Every day of the given years, e.g., to limit a holiday to the years in which it is in force
+        Years(YearSpec),
+
+        /// Days `offset` days after a day of a month of the tabular Islamic calendar, `day` 0 selects
+        /// every day of the month, e.g., Eid al-Fitr is month 10 and day 1, Ramadan is month 9 and day 0
+        Hijri { month: u8, day: u8, offset: i16, adjustments: HijriAdjustments },
+
+        /// A day of a month of the Hebrew calendar, months are numbered from Nisan, e.g., Rosh Hashanah
+        /// is month 7 and day 1 and Passover is month 1 and day 15
+        Hebrew { month: u8, day: u8 },
+
+        /// A festival of the Chinese lunisolar calendar, from 1900 to 2100, years outside that range
+        /// have no festival
+        ChineseFestival(ChineseFestival),
+
+        /// A day of a month of a foreign solar calendar, `day` 0 selects every day of the month, e.g.,
+        /// Nowruz is `Persian` month 1 and day 1
+        ForeignDate { system: CalendarSystem, month: u8, day: u8 },
     }
 
     /// Enum for the abstract calendar, which can be a single pattern or a combined operation
     /// Example of use for the calendar "working days":
     /// Not(Or(Or(FestivosFijos, FestivosFlotantes), FinesDeSemana))
-    #[derive(Debug)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum AbstractCalendar {
         Pattern(CalendarPattern),
         Operation(CalendarOperation),
     }
 
     /// Enum for operators and transformations that can be applied to patterns
-    #[derive(Debug)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum CalendarOperation {
         Invert(Box<AbstractCalendar>),                       // Inversión
         And(Box<AbstractCalendar>, Box<AbstractCalendar>),   // Intersección
         Or(Box<AbstractCalendar>, Box<AbstractCalendar>),    // Unión
         Minus(Box<AbstractCalendar>, Box<AbstractCalendar>), // Sustracción
+        Observed(Box<AbstractCalendar>, Vec<Weekday>, ObservanceRule), // Días observados
     }
 
     
@@ -836,11 +1193,34 @@ pub mod abstracto {
             AbstractCalendar::Operation(CalendarOperation::And(Box::new(self), Box::new(other)))
         }
     
+        // Function to subtract another abstract calendar from this one
+        pub fn minus(self, other: AbstractCalendar) -> AbstractCalendar {
+            AbstractCalendar::Operation(CalendarOperation::Minus(Box::new(self), Box::new(other)))
+        }
+
         // Function to negate (NOT) or invert an abstract calendar
         pub fn invert(self) -> AbstractCalendar {
             AbstractCalendar::Operation(CalendarOperation::Invert(Box::new(self)))
         }
 
+        // Function to move the holidays of this calendar that fall on a weekend to their observed day
+        pub fn observed(self, weekend: Vec<Weekday>, rule: ObservanceRule) -> AbstractCalendar {
+            AbstractCalendar::Operation(CalendarOperation::Observed(Box::new(self), weekend, rule))
+        }
+
+        /// Materializes the abstract calendar into a `DaysCalendar` for every month of a `DateSpan`
+        pub fn evaluate(&self, span: &DateSpan) -> Result<DaysCalendar<BiDay>, DayendarError> {
+            self.evaluate_months(&sorted_year_month(span))
+        }
+
+        /// Materializes the abstract calendar for an ordered list of year months
+        pub fn evaluate_months(&self, year_months: &[(Year, Month)]) -> Result<DaysCalendar<BiDay>, DayendarError> {
+            match self {
+                AbstractCalendar::Pattern(pattern) => pattern.evaluate_months(year_months),
+                AbstractCalendar::Operation(op) => op.evaluate_months(year_months),
+            }
+        }
+
         fn pretty_print(&self, f: &mut fmt::Formatter, indent: usize) -> fmt::Result {
             match self {
                 AbstractCalendar::Pattern(pattern) => {
@@ -859,18 +1239,65 @@ pub mod abstracto {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             match self {
                 AbstractCalendar::Pattern(pattern) => write!(f, "{}", pattern),
+                AbstractCalendar::Operation(op) if f.alternate() => write!(f, "{:#}", op),
                 AbstractCalendar::Operation(op) => write!(f, "{}", op),
             }
         }
     }
     
+    /// The alternate form `{:#}` writes the operation on a single line
     impl Display for CalendarOperation {
         fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-            self.pretty_print(f, 0)
+            if !f.alternate() {
+                return self.pretty_print(f, 0);
+            }
+            match self {
+                CalendarOperation::Invert(cal) => write!(f, "Invert({:#})", cal),
+                CalendarOperation::And(cal1, cal2) => write!(f, "And({:#}, {:#})", cal1, cal2),
+                CalendarOperation::Or(cal1, cal2) => write!(f, "Or({:#}, {:#})", cal1, cal2),
+                CalendarOperation::Minus(cal1, cal2) => write!(f, "Minus({:#}, {:#})", cal1, cal2),
+                CalendarOperation::Observed(cal, weekend, rule) => {
+                    write!(f, "Observed({:#}, [{}], {:?})", cal, join_list(weekend), rule)
+                },
+            }
         }
     }
 
     impl CalendarOperation {
+        /// Evaluates the operands and combines them with the corresponding `DaysCalendar` operator
+        pub fn evaluate_months(&self, year_months: &[(Year, Month)]) -> Result<DaysCalendar<BiDay>, DayendarError> {
+            match self {
+                CalendarOperation::Invert(cal) => {
+                    Ok(cal.evaluate_months(year_months)?.invert_biday())
+                },
+                CalendarOperation::And(cal1, cal2) => {
+                    Ok(cal1.evaluate_months(year_months)?.and(&cal2.evaluate_months(year_months)?))
+                },
+                CalendarOperation::Or(cal1, cal2) => {
+                    Ok(cal1.evaluate_months(year_months)?.or(&cal2.evaluate_months(year_months)?))
+                },
+                CalendarOperation::Minus(cal1, cal2) => {
+                    Ok(cal1.evaluate_months(year_months)?.minus(&cal2.evaluate_months(year_months)?))
+                },
+                CalendarOperation::Observed(cal, weekend, rule) => {
+                    // Holidays of the neighbouring months may be observed inside the requested ones
+                    let mut extended: Vec<(Year, Month)> = year_months.iter()
+                        .flat_map(|&(year, month)| [previous_month(year, month), Some((year, month)), next_month(year, month)])
+                        .flatten()
+                        .collect();
+                    extended.sort_unstable();
+                    extended.dedup();
+                    let observed = cal.evaluate_months(&extended)?.observe(weekend.iter().copied().collect(), *rule);
+                    let days_calendar = year_months.iter()
+                        .filter_map(|&(year, month)| {
+                            observed.get_days(year, month).map(|days| (year, month, days.clone()))
+                        })
+                        .collect();
+                    Ok(DaysCalendar { days_calendar })
+                },
+            }
+        }
+
         fn pretty_print(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
             match self {
                 CalendarOperation::Invert(op) => {
@@ -892,28 +1319,280 @@ pub mod abstracto {
                     op2.pretty_print(f, indent + 4)?;
                     write!(f, "\n{})", " ".repeat(indent))
                 }
+                CalendarOperation::Observed(op, weekend, rule) => {
+                    writeln!(f, "{}Observed(", " ".repeat(indent))?;
+                    op.pretty_print(f, indent + 4)?;
+                    writeln!(f, ",")?;
+                    writeln!(f, "{}[{}],", " ".repeat(indent + 4), join_list(weekend))?;
+                    write!(f, "{}{:?}\n{})", " ".repeat(indent + 4), rule, " ".repeat(indent))
+                }
             }
         }
     }
 
-    impl fmt::Display for CalendarPattern {
-        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    impl CalendarPattern {
+        /// Materializes the pattern into a `DaysCalendar` for every month of a `DateSpan`
+        pub fn evaluate(&self, span: &DateSpan) -> Result<DaysCalendar<BiDay>, DayendarError> {
+            self.evaluate_months(&sorted_year_month(span))
+        }
+
+        /// Materializes the pattern for an ordered list of year months
+        pub fn evaluate_months(&self, year_months: &[(Year, Month)]) -> Result<DaysCalendar<BiDay>, DayendarError> {
+            if let CalendarPattern::CronPattern(expression) = self {
+                // Parse the expression only once for the whole span
+                let cron: CronExpression = expression.parse()?;
+                return cron.evaluate_months(year_months);
+            }
+            if let CalendarPattern::RRule(rule) = self {
+                // Expand the rule only once for the whole span
+                let rule: RRule = rule.parse()?;
+                return rule.evaluate_months(year_months);
+            }
+
+            let mut days_calendar: Vec<(Year, Month, Vec<BiDay>)> = Vec::with_capacity(year_months.len());
+            for &(year, month) in year_months {
+                days_calendar.push((year, month, self.materialize(year, month)?));
+            }
+            Ok(DaysCalendar { days_calendar })
+        }
+
+        /// Materializes the pattern for a single year month
+        pub fn materialize(&self, year: Year, month: Month) -> Result<Vec<BiDay>, DayendarError> {
             match self {
-                CalendarPattern::Everyday => write!(f, "Everyday"),
+                CalendarPattern::None => month_from_predicate(year, month, |_, _| false),
+                CalendarPattern::Everyday => month_from_predicate(year, month, |_, _| true),
+                CalendarPattern::OddDays => month_from_predicate(year, month, |day, _| day % 2 == 1),
+                CalendarPattern::EvenDays => month_from_predicate(year, month, |day, _| day % 2 == 0),
                 CalendarPattern::Weekdays(weekdays) => {
-                    let weekdays_str: String = weekdays.iter()
-                        .map(|day: &Weekday| day.to_string())
-                        .collect::<Vec<String>>()
-                        .join(", ");
-                    write!(f, "Weekdays([{}])", weekdays_str)
+                    month_from_predicate(year, month, |_, date| weekdays.contains(&date.weekday()))
                 },
-                CalendarPattern::CustomDays(days) => {
-                    let days_str: String = days.iter()
-                        .map(|day| day.to_string())
-                        .collect::<Vec<String>>()
-                        .join(", ");
-                    write!(f, "CustomDays([{}])", days_str)
+                CalendarPattern::CustomWeekDays(weekdays) => {
+                    month_from_predicate(year, month, |_, date| weekdays.contains(&date.weekday().number_from_monday()))
+                },
+                CalendarPattern::CustomDays(days) => month_from_predicate(year, month, |day, _| days.contains(&day)),
+                CalendarPattern::CustomBiDay(pattern) => {
+                    let n_days = checked_days_in_month(year, month)?;
+                    if pattern.is_empty() {
+                        Ok(vec![BiDay::Zero; n_days as usize])
+                    } else {
+                        Ok(replicate_pattern(pattern, n_days as usize))
+                    }
+                },
+                CalendarPattern::SpecificDayOfMonth(n) => month_from_predicate(year, month, |day, _| day == *n),
+                CalendarPattern::SpecificWeekOfMonth(n) => {
+                    month_from_predicate(year, month, |day, date| week_of_month(day, date) == *n)
+                },
+                CalendarPattern::FixedHolidays(dates) | CalendarPattern::FloatingHolidays(dates) => {
+                    month_from_predicate(year, month, |_, date| dates.contains(&date))
+                },
+                CalendarPattern::Hebrew { month: hebrew_month, day } => {
+                    let (first, last): (Date, Date) = month_bounds(year, month)?;
+                    let dates = hebrew::gregorian_dates(*hebrew_month, *day, first, last)?;
+                    month_from_predicate(year, month, |_, date| dates.contains(&date))
+                },
+                CalendarPattern::ForeignDate { system, month: foreign_month, day } => {
+                    let (first, last): (Date, Date) = month_bounds(year, month)?;
+                    let dates = system.gregorian_dates(*foreign_month, *day, first, last)?;
+                    month_from_predicate(year, month, |_, date| dates.contains(&date))
+                },
+                CalendarPattern::ChineseFestival(festival) => {
+                    let feast: Option<Date> = festival.date_in(year, month)?;
+                    month_from_predicate(year, month, |_, date| Some(date) == feast)
+                },
+                CalendarPattern::SpecificWeekOfYear(week) => {
+                    month_from_predicate(year, month, |_, date| date.iso_week() as u32 == *week)
+                },
+                CalendarPattern::CustomFunction(function) => {
+                    month_from_predicate(year, month, |day, _| function(year, month, day) == BiDay::One)
+                },
+                CalendarPattern::CronPattern(expression) => {
+                    let cron: CronExpression = expression.parse()?;
+                    cron.materialize(year, month)
+                },
+                CalendarPattern::RRule(rule) => {
+                    let rule: RRule = rule.parse()?;
+                    rule.materialize(year, month)
+                },
+                CalendarPattern::EasterOffset { offset, computus } => {
+                    let feasts = easter_offset_dates(year, *offset, *computus);
+                    month_from_predicate(year, month, |_, date| feasts.contains(&date))
+                },
+                CalendarPattern::NthWeekdayOfMonth { n, weekday, months } => {
+                    let nth_day: Option<Day> = if months.contains(month) {
+                        nth_weekday_of_month(year, month, *n, *weekday)?
+                    } else {
+                        None
+                    };
+                    month_from_predicate(year, month, |day, _| Some(day) == nth_day)
                 },
+                CalendarPattern::AnnualDate { month: annual_month, day } => {
+                    month_from_predicate(year, month, |current, _| month == *annual_month && current == *day)
+                },
+                CalendarPattern::Years(years) => month_from_predicate(year, month, |_, _| years.contains(year)),
+                CalendarPattern::Hijri { month: hijri_month, day, offset, adjustments } => {
+                    let (first, last): (Date, Date) = month_bounds(year, month)?;
+                    let dates = hijri::gregorian_dates(*hijri_month, *day, *offset, first, last, adjustments)?;
+                    month_from_predicate(year, month, |_, date| dates.contains(&date))
+                },
+            }
+        }
+    }
+
+    /// Gets the month before a year month, if it is in the supported range of years
+    fn previous_month(year: Year, month: Month) -> Option<(Year, Month)> {
+        match month.prev() {
+            Some(previous) => Some((year, previous)),
+            None if year > 1 => Some((year - 1, Month::December)),
+            None => None,
+        }
+    }
+
+    /// Gets the month after a year month, if it is in the supported range of years
+    fn next_month(year: Year, month: Month) -> Option<(Year, Month)> {
+        match month.next() {
+            Some(next) => Some((year, next)),
+            None if year < 9999 => Some((year + 1, Month::January)),
+            None => None,
+        }
+    }
+
+    /// Gets the year months of a `DateSpan` in chronological order
+    fn sorted_year_month(span: &DateSpan) -> Vec<(Year, Month)> {
+        let mut year_months: Vec<(Year, Month)> = span.to_year_month().into_iter().collect();
+        year_months.sort_unstable();
+        year_months
+    }
+
+    /// Builds the BiDay vector of a month by evaluating a predicate on each of its days
+    fn month_from_predicate<F>(year: Year, month: Month, predicate: F) -> Result<Vec<BiDay>, DayendarError>
+    where
+        F: Fn(Day, Date) -> bool,
+    {
+        let n_days: u8 = checked_days_in_month(year, month)?;
+
+        let mut days: Vec<BiDay> = Vec::with_capacity(n_days as usize);
+        let mut date: Date = checked_date(year, month, 1)?;
+        for day in 1..=n_days {
+            days.push(if predicate(day, date) { BiDay::One } else { BiDay::Zero });
+            date = date.next_day().unwrap_or(date);
+        }
+        Ok(days)
+    }
+
+    /// Gets the dates at `offset` days from Easter Sunday that may fall in a given year
+    fn easter_offset_dates(year: Year, offset: i16, computus: Computus) -> Vec<Date> {
+        let reach: Year = offset.unsigned_abs() / 365 + 1;
+        (year.saturating_sub(reach)..=year.saturating_add(reach))
+            .filter_map(|easter_year| easter_sunday(easter_year, computus).ok())
+            .filter_map(|easter| easter.checked_add(Duration::days(offset.into())))
+            .collect()
+    }
+
+    /// Gets the week of the month of a day, where weeks start on Monday and
+    /// the first week is the one containing the first day of the month
+    fn week_of_month(day: Day, date: Date) -> u8 {
+        let first_weekday: u8 = (date.weekday().number_days_from_monday() + 7 - (day - 1) % 7) % 7;
+        (day - 1 + first_weekday) / 7 + 1
+    }
+
+    /// Joins a list of displayable values separated by commas
+    fn join_list<T: Display>(values: &[T]) -> String {
+        values.iter()
+            .map(|value: &T| value.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// Writes a `MonthSpec` as `May`, `March-May` or `[January, July]`
+    fn month_spec_text(months: &MonthSpec) -> String {
+        match months {
+            MonthSpec::Single(month) => format!("{:?}", month),
+            MonthSpec::Range(range) => format!("{:?}-{:?}", range.start(), range.end()),
+            MonthSpec::List(set) => {
+                let mut sorted: Vec<&Month> = set.iter().collect();
+                sorted.sort();
+                let names: Vec<String> = sorted.iter().map(|month| format!("{:?}", month)).collect();
+                format!("[{}]", names.join(", "))
+            },
+        }
+    }
+
+    /// Writes a `YearSpec` as `2024`, `1974-9999` or `[2023, 2025]`
+    fn year_spec_text(years: &YearSpec) -> String {
+        match years {
+            YearSpec::Single(year) => year.to_string(),
+            YearSpec::Range(range) => format!("{}-{}", range.start(), range.end()),
+            YearSpec::List(set) => {
+                let mut sorted: Vec<&Year> = set.iter().collect();
+                sorted.sort();
+                format!("[{}]", join_list(&sorted))
+            },
+        }
+    }
+
+    /// Patterns are equal when they have the same variant and values. `CustomFunction` patterns are
+    /// compared by function address, so the same function may not compare equal to itself when the
+    /// compiler duplicates or merges function bodies
+    impl PartialEq for CalendarPattern {
+        fn eq(&self, other: &Self) -> bool {
+            match (self, other) {
+                (CalendarPattern::None, CalendarPattern::None)
+                | (CalendarPattern::Everyday, CalendarPattern::Everyday)
+                | (CalendarPattern::OddDays, CalendarPattern::OddDays)
+                | (CalendarPattern::EvenDays, CalendarPattern::EvenDays) => true,
+                (CalendarPattern::Weekdays(a), CalendarPattern::Weekdays(b)) => a == b,
+                (CalendarPattern::CustomWeekDays(a), CalendarPattern::CustomWeekDays(b)) => a == b,
+                (CalendarPattern::CustomDays(a), CalendarPattern::CustomDays(b)) => a == b,
+                (CalendarPattern::CustomBiDay(a), CalendarPattern::CustomBiDay(b)) => a == b,
+                (CalendarPattern::SpecificDayOfMonth(a), CalendarPattern::SpecificDayOfMonth(b)) => a == b,
+                (CalendarPattern::SpecificWeekOfMonth(a), CalendarPattern::SpecificWeekOfMonth(b)) => a == b,
+                (CalendarPattern::FixedHolidays(a), CalendarPattern::FixedHolidays(b)) => a == b,
+                (CalendarPattern::FloatingHolidays(a), CalendarPattern::FloatingHolidays(b)) => a == b,
+                (CalendarPattern::SpecificWeekOfYear(a), CalendarPattern::SpecificWeekOfYear(b)) => a == b,
+                (CalendarPattern::CustomFunction(a), CalendarPattern::CustomFunction(b)) => std::ptr::fn_addr_eq(*a, *b),
+                (CalendarPattern::CronPattern(a), CalendarPattern::CronPattern(b)) => a == b,
+                (CalendarPattern::RRule(a), CalendarPattern::RRule(b)) => a == b,
+                (
+                    CalendarPattern::EasterOffset { offset, computus },
+                    CalendarPattern::EasterOffset { offset: other_offset, computus: other_computus },
+                ) => offset == other_offset && computus == other_computus,
+                (
+                    CalendarPattern::NthWeekdayOfMonth { n, weekday, months },
+                    CalendarPattern::NthWeekdayOfMonth { n: other_n, weekday: other_weekday, months: other_months },
+                ) => n == other_n && weekday == other_weekday && months == other_months,
+                (
+                    CalendarPattern::AnnualDate { month, day },
+                    CalendarPattern::AnnualDate { month: other_month, day: other_day },
+                ) => month == other_month && day == other_day,
+                (CalendarPattern::Years(a), CalendarPattern::Years(b)) => a == b,
+                (
+                    CalendarPattern::Hijri { month, day, offset, adjustments },
+                    CalendarPattern::Hijri { month: other_month, day: other_day, offset: other_offset, adjustments: other_adjustments },
+                ) => month == other_month && day == other_day && offset == other_offset && adjustments == other_adjustments,
+                (
+                    CalendarPattern::Hebrew { month, day },
+                    CalendarPattern::Hebrew { month: other_month, day: other_day },
+                ) => month == other_month && day == other_day,
+                (CalendarPattern::ChineseFestival(a), CalendarPattern::ChineseFestival(b)) => a == b,
+                (
+                    CalendarPattern::ForeignDate { system, month, day },
+                    CalendarPattern::ForeignDate { system: other_system, month: other_month, day: other_day },
+                ) => system == other_system && month == other_month && day == other_day,
+                _ => false,
+            }
+        }
+    }
+
+    impl fmt::Display for CalendarPattern {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                CalendarPattern::None => write!(f, "None"),
+                CalendarPattern::Everyday => write!(f, "Everyday"),
+                CalendarPattern::OddDays => write!(f, "OddDays"),
+                CalendarPattern::EvenDays => write!(f, "EvenDays"),
+                CalendarPattern::Weekdays(weekdays) => write!(f, "Weekdays([{}])", join_list(weekdays)),
+                CalendarPattern::CustomWeekDays(weekdays) => write!(f, "CustomWeekDays([{}])", join_list(weekdays)),
+                CalendarPattern::CustomDays(days) => write!(f, "CustomDays([{}])", join_list(days)),
                 CalendarPattern::CustomBiDay(bidays) => {
                     let bidays_str: String = bidays.iter()
                         .map(|biday: &BiDay| match biday {
@@ -924,18 +1603,44 @@ pub mod abstracto {
                         .join(", ");
                     write!(f, "CustomBiDay([{}])", bidays_str)
                 },
-                CalendarPattern::CronPattern(cron) => write!(f, "CronPattern(\"{}\")", cron),
-                _ => todo!(),
+                CalendarPattern::SpecificDayOfMonth(day) => write!(f, "SpecificDayOfMonth({})", day),
+                CalendarPattern::SpecificWeekOfMonth(week) => write!(f, "SpecificWeekOfMonth({})", week),
+                CalendarPattern::FixedHolidays(dates) => write!(f, "FixedHolidays([{}])", join_list(dates)),
+                CalendarPattern::FloatingHolidays(dates) => write!(f, "FloatingHolidays([{}])", join_list(dates)),
+                CalendarPattern::SpecificWeekOfYear(week) => write!(f, "SpecificWeekOfYear({})", week),
+                // Functions have no textual representation, so this form can not be parsed back
+                CalendarPattern::CustomFunction(_) => write!(f, "CustomFunction(<fn>)"),
+                CalendarPattern::CronPattern(cron) => {
+                    let escaped: String = cron.replace('\\', "\\\\").replace('"', "\\\"");
+                    write!(f, "CronPattern(\"{}\")", escaped)
+                },
+                CalendarPattern::RRule(rule) => {
+                    let escaped: String = rule.replace('\\', "\\\\").replace('"', "\\\"");
+                    write!(f, "RRule(\"{}\")", escaped)
+                },
+                CalendarPattern::EasterOffset { offset, computus } => {
+                    write!(f, "EasterOffset({}, {:?})", offset, computus)
+                },
+                CalendarPattern::NthWeekdayOfMonth { n, weekday, months } => {
+                    write!(f, "NthWeekdayOfMonth({}, {}, {})", n, weekday, month_spec_text(months))
+                },
+                CalendarPattern::AnnualDate { month, day } => write!(f, "AnnualDate({:?}, {})", month, day),
+                CalendarPattern::Years(years) => write!(f, "Years({})", year_spec_text(years)),
+                CalendarPattern::Hijri { month, day, offset, adjustments } => {
+                    if adjustments.is_empty() {
+                        write!(f, "Hijri({}, {}, {})", month, day, offset)
+                    } else {
+                        write!(f, "Hijri({}, {}, {}, {})", month, day, offset, adjustments)
+                    }
+                },
+                CalendarPattern::Hebrew { month, day } => write!(f, "Hebrew({}, {})", month, day),
+                CalendarPattern::ChineseFestival(festival) => write!(f, "ChineseFestival({:?})", festival),
+                CalendarPattern::ForeignDate { system, month, day } => {
+                    write!(f, "ForeignDate({:?}, {}, {})", system, month, day)
+                },
             }
         }
     }
-    
-    
-    
-
-
-    
-
 
 }
 
@@ -954,14 +1659,13 @@ mod tests_calendar {
     use crate::types::*;
     use crate::binary::*;
     use crate::calendar::*;
+    use crate::error::DayendarError;
     use std::collections::HashMap;
     use std::collections::HashSet;
-    use std::iter::FromIterator;
     use std::vec::Vec;
     use time::macros::date;
     use time::Date;
     use time::Duration;
-    use time::Month as TimeMonth;
     use time::Weekday;
 
     #[test]
@@ -1254,6 +1958,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(clippy::redundant_closure)]
     fn test_resume_days_with_and_operator() {
         let days = vec![
             (
@@ -1357,6 +2062,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(clippy::redundant_closure)]
     fn test_resume_days_with_or_operator() {
         let days = vec![
             (
@@ -1460,6 +2166,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(clippy::redundant_closure)]
     fn test_resume_with_empty_calendar() {
         let calendar = DaysCalendar {
             days_calendar: vec![],
@@ -1474,6 +2181,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(clippy::redundant_closure)]
     fn test_resume_with_single_day() {
         let days = vec![(
             2022,
@@ -1520,6 +2228,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(clippy::redundant_closure)]
     fn test_resume_with_multiple_days() {
         let days = vec![
             (
@@ -1714,6 +2423,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(clippy::redundant_closure)]
     fn test_resume_with_or_operator() {
         let days = vec![
             (
@@ -3255,6 +3965,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(unused_mut)]
     fn test_upward_step_with_single_day_calendar() {
         let mut calendar: DaysCalendar<BiDay> =
             DaysCalendar::singleton(2023, Month::March).unwrap();
@@ -3477,6 +4188,7 @@ mod tests_calendar {
         assert_eq!(result_calendar, expected_calendar);
     }
 
+    #[allow(dead_code)]
     fn test_new() {
         let data = vec![
             (
@@ -3509,6 +4221,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(clippy::unnecessary_cast)]
     fn test_to_day() {
         let bi_data = vec![
             (
@@ -3749,14 +4462,14 @@ mod tests_calendar {
     #[test]
     fn test_from_date_empty() {
         let dates: Vec<Date> = vec![];
-        let calendar = from_date(dates);
+        let calendar = from_date(dates).unwrap();
         assert!(calendar.days_calendar.is_empty());
     }
 
     #[test]
     fn test_from_date_single_date() {
         let dates = vec![date!(2022 - 03 - 30)];
-        let calendar = from_date(dates);
+        let calendar = from_date(dates).unwrap();
         assert_eq!(calendar.days_calendar.len(), 1);
         assert_eq!(calendar.days_calendar[0], (2022, Month::March, vec![30]));
     }
@@ -3768,7 +4481,7 @@ mod tests_calendar {
             date!(2022 - 03 - 15),
             date!(2022 - 03 - 20),
         ];
-        let calendar = from_date(dates);
+        let calendar = from_date(dates).unwrap();
         assert_eq!(calendar.days_calendar.len(), 1);
         assert_eq!(
             calendar.days_calendar[0],
@@ -3783,7 +4496,7 @@ mod tests_calendar {
             date!(2022 - 02 - 15),
             date!(2022 - 01 - 20),
         ];
-        let calendar = from_date(dates);
+        let calendar = from_date(dates).unwrap();
         assert_eq!(calendar.days_calendar.len(), 3);
         assert_eq!(calendar.days_calendar[0], (2022, Month::January, vec![20]));
         assert_eq!(calendar.days_calendar[1], (2022, Month::February, vec![15]));
@@ -3797,7 +4510,7 @@ mod tests_calendar {
             date!(2022 - 03 - 15),
             date!(2021 - 03 - 20),
         ];
-        let calendar = from_date(dates);
+        let calendar = from_date(dates).unwrap();
         assert_eq!(calendar.days_calendar.len(), 3);
         assert_eq!(calendar.days_calendar[0], (2021, Month::March, vec![20]));
         assert_eq!(calendar.days_calendar[1], (2022, Month::March, vec![15]));
@@ -3811,7 +4524,7 @@ mod tests_calendar {
             date!(2022 - 03 - 15),
             date!(2022 - 03 - 30),
         ];
-        let calendar = from_date(dates);
+        let calendar = from_date(dates).unwrap();
         assert_eq!(calendar.days_calendar.len(), 1);
         assert_eq!(
             calendar.days_calendar[0],
@@ -3819,6 +4532,25 @@ mod tests_calendar {
         );
     }
 
+    #[test]
+    fn test_from_date_year_out_of_range() {
+        let dates = vec![date!(2023 - 01 - 01), date!(0000 - 12 - 31)];
+        assert_eq!(from_date(dates), Err(DayendarError::YearOutOfRange(0)));
+    }
+
+    #[test]
+    fn test_from_day_invalid_days() {
+        let zero = DaysCalendar { days_calendar: vec![(2023, Month::January, vec![0])] };
+        assert_eq!(from_day(zero), Err(DayendarError::InvalidDate { year: 2023, month: 1, day: 0 }));
+
+        let thirty_second = DaysCalendar { days_calendar: vec![(2023, Month::January, vec![1, 32])] };
+        assert_eq!(from_day(thirty_second), Err(DayendarError::InvalidDate { year: 2023, month: 1, day: 32 }));
+
+        let year_zero = DaysCalendar { days_calendar: vec![(0, Month::January, vec![1])] };
+        assert_eq!(from_day(year_zero), Err(DayendarError::YearOutOfRange(0)));
+        assert_eq!(DaysCalendar::singleton(10000, Month::January), Err(DayendarError::YearOutOfRange(10000)));
+    }
+
     #[test]
     fn test_from_day() {
         let input_calendar = DaysCalendar {
@@ -3943,7 +4675,7 @@ mod tests_calendar {
             ],
         };
 
-        let output_calendar = from_day(input_calendar);
+        let output_calendar = from_day(input_calendar).unwrap();
 
         assert_eq!(output_calendar, expected_calendar);
     }
@@ -4012,6 +4744,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_contains() {
         let mut days_calendar = create_unsorted_days_calendar();
 
@@ -4146,6 +4879,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(unused_variables)]
     fn test_next_day() {
         let calendar = sample_calendar();
         let date = Date::from_calendar_date(2020, Month::January.to_time_month().unwrap(), 1).unwrap();
@@ -4172,6 +4906,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(unused_variables)]
     fn test_next_day_edge_cases() {
         let calendar = sample_calendar();
 
@@ -4313,6 +5048,137 @@ mod tests_calendar {
         assert!(new_date.is_ok());
     }
 
+    #[test]
+    fn test_dates_double_ended() {
+        let calendar = DaysCalendar::singleton(2024, Month::January).unwrap()
+            .append(&DaysCalendar::singleton(2024, Month::February).unwrap())
+            .downward_step();
+        let all: Vec<Date> = calendar.dates().collect();
+        assert_eq!(all, to_date(calendar.clone()));
+
+        let mut reversed: Vec<Date> = calendar.dates().rev().collect();
+        reversed.reverse();
+        assert_eq!(reversed, all);
+
+        // Both ends meet without yielding a date twice
+        let mut dates = calendar.dates();
+        let mut mixed = Vec::new();
+        while let Some(first) = dates.next() {
+            mixed.push(first);
+            if let Some(last) = dates.next_back() {
+                mixed.push(last);
+            }
+        }
+        mixed.sort();
+        assert_eq!(mixed, all);
+    }
+
+    #[test]
+    fn test_dates_from_and_before() {
+        let calendar = DaysCalendar::singleton(2024, Month::January).unwrap()
+            .append(&DaysCalendar::singleton(2024, Month::March).unwrap())
+            .downward_step();
+        let from: Vec<Date> = calendar.dates_from(date!(2024 - 01 - 30)).take(3).collect();
+        assert_eq!(from, vec![date!(2024 - 01 - 31), date!(2024 - 03 - 01), date!(2024 - 03 - 03)]);
+
+        let before: Vec<Date> = calendar.dates_before(date!(2024 - 03 - 03)).rev().take(2).collect();
+        assert_eq!(before, vec![date!(2024 - 03 - 01), date!(2024 - 01 - 31)]);
+
+        // Bounds outside the calendar
+        assert_eq!(calendar.dates_from(date!(2025 - 01 - 01)).next(), None);
+        assert_eq!(calendar.dates_before(date!(2023 - 12 - 31)).next_back(), None);
+        assert_eq!(calendar.dates_from(date!(2024 - 02 - 10)).next(), Some(date!(2024 - 03 - 01)));
+
+        // Unsorted calendars are filtered in calendar order
+        let unsorted = DaysCalendar {
+            days_calendar: vec![calendar.days_calendar[1].clone(), calendar.days_calendar[0].clone()],
+        };
+        assert_eq!(unsorted.dates_from(date!(2024 - 01 - 30)).next(), Some(date!(2024 - 03 - 01)));
+        assert_eq!(unsorted.dates_before(date!(2024 - 03 - 02)).next_back(), Some(date!(2024 - 01 - 31)));
+    }
+
+    #[test]
+    fn test_months_iterator() {
+        let calendar = DaysCalendar::singleton(2024, Month::January).unwrap()
+            .append(&DaysCalendar::singleton(2024, Month::February).unwrap());
+        let months: Vec<(Year, Month, usize)> = calendar.months().map(|(y, m, days)| (y, m, days.len())).collect();
+        assert_eq!(months, vec![(2024, Month::January, 31), (2024, Month::February, 29)]);
+        assert_eq!(calendar.months().len(), 2);
+        assert_eq!(calendar.months().next_back().map(|(_, m, _)| m), Some(Month::February));
+    }
+
+    #[test]
+    fn test_navigation_over_decades() {
+        let mut calendar = DaysCalendar::empty();
+        for year in 1990..=2040 {
+            for month in 1..=12 {
+                calendar = calendar.append(&DaysCalendar::singleton(year, Month::from_index(month).unwrap()).unwrap());
+            }
+        }
+        let calendar = replicate::<BiDay>(&[BiDay::One, BiDay::Zero, BiDay::Zero], calendar);
+        let dates = to_date(calendar.clone());
+        let reference = date!(2020 - 06 - 15);
+        let position = dates.partition_point(|date| *date <= reference);
+
+        assert_eq!(calendar.next_day(2020, Month::June, 15), Some(dates[position]));
+        assert_eq!(calendar.seek_nth_day(2020, Month::June, 15, 5), Ok(Some(dates[position + 4])));
+        let before = dates.partition_point(|date| *date < reference);
+        assert_eq!(calendar.previous_day(2020, Month::June, 15), Some(dates[before - 1]));
+        assert_eq!(calendar.seek_nth_day(2020, Month::June, 15, -7), Ok(Some(dates[before - 7])));
+    }
+
+    #[test]
+    fn test_and_nth_weekday() {
+        let calendar = DaysCalendar::singleton(2024, Month::May).unwrap()
+            .append(&DaysCalendar::singleton(2024, Month::June).unwrap());
+        let may = MonthSpec::Single(Month::May);
+        let last_monday = calendar.and_nth_weekday(-1, Weekday::Monday, &may).unwrap();
+        assert_eq!(to_date(last_monday), vec![date!(2024 - 05 - 27)]);
+
+        let second_fridays = calendar.and_nth_weekday(2, Weekday::Friday, &MonthSpec::Range(Month::January..=Month::December)).unwrap();
+        assert_eq!(to_date(second_fridays), vec![date!(2024 - 05 - 10), date!(2024 - 06 - 14)]);
+
+        // Days already excluded stay excluded
+        let odd = calendar.downward_step();
+        assert_eq!(to_date(odd.and_nth_weekday(-1, Weekday::Monday, &may).unwrap()), vec![date!(2024 - 05 - 27)]);
+        assert_eq!(to_date(odd.and_nth_weekday(1, Weekday::Monday, &may).unwrap()), Vec::<Date>::new());
+    }
+
+    #[test]
+    fn test_observe() {
+        let weekend: HashSet<Weekday> = [Weekday::Saturday, Weekday::Sunday].into_iter().collect();
+        let holidays = from_day(from_date(vec![
+            date!(2021 - 12 - 25), date!(2021 - 12 - 26), date!(2022 - 01 - 01), date!(2022 - 12 - 25), date!(2022 - 12 - 26),
+        ]).unwrap()).unwrap();
+
+        let nearest = holidays.observe(weekend.clone(), ObservanceRule::NearestWeekday);
+        assert_eq!(to_date(nearest.clone()), vec![
+            date!(2021 - 12 - 24), date!(2021 - 12 - 27), date!(2021 - 12 - 31), date!(2022 - 12 - 26),
+        ]);
+        // The observed days keep the months of the holidays
+        assert_eq!(nearest.days_calendar.len(), 3);
+
+        let next_non_holiday = holidays.observe(weekend.clone(), ObservanceRule::NextNonHoliday);
+        assert_eq!(to_date(next_non_holiday), vec![
+            date!(2021 - 12 - 27), date!(2021 - 12 - 28), date!(2022 - 01 - 03), date!(2022 - 12 - 26), date!(2022 - 12 - 27),
+        ]);
+
+        let next = holidays.observe(weekend.clone(), ObservanceRule::NextWeekday);
+        assert_eq!(to_date(next), vec![date!(2021 - 12 - 27), date!(2022 - 01 - 03), date!(2022 - 12 - 26)]);
+
+        let previous = holidays.observe(weekend, ObservanceRule::PreviousWeekday);
+        assert_eq!(to_date(previous), vec![date!(2021 - 12 - 24), date!(2021 - 12 - 31), date!(2022 - 12 - 23), date!(2022 - 12 - 26)]);
+    }
+
+    #[test]
+    fn test_seek_nth_day_invalid_date() {
+        let calendar = create_test_calendar();
+        assert_eq!(
+            calendar.seek_nth_day(2021, Month::February, 29, 1),
+            Err(DayendarError::InvalidDate { year: 2021, month: 2, day: 29 })
+        );
+    }
+
     fn generate_sample_calendar() -> DaysCalendar<BiDay> {
         DaysCalendar {
             days_calendar: vec![
@@ -4332,6 +5198,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(clippy::if_same_then_else)]
     fn test_or_weekdays_single_weekday() {
         let calendar = generate_sample_calendar();
         let mut weekdays = HashSet::new();
@@ -4356,6 +5223,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(clippy::if_same_then_else)]
     fn test_or_weekdays_multiple_weekdays() {
         let calendar = generate_sample_calendar();
         let mut weekdays = HashSet::new();
@@ -4391,6 +5259,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(clippy::if_same_then_else, clippy::needless_range_loop)]
     fn test_or_iso_weeks_single_week() {
         let calendar = generate_sample_calendar();
         let weeks = vec![5];
@@ -4416,6 +5285,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(clippy::if_same_then_else, clippy::needless_range_loop)]
     fn test_or_iso_weeks_multiple_weeks() {
         let calendar = generate_sample_calendar();
         let weeks = vec![2, 5, 7];
@@ -4441,6 +5311,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(unused_variables, clippy::unused_enumerate_index)]
     fn test_or_iso_weeks_all_weeks() {
         let calendar = generate_sample_calendar();
         let weeks = (1..=53).collect::<Vec<u32>>();
@@ -4455,6 +5326,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(unused_variables, clippy::unused_enumerate_index)]
     fn test_and_weekdays_empty_input() {
         let calendar = generate_sample_calendar();
         let weekdays: HashSet<Weekday> = HashSet::new();
@@ -4468,6 +5340,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(clippy::explicit_counter_loop, clippy::unused_enumerate_index)]
     fn test_and_weekdays_single_weekday() {
         let calendar = generate_sample_calendar();
         let mut weekdays = HashSet::new();
@@ -4493,6 +5366,7 @@ mod tests_calendar {
     }
 
     #[test]
+    #[allow(clippy::explicit_counter_loop, clippy::unused_enumerate_index)]
     fn test_and_weekdays_multiple_weekdays() {
         let calendar = generate_sample_calendar();
         let mut weekdays = HashSet::new();
@@ -4616,9 +5490,11 @@ mod tests_calendar {
         assert_eq!(new_calendar, Ok(calendar));
     }
 
+    #[allow(unused_imports)]
     use std::iter::repeat;
 
     #[test]
+    #[allow(unused_variables, clippy::needless_range_loop)]
     fn test_not_iso_weeks() {
         let calendar = create_sample_calendar();
         let weeks_to_exclude = vec![1, 5, 52];
@@ -4794,6 +5670,144 @@ mod tests_calendar {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_merge_join_matches_resume() {
+        let cal1 = DaysCalendar {
+            days_calendar: vec![
+                (2023, Month::January, vec![BiDay::One, BiDay::Zero, BiDay::One]),
+                (2023, Month::March, vec![BiDay::One; 31]),
+                (2024, Month::February, vec![BiDay::Zero, BiDay::One]),
+            ],
+        };
+        let cal2 = DaysCalendar {
+            days_calendar: vec![
+                (2023, Month::January, vec![BiDay::One, BiDay::One]),
+                (2023, Month::February, vec![BiDay::One; 30]),
+                (2024, Month::February, vec![BiDay::One; 29]),
+            ],
+        };
+        let operators: [fn(BiDay, BiDay) -> BiDay; 5] = [
+            or_biday_operation, and_biday_operation, minus_biday_operation,
+            match_biday_operation, nomatch_biday_operation,
+        ];
+        for op in operators {
+            assert_eq!(merge_join(&[&cal1, &cal2], op), resume(&cal1.append(&cal2), op));
+            assert_eq!(merge_join(&[&cal2, &cal1], op), resume(&cal2.append(&cal1), op));
+        }
+    }
+
+    #[test]
+    fn test_merge_join_unsorted_falls_back_to_resume() {
+        let unsorted = DaysCalendar {
+            days_calendar: vec![
+                (2023, Month::March, vec![BiDay::One]),
+                (2023, Month::January, vec![BiDay::One]),
+                (2023, Month::January, vec![BiDay::Zero, BiDay::One]),
+            ],
+        };
+        let sorted = DaysCalendar::singleton(2023, Month::February).unwrap();
+        assert!(!unsorted.is_sorted());
+        assert!(sorted.is_sorted());
+        assert_eq!(
+            merge_join(&[&unsorted, &sorted], or_biday_operation),
+            resume(&unsorted.append(&sorted), or_biday_operation)
+        );
+    }
+
+    #[test]
+    fn test_merge_join_n_ary() {
+        let jan = DaysCalendar::singleton(2023, Month::January).unwrap();
+        let odd = jan.clone().downward_step();
+        let even = jan.clone().upward_step();
+        let feb = DaysCalendar::singleton(2023, Month::February).unwrap();
+
+        assert_eq!(DaysCalendar::or_all(&[&odd, &even, &feb]), jan.or(&feb));
+        assert_eq!(DaysCalendar::and_all(&[&odd, &even, &jan]), jan.clone().zeros());
+        assert_eq!(
+            merge_join(&[&jan, &odd, &even], minus_biday_operation),
+            jan.minus(&odd).minus(&even)
+        );
+        assert_eq!(DaysCalendar::or_all(&[]), DaysCalendar::empty());
+    }
+
+    #[test]
+    fn test_try_new_validates() {
+        let jan = vec![BiDay::One; 31];
+        let feb = vec![BiDay::Zero; 28];
+        assert!(DaysCalendar::try_new(vec![(2023, Month::January, jan.clone()), (2023, Month::February, feb.clone())]).is_ok());
+        assert_eq!(
+            DaysCalendar::try_new(vec![(2023, Month::February, feb.clone()), (2023, Month::January, jan.clone())]),
+            Err(DayendarError::UnsortedCalendar)
+        );
+        assert_eq!(
+            DaysCalendar::try_new(vec![(2023, Month::January, jan.clone()), (2023, Month::January, jan.clone())]),
+            Err(DayendarError::DuplicatedMonth { year: 2023, month: Month::January })
+        );
+        assert_eq!(
+            DaysCalendar::try_new(vec![(2024, Month::February, feb)]),
+            Err(DayendarError::InvalidMonthLength { year: 2024, month: Month::February, expected: 29, found: 28 })
+        );
+        assert_eq!(DaysCalendar::try_new(vec![(0, Month::January, jan)]), Err(DayendarError::YearOutOfRange(0)));
+    }
+
+    #[test]
+    fn test_normalize() {
+        let raw = DaysCalendar::new(vec![
+            (2023, Month::February, vec![BiDay::One]),
+            (2023, Month::January, vec![BiDay::Zero, BiDay::One]),
+            (2023, Month::January, vec![BiDay::One; 40]),
+            (0, Month::January, vec![BiDay::One]),
+        ]);
+        assert!(!raw.is_canonical());
+        let normalized = raw.normalize();
+        assert!(normalized.is_canonical());
+        assert_eq!(normalized.days_calendar.len(), 2);
+        assert_eq!(normalized.days_calendar[0].2, vec![BiDay::One; 31]);
+        assert_eq!(normalized.days_calendar[1].2.len(), 28);
+        assert_eq!(normalized.days_calendar[1].2[0], BiDay::One);
+    }
+
+    #[test]
+    fn test_semantic_equality_and_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash_of<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let canonical = DaysCalendar::singleton(2023, Month::January).unwrap()
+            .append(&DaysCalendar::singleton(2023, Month::February).unwrap().zeros());
+        let raw = DaysCalendar::new(vec![
+            (2023, Month::February, vec![]),
+            (2023, Month::January, vec![BiDay::One; 20]),
+            (2023, Month::January, vec![BiDay::One; 31]),
+        ]);
+        assert_eq!(raw, canonical);
+        assert_eq!(hash_of(&raw), hash_of(&canonical));
+        assert_ne!(raw, DaysCalendar::singleton(2023, Month::January).unwrap());
+
+        let days = DaysCalendar::new_days(vec![(2023, Month::March, vec![3, 1]), (2023, Month::January, vec![5, 5, 40])]);
+        let sorted_days = DaysCalendar::new_days(vec![(2023, Month::January, vec![5]), (2023, Month::March, vec![1, 3])]);
+        assert_eq!(days, sorted_days);
+        assert_eq!(hash_of(&days), hash_of(&sorted_days));
+    }
+
+    #[test]
+    fn test_extract_day_month_calendar_unsorted() {
+        let dc = DaysCalendar {
+            days_calendar: vec![
+                (2023, Month::March, vec![BiDay::One]),
+                (2022, Month::January, vec![BiDay::Zero]),
+                (2023, Month::January, vec![BiDay::One, BiDay::One]),
+            ],
+        };
+        assert_eq!(extract_day_month_calendar(2023, Month::January, &dc), Some(vec![BiDay::One, BiDay::One]));
+        assert_eq!(extract_day_month_calendar(2022, Month::January, &dc), Some(vec![BiDay::Zero]));
+    }
+
     #[test]
     fn test_get_days_present() {
         let calendar = DaysCalendar {
@@ -4842,4 +5856,220 @@ mod tests_calendar {
 
 
 
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests_abstracto {
+
+    use crate::types::*;
+    use crate::calendar::*;
+    use crate::abstracto::*;
+    use crate::error::DayendarError;
+    use std::collections::HashSet;
+
+    fn span_2023_january() -> DateSpan {
+        DateSpan::YearMonth(YearMonthSpec::parse("2023-January").unwrap())
+    }
+
+    fn included_days(calendar: DaysCalendar<BiDay>) -> Vec<Day> {
+        biday_to_vec_day(calendar).into_iter().flat_map(|(_, _, days)| days).collect()
+    }
+
+    #[test]
+    fn test_evaluate_everyday_and_none() {
+        let span = span_2023_january();
+        let everyday = AbstractCalendar::Pattern(CalendarPattern::Everyday).evaluate(&span).unwrap();
+        assert_eq!(everyday, DaysCalendar::singleton(2023, Month::January).unwrap());
+
+        let none = AbstractCalendar::Pattern(CalendarPattern::None).evaluate(&span).unwrap();
+        assert_eq!(none, DaysCalendar::singleton(2023, Month::January).unwrap().zeros());
+    }
+
+    #[test]
+    fn test_evaluate_odd_even_days() {
+        let span = span_2023_january();
+        let odd = CalendarPattern::OddDays.evaluate(&span).unwrap();
+        let even = CalendarPattern::EvenDays.evaluate(&span).unwrap();
+        assert_eq!(included_days(odd.clone()), (1..=31).step_by(2).collect::<Vec<Day>>());
+        assert_eq!(odd.invert_biday(), even);
+    }
+
+    #[test]
+    fn test_evaluate_weekdays() {
+        // January 2023 starts on a Sunday
+        let span = span_2023_january();
+        let weekends = CalendarPattern::Weekdays(vec![Weekday::Saturday, Weekday::Sunday]).evaluate(&span).unwrap();
+        assert_eq!(included_days(weekends.clone()), vec![1, 7, 8, 14, 15, 21, 22, 28, 29]);
+
+        let custom = CalendarPattern::CustomWeekDays(vec![6, 7]).evaluate(&span).unwrap();
+        assert_eq!(custom, weekends);
+    }
+
+    #[test]
+    fn test_evaluate_custom_days_and_day_of_month() {
+        let span = DateSpan::Year(YearSpec::Single(2023));
+        let custom = CalendarPattern::CustomDays(vec![1, 31]).evaluate(&span).unwrap();
+        assert_eq!(custom.days_calendar.len(), 12);
+        assert_eq!(included_days(custom).len(), 12 + 7);
+
+        let fifteenth = CalendarPattern::SpecificDayOfMonth(15).evaluate(&span).unwrap();
+        assert!(biday_to_vec_day(fifteenth).iter().all(|(_, _, days)| days == &vec![15]));
+    }
+
+    #[test]
+    fn test_evaluate_custom_biday() {
+        let span = span_2023_january();
+        let pattern = CalendarPattern::CustomBiDay(vec![BiDay::One, BiDay::Zero]).evaluate(&span).unwrap();
+        assert_eq!(pattern, DaysCalendar::singleton(2023, Month::January).unwrap().downward_step());
+
+        let empty = CalendarPattern::CustomBiDay(vec![]).evaluate(&span).unwrap();
+        assert_eq!(included_days(empty), Vec::<Day>::new());
+    }
+
+    #[test]
+    fn test_evaluate_specific_week_of_month() {
+        // February 2023 starts on a Wednesday
+        let span = DateSpan::YearMonth(YearMonthSpec::parse("2023-February").unwrap());
+        let first_week = CalendarPattern::SpecificWeekOfMonth(1).evaluate(&span).unwrap();
+        assert_eq!(included_days(first_week), vec![1, 2, 3, 4, 5]);
+
+        let second_week = CalendarPattern::SpecificWeekOfMonth(2).evaluate(&span).unwrap();
+        assert_eq!(included_days(second_week), vec![6, 7, 8, 9, 10, 11, 12]);
+    }
+
+    #[test]
+    fn test_evaluate_holidays_and_week_of_year() {
+        let span = DateSpan::Date(DateSpec::Range(date!(2023 - 12 - 01), date!(2024 - 01 - 31)));
+        let holidays = CalendarPattern::FixedHolidays(vec![date!(2023 - 12 - 25), date!(2024 - 01 - 01)])
+            .evaluate(&span)
+            .unwrap();
+        assert_eq!(
+            biday_to_vec_day(holidays),
+            vec![(2023, Month::December, vec![25]), (2024, Month::January, vec![1])]
+        );
+
+        let week_one = CalendarPattern::SpecificWeekOfYear(1).evaluate(&span).unwrap();
+        assert_eq!(
+            biday_to_vec_day(week_one),
+            vec![(2023, Month::December, vec![]), (2024, Month::January, vec![1, 2, 3, 4, 5, 6, 7])]
+        );
+    }
+
+    #[test]
+    fn test_evaluate_custom_function() {
+        fn last_day(year: Year, month: Month, day: Day) -> BiDay {
+            if Some(day) == crate::utils::days_in_month(year, month) { BiDay::One } else { BiDay::Zero }
+        }
+        let span = DateSpan::Year(YearSpec::Single(2024));
+        let calendar = CalendarPattern::CustomFunction(last_day).evaluate(&span).unwrap();
+        assert_eq!(calendar.get_days(2024, Month::February).unwrap()[28], BiDay::One);
+        assert_eq!(included_days(calendar).len(), 12);
+    }
+
+    #[test]
+    fn test_pattern_equality() {
+        fn first_day(_: Year, _: Month, day: Day) -> BiDay {
+            if day == 1 { BiDay::One } else { BiDay::Zero }
+        }
+        fn no_day(_: Year, _: Month, _: Day) -> BiDay {
+            BiDay::Zero
+        }
+        assert_eq!(CalendarPattern::CustomFunction(first_day), CalendarPattern::CustomFunction(first_day));
+        assert_ne!(CalendarPattern::CustomFunction(first_day), CalendarPattern::CustomFunction(no_day));
+        assert_ne!(CalendarPattern::CustomFunction(no_day), CalendarPattern::None);
+        assert_eq!(
+            CalendarPattern::AnnualDate { month: Month::May, day: 1 },
+            CalendarPattern::AnnualDate { month: Month::May, day: 1 }
+        );
+        assert_ne!(
+            CalendarPattern::AnnualDate { month: Month::May, day: 1 },
+            CalendarPattern::AnnualDate { month: Month::May, day: 2 }
+        );
+        assert_ne!(CalendarPattern::OddDays, CalendarPattern::EvenDays);
+    }
+
+    #[test]
+    fn test_evaluate_operations() {
+        let span = span_2023_january();
+        let weekends = AbstractCalendar::Pattern(CalendarPattern::Weekdays(vec![Weekday::Saturday, Weekday::Sunday]));
+        let holidays = AbstractCalendar::Pattern(CalendarPattern::CustomDays(vec![1, 6]));
+
+        let workdays = holidays.or(weekends).invert().evaluate(&span).unwrap();
+        assert_eq!(workdays.days_calendar.len(), 1);
+        assert_eq!(included_days(workdays.clone()).len(), 21);
+        assert_eq!(workdays.get_days(2023, Month::January).unwrap()[5], BiDay::Zero);
+
+        let odd = AbstractCalendar::Pattern(CalendarPattern::OddDays);
+        let firsts = AbstractCalendar::Pattern(CalendarPattern::CustomDays(vec![1, 2, 3]));
+        assert_eq!(included_days(odd.and(firsts).evaluate(&span).unwrap()), vec![1, 3]);
+
+        let odd = AbstractCalendar::Pattern(CalendarPattern::OddDays);
+        let firsts = AbstractCalendar::Pattern(CalendarPattern::CustomDays(vec![1, 2, 3]));
+        assert_eq!(included_days(firsts.minus(odd).evaluate(&span).unwrap()), vec![2]);
+    }
+
+    #[test]
+    fn test_evaluate_cron_pattern() {
+        let span = DateSpan::Year(YearSpec::Single(2024));
+        let thanksgiving = CalendarPattern::CronPattern(String::from("0 0 0 ? NOV THU#4")).evaluate(&span).unwrap();
+        assert_eq!(to_date(thanksgiving), vec![date!(2024 - 11 - 28)]);
+
+        let invalid = CalendarPattern::CronPattern(String::from("* * *")).evaluate(&span);
+        assert!(matches!(invalid, Err(DayendarError::ParseError { .. })));
+    }
+
+    #[test]
+    fn test_evaluate_easter_offset() {
+        let span = DateSpan::Year(YearSpec::Range(2024..=2025));
+        let good_friday = CalendarPattern::EasterOffset { offset: -2, computus: Computus::Western };
+        assert_eq!(to_date(good_friday.evaluate(&span).unwrap()), vec![date!(2024 - 03 - 29), date!(2025 - 04 - 18)]);
+
+        let whit_monday = CalendarPattern::EasterOffset { offset: 50, computus: Computus::Orthodox };
+        assert_eq!(to_date(whit_monday.evaluate(&span).unwrap()), vec![date!(2024 - 06 - 24), date!(2025 - 06 - 09)]);
+
+        // Offsets reaching into the next year are found from the previous Easter
+        let far = CalendarPattern::EasterOffset { offset: 300, computus: Computus::Western };
+        let span = DateSpan::Year(YearSpec::Single(2025));
+        assert_eq!(to_date(far.evaluate(&span).unwrap()), vec![date!(2025 - 01 - 25)]);
+    }
+
+    #[test]
+    fn test_evaluate_nth_weekday_of_month() {
+        let span = DateSpan::Year(YearSpec::Single(2024));
+        let thanksgiving = CalendarPattern::NthWeekdayOfMonth {
+            n: 4, weekday: Weekday::Thursday, months: MonthSpec::Single(Month::November),
+        };
+        assert_eq!(to_date(thanksgiving.evaluate(&span).unwrap()), vec![date!(2024 - 11 - 28)]);
+
+        // May 2024 starts on a Wednesday, so the first Monday is not in the first week
+        let first_monday = CalendarPattern::NthWeekdayOfMonth {
+            n: 1, weekday: Weekday::Monday, months: MonthSpec::Range(Month::May..=Month::June),
+        };
+        assert_eq!(to_date(first_monday.evaluate(&span).unwrap()), vec![date!(2024 - 05 - 06), date!(2024 - 06 - 03)]);
+
+        let memorial_day = CalendarPattern::NthWeekdayOfMonth {
+            n: -1, weekday: Weekday::Monday, months: MonthSpec::Single(Month::May),
+        };
+        assert_eq!(to_date(memorial_day.evaluate(&span).unwrap()), vec![date!(2024 - 05 - 27)]);
+    }
+
+    #[test]
+    fn test_evaluate_observed() {
+        let holidays = AbstractCalendar::Pattern(CalendarPattern::FixedHolidays(vec![
+            date!(2021 - 01 - 01), date!(2021 - 12 - 25), date!(2022 - 01 - 01),
+        ]));
+        let observed = holidays.observed(vec![Weekday::Saturday, Weekday::Sunday], ObservanceRule::NearestWeekday);
+
+        // New Year's Day 2022 is a Saturday and is observed on the last day of 2021
+        let calendar = observed.evaluate(&DateSpan::Year(YearSpec::Single(2021))).unwrap();
+        assert_eq!(calendar.days_calendar.len(), 12);
+        assert_eq!(to_date(calendar), vec![date!(2021 - 01 - 01), date!(2021 - 12 - 24), date!(2021 - 12 - 31)]);
+    }
+
+    #[test]
+    fn test_evaluate_empty_span() {
+        let span = DateSpan::Date(DateSpec::List(HashSet::new()));
+        let calendar = AbstractCalendar::Pattern(CalendarPattern::Everyday).evaluate(&span).unwrap();
+        assert_eq!(calendar, DaysCalendar::empty());
+    }
+}
@@ -0,0 +1,136 @@
+/// The `error` module defines the error type shared by every fallible operation of the crate
+pub mod error {
+
+    use std::fmt;
+
+    use crate::types::{Year, Month, Date};
+    use crate::fiscal::FiscalPeriod;
+    use crate::hijri::MAX_ADJUSTMENT;
+
+    /// Error returned by the fallible operations of dayendar
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum DayendarError {
+        /// The year, month and day do not form a valid date
+        InvalidDate { year: i32, month: u8, day: u8 },
+        /// The month number is not between 1 and 12
+        InvalidMonth(u8),
+        /// The day number is beyond the longest month of the calendar
+        InvalidDay(u8),
+        /// The Hijri month adjustment is more than `MAX_ADJUSTMENT` days
+        InvalidAdjustment(i8),
+        /// The year is outside the supported range, from 1 to 9999
+        YearOutOfRange(i32),
+        /// A month of a calendar does not hold one value per day of the month
+        InvalidMonthLength { year: Year, month: Month, expected: u8, found: usize },
+        /// The same year month appears more than once in a calendar
+        DuplicatedMonth { year: Year, month: Month },
+        /// The months of a calendar are not in chronological order
+        UnsortedCalendar,
+        /// Moving from the date runs past the first or last business day of the calendar
+        HorizonExceeded(Date),
+        /// A year, month or date spec has an empty list or a reversed range
+        InvalidSpec(&'static str),
+        /// The ICMA reference period is empty or the coupon frequency is zero
+        InvalidReferencePeriod { start: Date, end: Date, frequency: u8 },
+        /// The quarter, period or week does not exist in the fiscal year
+        InvalidFiscalPeriod(FiscalPeriod),
+        /// A text can not be parsed, `position` is the byte offset of the error in `input`
+        ParseError { input: String, position: usize, message: String },
+    }
+
+    impl DayendarError {
+        pub(crate) fn parse_error(input: &str, position: usize, message: impl Into<String>) -> Self {
+            DayendarError::ParseError { input: input.to_string(), position, message: message.into() }
+        }
+
+        /// 1-based line and column, counted in characters, of a parse error
+        pub fn line_column(&self) -> Option<(usize, usize)> {
+            match self {
+                DayendarError::ParseError { input, position, .. } => {
+                    let before = &input[..(*position).min(input.len())];
+                    let line = before.matches('\n').count() + 1;
+                    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+                    Some((line, before[line_start..].chars().count() + 1))
+                },
+                _ => None,
+            }
+        }
+    }
+
+    impl fmt::Display for DayendarError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                DayendarError::InvalidDate { year, month, day } => {
+                    write!(f, "invalid date {:04}-{:02}-{:02}", year, month, day)
+                },
+                DayendarError::InvalidMonth(month) => {
+                    write!(f, "invalid month {}, expected a number from 1 to 12", month)
+                },
+                DayendarError::InvalidDay(day) => write!(f, "invalid day {} of the month", day),
+                DayendarError::InvalidAdjustment(days) => write!(
+                    f, "invalid month adjustment {} days, expected {} to {}", days, -MAX_ADJUSTMENT, MAX_ADJUSTMENT
+                ),
+                DayendarError::YearOutOfRange(year) => write!(f, "year {} out of range 1..=9999", year),
+                DayendarError::InvalidMonthLength { year, month, expected, found } => write!(
+                    f, "month {:04}-{:02} has {} days, expected {}",
+                    year, month.to_index(), found, expected
+                ),
+                DayendarError::DuplicatedMonth { year, month } => {
+                    write!(f, "duplicated month {:04}-{:02} in calendar", year, month.to_index())
+                },
+                DayendarError::UnsortedCalendar => write!(f, "calendar months are not sorted"),
+                DayendarError::HorizonExceeded(date) => {
+                    write!(f, "calendar horizon exceeded moving from {}", date)
+                },
+                DayendarError::InvalidSpec(reason) => write!(f, "invalid spec: {}", reason),
+                DayendarError::InvalidReferencePeriod { start, end, frequency } => write!(
+                    f, "invalid reference period {} to {} with frequency {}", start, end, frequency
+                ),
+                DayendarError::InvalidFiscalPeriod(period) => write!(f, "invalid fiscal period {}", period),
+                DayendarError::ParseError { message, .. } => {
+                    let (line, column) = self.line_column().unwrap_or((1, 1));
+                    write!(f, "line {}, column {}: {}", line, column, message)
+                },
+            }
+        }
+    }
+
+    impl std::error::Error for DayendarError {}
+
+}
+
+// =========================================
+// SECTION FOR UNIT TEST CODE ...
+// =========================================
+#[cfg(test)]
+mod tests_error {
+
+    use crate::error::*;
+    use crate::types::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(DayendarError::InvalidDate { year: 2023, month: 2, day: 29 }.to_string(), "invalid date 2023-02-29");
+        assert_eq!(DayendarError::YearOutOfRange(0).to_string(), "year 0 out of range 1..=9999");
+        assert_eq!(DayendarError::InvalidDay(32).to_string(), "invalid day 32 of the month");
+        assert_eq!(DayendarError::InvalidAdjustment(4).to_string(), "invalid month adjustment 4 days, expected -3 to 3");
+        assert_eq!(
+            DayendarError::InvalidMonthLength { year: 2024, month: Month::February, expected: 29, found: 28 }.to_string(),
+            "month 2024-02 has 28 days, expected 29"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_location() {
+        let error = DayendarError::parse_error("first\nsecond line", 13, "unexpected `l`");
+        assert_eq!(error.line_column(), Some((2, 8)));
+        assert_eq!(error.to_string(), "line 2, column 8: unexpected `l`");
+        assert_eq!(DayendarError::UnsortedCalendar.line_column(), None);
+    }
+
+    #[test]
+    fn test_is_std_error() {
+        let error: Box<dyn std::error::Error> = Box::new(DayendarError::InvalidMonth(13));
+        assert_eq!(error.to_string(), "invalid month 13, expected a number from 1 to 12");
+    }
+}
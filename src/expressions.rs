@@ -0,0 +1,2331 @@
+/// The `cron` module parses Quartz-style cron expressions and materializes their days.
+///
+/// Only the day-relevant fields (day-of-month, month, day-of-week and year) take part
+/// in the evaluation, the time fields are validated but otherwise ignored.
+pub mod cron {
+
+    use std::fmt;
+    use std::str::FromStr;
+
+    use crate::types::{Year, Month, Day, BiDay, Date, DateSpan};
+    use crate::utils::{days_in_month, checked_days_in_month, checked_date};
+    use crate::calendar::DaysCalendar;
+    use crate::error::DayendarError;
+
+    const MONTH_NAMES: [&str; 12] = [
+        "JAN", "FEB", "MAR", "APR", "MAY", "JUN",
+        "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+    ];
+    const WEEKDAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+    /// Fields of a cron expression, in the order they are written
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    enum CronField {
+        Seconds,
+        Minutes,
+        Hours,
+        DayOfMonth,
+        Month,
+        DayOfWeek,
+        Year,
+        /// The expression as a whole, e.g. when the number of fields is wrong
+        Expression,
+    }
+
+    impl CronField {
+        /// 1-based position of the field in the expression
+        fn position(&self) -> Option<usize> {
+            match self {
+                CronField::Seconds => Some(1),
+                CronField::Minutes => Some(2),
+                CronField::Hours => Some(3),
+                CronField::DayOfMonth => Some(4),
+                CronField::Month => Some(5),
+                CronField::DayOfWeek => Some(6),
+                CronField::Year => Some(7),
+                CronField::Expression => None,
+            }
+        }
+    }
+
+    impl fmt::Display for CronField {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let name = match self {
+                CronField::Seconds => "seconds",
+                CronField::Minutes => "minutes",
+                CronField::Hours => "hours",
+                CronField::DayOfMonth => "day-of-month",
+                CronField::Month => "month",
+                CronField::DayOfWeek => "day-of-week",
+                CronField::Year => "year",
+                CronField::Expression => "expression",
+            };
+            write!(f, "{}", name)
+        }
+    }
+
+    /// Problem found while parsing a cron expression, reported as a `DayendarError::ParseError`
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct CronError {
+        /// The field containing the error
+        field: CronField,
+        /// The offending text
+        value: String,
+        /// Description of the problem
+        reason: &'static str,
+    }
+
+    impl CronError {
+        fn new(field: CronField, value: &str, reason: &'static str) -> Self {
+            CronError { field, value: value.to_string(), reason }
+        }
+
+        /// Converts the error, locating the offending text inside the field it belongs to
+        fn into_parse_error(self, input: &str, fields: &[&str]) -> DayendarError {
+            let position = match self.field.position().and_then(|n| fields.get(n - 1)) {
+                Some(field) => {
+                    let start = field.as_ptr() as usize - input.as_ptr() as usize;
+                    let offset = field.to_ascii_uppercase().find(&self.value.to_ascii_uppercase()).unwrap_or(0);
+                    start + offset
+                },
+                None => 0,
+            };
+            DayendarError::parse_error(input, position, self.to_string())
+        }
+    }
+
+    impl fmt::Display for CronError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self.field.position() {
+                Some(position) => write!(
+                    f, "invalid {} field (#{}) `{}`: {}",
+                    self.field, position, self.value, self.reason
+                ),
+                None => write!(f, "invalid cron expression `{}`: {}", self.value, self.reason),
+            }
+        }
+    }
+
+    /// Day-of-month rules of a cron expression
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    struct DayOfMonthRule {
+        /// Bit `n` is set when day `n` is selected
+        days: u32,
+        /// Days before the last day of the month (`L` is 0, `L-3` is 3)
+        last_offsets: Vec<u8>,
+        /// Last weekday (Monday to Friday) of the month (`LW`)
+        last_weekday: bool,
+        /// Weekday nearest to the given days (`15W`)
+        nearest_weekdays: Vec<u8>,
+    }
+
+    impl DayOfMonthRule {
+        fn matches(&self, day: Day, date: Date, n_days: Day) -> bool {
+            if self.days & (1 << day) != 0 {
+                return true;
+            }
+            if self.last_offsets.iter().any(|&offset| offset < n_days && day == n_days - offset) {
+                return true;
+            }
+            if self.last_weekday && day == nearest_weekday(n_days, n_days, date, day) {
+                return true;
+            }
+            self.nearest_weekdays.iter()
+                .filter(|&&target| target <= n_days)
+                .any(|&target| day == nearest_weekday(target, n_days, date, day))
+        }
+    }
+
+    /// Day-of-week rules of a cron expression, weekdays are numbered 1 (Sunday) to 7 (Saturday)
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    struct DayOfWeekRule {
+        /// Bit `n` is set when weekday `n` is selected
+        weekdays: u8,
+        /// Bit `n` is set when the last weekday `n` of the month is selected (`6L`)
+        last: u8,
+        /// Selected (weekday, occurrence) pairs (`6#3`)
+        nth: Vec<(u8, u8)>,
+    }
+
+    impl DayOfWeekRule {
+        fn matches(&self, day: Day, date: Date, n_days: Day) -> bool {
+            let weekday: u8 = date.weekday().number_from_sunday();
+            if self.weekdays & (1 << weekday) != 0 {
+                return true;
+            }
+            if self.last & (1 << weekday) != 0 && day + 7 > n_days {
+                return true;
+            }
+            let occurrence: u8 = (day - 1) / 7 + 1;
+            self.nth.iter().any(|&(w, k)| w == weekday && k == occurrence)
+        }
+    }
+
+    /// A parsed Quartz-style cron expression.
+    ///
+    /// The expression has six or seven whitespace separated fields:
+    /// `seconds minutes hours day-of-month month day-of-week [year]`.
+    /// Exactly one of day-of-month and day-of-week must be `?`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dayendar::cron::CronExpression;
+    /// use dayendar::types::Month;
+    ///
+    /// // Last Friday of every month
+    /// let cron: CronExpression = "0 0 12 ? * 6L".parse().unwrap();
+    /// let calendar = cron.evaluate_months(&[(2024, Month::March)]).unwrap();
+    /// assert_eq!(calendar.get_days(2024, Month::March).unwrap()[28].to_u8(), 1);
+    /// ```
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct CronExpression {
+        day_of_month: Option<DayOfMonthRule>,
+        months: u16,
+        day_of_week: Option<DayOfWeekRule>,
+        years: Option<Vec<(Year, Year, Year)>>,
+    }
+
+    impl CronExpression {
+        /// Parses a cron expression
+        pub fn parse(input: &str) -> Result<Self, DayendarError> {
+            let fields: Vec<&str> = input.split_whitespace().collect();
+            Self::parse_fields(input, &fields).map_err(|error| error.into_parse_error(input, &fields))
+        }
+
+        fn parse_fields(input: &str, fields: &[&str]) -> Result<Self, CronError> {
+            if fields.len() != 6 && fields.len() != 7 {
+                return Err(CronError::new(CronField::Expression, input, "expected 6 or 7 fields"));
+            }
+
+            parse_numeric_field(fields[0], CronField::Seconds, 0, 59, &[])?;
+            parse_numeric_field(fields[1], CronField::Minutes, 0, 59, &[])?;
+            parse_numeric_field(fields[2], CronField::Hours, 0, 23, &[])?;
+
+            let day_of_month: Option<DayOfMonthRule> = parse_day_of_month(fields[3])?;
+            let months: u16 = to_bitmask(&parse_numeric_field(fields[4], CronField::Month, 1, 12, &MONTH_NAMES)?) as u16;
+            let day_of_week: Option<DayOfWeekRule> = parse_day_of_week(fields[5])?;
+
+            match (&day_of_month, &day_of_week) {
+                (Some(_), Some(_)) => {
+                    return Err(CronError::new(
+                        CronField::DayOfWeek, fields[5],
+                        "one of day-of-month and day-of-week must be `?`",
+                    ));
+                },
+                (None, None) => {
+                    return Err(CronError::new(
+                        CronField::DayOfWeek, fields[5],
+                        "day-of-month and day-of-week can not both be `?`",
+                    ));
+                },
+                _ => {},
+            }
+
+            let years: Option<Vec<(Year, Year, Year)>> = match fields.get(6) {
+                Some(&"*") | None => None,
+                Some(field) => Some(
+                    parse_ranges(field, CronField::Year, 1, 9999, &[])?
+                        .into_iter()
+                        .map(|(start, end, step)| (start as Year, end as Year, step as Year))
+                        .collect(),
+                ),
+            };
+
+            Ok(CronExpression { day_of_month, months, day_of_week, years })
+        }
+
+        /// Determines if the expression fires on the given year
+        pub fn matches_year(&self, year: Year) -> bool {
+            match &self.years {
+                None => true,
+                Some(ranges) => ranges.iter()
+                    .any(|&(start, end, step)| year >= start && year <= end && (year - start).is_multiple_of(step)),
+            }
+        }
+
+        /// Determines if the expression fires on the given month
+        pub fn matches_month(&self, month: Month) -> bool {
+            self.months & (1 << month.to_index()) != 0
+        }
+
+        /// Determines if the expression fires on the given date
+        pub fn matches(&self, date: Date) -> bool {
+            let year: Year = match Year::try_from(date.year()) {
+                Ok(year) => year,
+                Err(_) => return false,
+            };
+            let month: Month = match Month::from_index(date.month() as u8) {
+                Some(month) => month,
+                None => return false,
+            };
+            let n_days: Day = match days_in_month(year, month) {
+                Some(n_days) => n_days,
+                None => return false,
+            };
+            self.matches_year(year) && self.matches_month(month) && self.matches_day(date.day(), date, n_days)
+        }
+
+        fn matches_day(&self, day: Day, date: Date, n_days: Day) -> bool {
+            match (&self.day_of_month, &self.day_of_week) {
+                (Some(rule), _) => rule.matches(day, date, n_days),
+                (None, Some(rule)) => rule.matches(day, date, n_days),
+                (None, None) => false,
+            }
+        }
+
+        /// Materializes the days of a single year month in which the expression fires
+        pub fn materialize(&self, year: Year, month: Month) -> Result<Vec<BiDay>, DayendarError> {
+            let n_days: Day = checked_days_in_month(year, month)?;
+            let mut days: Vec<BiDay> = vec![BiDay::Zero; n_days as usize];
+
+            if self.matches_year(year) && self.matches_month(month) {
+                let mut date: Date = checked_date(year, month, 1)?;
+                for (index, day) in days.iter_mut().enumerate() {
+                    if self.matches_day(index as Day + 1, date, n_days) {
+                        *day = BiDay::One;
+                    }
+                    date = date.next_day().unwrap_or(date);
+                }
+            }
+
+            Ok(days)
+        }
+
+        /// Materializes the expression for an ordered list of year months
+        pub fn evaluate_months(&self, year_months: &[(Year, Month)]) -> Result<DaysCalendar<BiDay>, DayendarError> {
+            let days_calendar: Vec<(Year, Month, Vec<BiDay>)> = year_months.iter()
+                .map(|&(year, month)| self.materialize(year, month).map(|days| (year, month, days)))
+                .collect::<Result<_, _>>()?;
+            Ok(DaysCalendar { days_calendar })
+        }
+
+        /// Materializes the expression for every month of a `DateSpan`
+        pub fn evaluate(&self, span: &DateSpan) -> Result<DaysCalendar<BiDay>, DayendarError> {
+            let mut year_months: Vec<(Year, Month)> = span.to_year_month().into_iter().collect();
+            year_months.sort_unstable();
+            self.evaluate_months(&year_months)
+        }
+    }
+
+    impl FromStr for CronExpression {
+        type Err = DayendarError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            CronExpression::parse(s)
+        }
+    }
+
+    /// Finds the weekday (Monday to Friday) nearest to `target` without leaving the month.
+    /// `date` is any date of the month and `day` its day number.
+    fn nearest_weekday(target: Day, n_days: Day, date: Date, day: Day) -> Day {
+        // Weekday of the target day, numbered from Monday = 0
+        let weekday: u8 = (date.weekday().number_days_from_monday() + 7 * 5 + target - day) % 7;
+        match weekday {
+            5 if target == 1 => target + 2,
+            5 => target - 1,
+            6 if target == n_days => target - 2,
+            6 => target + 1,
+            _ => target,
+        }
+    }
+
+    fn to_bitmask(ranges: &[(u32, u32, u32)]) -> u32 {
+        let mut mask: u32 = 0;
+        for &(start, end, step) in ranges {
+            let mut value = start;
+            while value <= end {
+                mask |= 1 << value;
+                value += step;
+            }
+        }
+        mask
+    }
+
+    fn parse_value(value: &str, field: CronField, min: u32, max: u32, names: &[&str]) -> Result<u32, CronError> {
+        let upper = value.to_uppercase();
+        let parsed: u32 = match names.iter().position(|name| *name == upper) {
+            Some(index) => index as u32 + 1,
+            None => value.parse::<u32>()
+                .map_err(|_| CronError::new(field, value, "not a number or a valid name"))?,
+        };
+        if parsed < min || parsed > max {
+            return Err(CronError::new(field, value, "value out of range"));
+        }
+        Ok(parsed)
+    }
+
+    /// Parses the `*`, `n`, `a-b` and `/step` forms of a list of items into (start, end, step) ranges
+    fn parse_ranges(input: &str, field: CronField, min: u32, max: u32, names: &[&str])
+        -> Result<Vec<(u32, u32, u32)>, CronError>
+    {
+        input.split(',').map(|item| parse_range(item, field, min, max, names)).collect()
+    }
+
+    fn parse_range(item: &str, field: CronField, min: u32, max: u32, names: &[&str])
+        -> Result<(u32, u32, u32), CronError>
+    {
+        if item.is_empty() {
+            return Err(CronError::new(field, item, "empty list item"));
+        }
+
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse::<u32>()
+                    .map_err(|_| CronError::new(field, item, "step is not a number"))?;
+                if step == 0 {
+                    return Err(CronError::new(field, item, "step must be greater than zero"));
+                }
+                // Larger steps select the start alone and would overflow the bit masks and years
+                if step > max {
+                    return Err(CronError::new(field, item, "step is greater than the maximum value"));
+                }
+                (range, Some(step))
+            },
+            None => (item, None),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            let start = parse_value(start, field, min, max, names)?;
+            let end = parse_value(end, field, min, max, names)?;
+            if start > end {
+                return Err(CronError::new(field, item, "range start is greater than range end"));
+            }
+            (start, end)
+        } else {
+            let value = parse_value(range, field, min, max, names)?;
+            // `n/step` runs from `n` up to the maximum
+            if step.is_some() { (value, max) } else { (value, value) }
+        };
+
+        Ok((start, end, step.unwrap_or(1)))
+    }
+
+    fn parse_numeric_field(input: &str, field: CronField, min: u32, max: u32, names: &[&str])
+        -> Result<Vec<(u32, u32, u32)>, CronError>
+    {
+        if input == "?" {
+            return Err(CronError::new(field, input, "`?` is only allowed in day-of-month and day-of-week"));
+        }
+        parse_ranges(input, field, min, max, names)
+    }
+
+    fn parse_day_of_month(input: &str) -> Result<Option<DayOfMonthRule>, CronError> {
+        let field = CronField::DayOfMonth;
+        if input == "?" {
+            return Ok(None);
+        }
+
+        let mut rule = DayOfMonthRule::default();
+        for item in input.split(',') {
+            let upper = item.to_uppercase();
+            if upper == "LW" {
+                rule.last_weekday = true;
+            } else if upper == "L" {
+                rule.last_offsets.push(0);
+            } else if let Some(offset) = upper.strip_prefix("L-") {
+                let offset = parse_value(offset, field, 0, 30, &[])?;
+                rule.last_offsets.push(offset as u8);
+            } else if let Some(day) = upper.strip_suffix('W') {
+                let day = parse_value(day, field, 1, 31, &[])?;
+                rule.nearest_weekdays.push(day as u8);
+            } else {
+                rule.days |= to_bitmask(&[parse_range(item, field, 1, 31, &[])?]);
+            }
+        }
+        Ok(Some(rule))
+    }
+
+    fn parse_day_of_week(input: &str) -> Result<Option<DayOfWeekRule>, CronError> {
+        let field = CronField::DayOfWeek;
+        if input == "?" {
+            return Ok(None);
+        }
+
+        let mut rule = DayOfWeekRule::default();
+        for item in input.split(',') {
+            let upper = item.to_uppercase();
+            if upper == "L" {
+                // On its own `L` means Saturday
+                rule.weekdays |= 1 << 7;
+            } else if let Some((weekday, nth)) = upper.split_once('#') {
+                let weekday = parse_value(weekday, field, 1, 7, &WEEKDAY_NAMES)?;
+                let nth = parse_value(nth, field, 1, 5, &[])?;
+                rule.nth.push((weekday as u8, nth as u8));
+            } else if let Some(weekday) = upper.strip_suffix('L') {
+                let weekday = parse_value(weekday, field, 1, 7, &WEEKDAY_NAMES)?;
+                rule.last |= 1 << weekday;
+            } else {
+                rule.weekdays |= to_bitmask(&[parse_range(item, field, 1, 7, &WEEKDAY_NAMES)?]) as u8;
+            }
+        }
+        Ok(Some(rule))
+    }
+
+}
+
+
+/// The `rrule` module evaluates RFC 5545 recurrence rules with a day granularity.
+///
+/// A rule is written as in an iCalendar file, a `DTSTART` line followed by a `RRULE` line,
+/// e.g. `DTSTART:20240109 RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU`. The lines can be separated
+/// by any whitespace and the time of a DATE-TIME value is ignored.
+pub mod rrule {
+
+    use std::collections::BTreeSet;
+    use std::str::FromStr;
+
+    use crate::types::{Year, Month, BiDay, Weekday, Date, DateSpan, Duration, MAX_DATE};
+    use crate::utils::month_bounds;
+    use crate::calendar::DaysCalendar;
+    use crate::error::DayendarError;
+    use crate::ics::parse_instant;
+
+    const WEEKDAY_NAMES: [&str; 7] = ["MO", "TU", "WE", "TH", "FR", "SA", "SU"];
+
+    /// Frequency of a recurrence rule, the finer frequencies of RFC 5545 have no meaning for days
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum Frequency {
+        Daily,
+        Weekly,
+        Monthly,
+        Yearly,
+    }
+
+    /// A parsed recurrence rule anchored at its DTSTART date.
+    ///
+    /// Every rule part of RFC 5545 that selects days is supported: FREQ, INTERVAL, COUNT, UNTIL,
+    /// BYDAY (with ordinal prefixes), BYMONTHDAY, BYMONTH, BYYEARDAY, BYWEEKNO, BYSETPOS and WKST.
+    /// The DTSTART date is an occurrence only when it matches the rule.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dayendar::rrule::RRule;
+    /// use dayendar::types::date;
+    ///
+    /// // Last weekday of the month
+    /// let rule: RRule = "DTSTART:20240101 RRULE:FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1".parse().unwrap();
+    /// let dates: Vec<_> = rule.occurrences().take(3).collect();
+    /// assert_eq!(dates, vec![date!(2024 - 01 - 31), date!(2024 - 02 - 29), date!(2024 - 03 - 29)]);
+    /// ```
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct RRule {
+        dtstart: Date,
+        frequency: Frequency,
+        interval: u32,
+        count: Option<u32>,
+        until: Option<Date>,
+        /// (occurrence, weekday) pairs, occurrence 0 selects every such weekday
+        by_day: Vec<(i16, Weekday)>,
+        by_month_day: Vec<i8>,
+        by_month: Vec<Month>,
+        by_year_day: Vec<i16>,
+        by_week_no: Vec<i8>,
+        by_set_pos: Vec<i16>,
+        week_start: Weekday,
+    }
+
+    impl RRule {
+        /// Parses a `DTSTART` line followed by a `RRULE` line
+        pub fn parse(input: &str) -> Result<Self, DayendarError> {
+            let mut lines = input.split_whitespace();
+            let (dtstart, rule) = match (lines.next(), lines.next(), lines.next()) {
+                (Some(dtstart), Some(rule), None) => (dtstart, rule),
+                _ => return Err(DayendarError::parse_error(input, 0, "expected a DTSTART line followed by a RRULE line")),
+            };
+            let value = match dtstart.split_once(':') {
+                Some((name, value)) if name.split(';').next().is_some_and(|name| name.eq_ignore_ascii_case("DTSTART")) => value,
+                _ => return Err(DayendarError::parse_error(input, offset_of(input, dtstart), "expected a DTSTART line")),
+            };
+            let start: Date = parse_instant(value)
+                .ok_or_else(|| DayendarError::parse_error(input, offset_of(input, value), format!("invalid DTSTART `{}`", value)))?
+                .date();
+            Self::parse_rule(input, rule, start)
+        }
+
+        /// Parses the value of a `RRULE` line, with or without the `RRULE:` prefix, anchored at `dtstart`
+        pub fn with_start(dtstart: Date, rule: &str) -> Result<Self, DayendarError> {
+            Self::parse_rule(rule, rule.trim(), dtstart)
+        }
+
+        fn parse_rule(input: &str, rule: &str, dtstart: Date) -> Result<Self, DayendarError> {
+            let rule = match rule.split_once(':') {
+                Some((name, value)) if name.eq_ignore_ascii_case("RRULE") => value,
+                _ => rule,
+            };
+            let mut parsed = RRule {
+                dtstart,
+                frequency: Frequency::Daily,
+                interval: 1,
+                count: None,
+                until: None,
+                by_day: Vec::new(),
+                by_month_day: Vec::new(),
+                by_month: Vec::new(),
+                by_year_day: Vec::new(),
+                by_week_no: Vec::new(),
+                by_set_pos: Vec::new(),
+                week_start: Weekday::Monday,
+            };
+            let mut frequency: Option<Frequency> = None;
+
+            for part in rule.split(';').filter(|part| !part.is_empty()) {
+                let error = |message: String| DayendarError::parse_error(input, offset_of(input, part), message);
+                let (name, value) = part.split_once('=')
+                    .ok_or_else(|| error(format!("expected `NAME=value`, found `{}`", part)))?;
+                let invalid = || error(format!("invalid {} `{}`", name, value));
+                let values = || value.split(',');
+                match name.to_ascii_uppercase().as_str() {
+                    "FREQ" => frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        "SECONDLY" | "MINUTELY" | "HOURLY" => {
+                            return Err(error(format!("unsupported frequency `{}`, days can not recur more than daily", value)));
+                        },
+                        _ => return Err(invalid()),
+                    }),
+                    "INTERVAL" => parsed.interval = value.parse().ok().filter(|&n| n > 0).ok_or_else(invalid)?,
+                    "COUNT" => parsed.count = Some(value.parse().map_err(|_| invalid())?),
+                    "UNTIL" => parsed.until = Some(parse_instant(value).ok_or_else(invalid)?.date()),
+                    "BYDAY" => parsed.by_day = values().map(parse_by_day).collect::<Option<_>>().ok_or_else(invalid)?,
+                    "BYMONTHDAY" => parsed.by_month_day = values().map(|v| signed(v, 31)).collect::<Option<_>>().ok_or_else(invalid)?,
+                    "BYMONTH" => parsed.by_month = values()
+                        .map(|v| v.parse::<u8>().ok().and_then(Month::from_index))
+                        .collect::<Option<_>>()
+                        .ok_or_else(invalid)?,
+                    "BYYEARDAY" => parsed.by_year_day = values().map(|v| signed(v, 366)).collect::<Option<_>>().ok_or_else(invalid)?,
+                    "BYWEEKNO" => parsed.by_week_no = values().map(|v| signed(v, 53)).collect::<Option<_>>().ok_or_else(invalid)?,
+                    "BYSETPOS" => parsed.by_set_pos = values().map(|v| signed(v, 366)).collect::<Option<_>>().ok_or_else(invalid)?,
+                    "WKST" => parsed.week_start = parse_weekday(value).ok_or_else(invalid)?,
+                    "BYHOUR" | "BYMINUTE" | "BYSECOND" => {
+                        return Err(error(format!("unsupported rule part {}, rules are evaluated by day", name)));
+                    },
+                    _ => return Err(error(format!("unknown rule part `{}`", name))),
+                }
+                if parsed.count.is_some() && parsed.until.is_some() {
+                    return Err(error(String::from("COUNT and UNTIL can not be used together")));
+                }
+            }
+
+            parsed.frequency = frequency.ok_or_else(|| DayendarError::parse_error(input, offset_of(input, rule), "missing FREQ"))?;
+            parsed.check_frequency(input, rule)?;
+            Ok(parsed)
+        }
+
+        /// Rejects the rule parts that RFC 5545 does not allow with the frequency
+        fn check_frequency(&self, input: &str, rule: &str) -> Result<(), DayendarError> {
+            let message = match self.frequency {
+                Frequency::Daily | Frequency::Weekly if self.by_day.iter().any(|&(n, _)| n != 0) => {
+                    "BYDAY occurrences are only allowed with MONTHLY and YEARLY frequencies"
+                },
+                Frequency::Weekly if !self.by_month_day.is_empty() => "BYMONTHDAY is not allowed with a WEEKLY frequency",
+                Frequency::Daily | Frequency::Weekly | Frequency::Monthly if !self.by_year_day.is_empty() => {
+                    "BYYEARDAY is only allowed with a YEARLY frequency"
+                },
+                Frequency::Daily | Frequency::Weekly | Frequency::Monthly if !self.by_week_no.is_empty() => {
+                    "BYWEEKNO is only allowed with a YEARLY frequency"
+                },
+                _ => return Ok(()),
+            };
+            Err(DayendarError::parse_error(input, offset_of(input, rule), message))
+        }
+
+        /// First date of the rule, the occurrences never precede it
+        pub fn dtstart(&self) -> Date {
+            self.dtstart
+        }
+
+        /// Frequency of the rule
+        pub fn frequency(&self) -> Frequency {
+            self.frequency
+        }
+
+        /// Iterates the occurrences of the rule in chronological order.
+        ///
+        /// Without COUNT or UNTIL the iteration goes on to the year 9999.
+        pub fn occurrences(&self) -> Occurrences<'_> {
+            self.occurrences_until(MAX_DATE)
+        }
+
+        fn occurrences_until(&self, horizon: Date) -> Occurrences<'_> {
+            let period_start: Date = match self.frequency {
+                Frequency::Daily => self.dtstart,
+                Frequency::Weekly => week_start(self.dtstart, self.week_start),
+                Frequency::Monthly => self.dtstart.replace_day(1).unwrap_or(self.dtstart),
+                Frequency::Yearly => self.dtstart.replace_ordinal(1).unwrap_or(self.dtstart),
+            };
+            Occurrences { rule: self, period: 0, period_start: Some(period_start), pending: Vec::new(), emitted: 0, horizon }
+        }
+
+        /// Dates of the period starting at `start` that match the rule, in chronological order
+        fn period_dates(&self, start: Date) -> Vec<Date> {
+            let end: Date = match self.frequency {
+                Frequency::Daily => start,
+                Frequency::Weekly => start.checked_add(Duration::days(6)).unwrap_or(MAX_DATE),
+                Frequency::Monthly => last_of_month(start),
+                Frequency::Yearly => start.replace_month(time::Month::December).and_then(|d| d.replace_day(31)).unwrap_or(start),
+            };
+            let dates: Vec<Date> = std::iter::successors(Some(start), |date| date.next_day())
+                .take_while(|date| *date <= end)
+                .filter(|date| self.matches(*date))
+                .collect();
+            if self.by_set_pos.is_empty() {
+                return dates;
+            }
+            let selected: BTreeSet<Date> = self.by_set_pos.iter()
+                .filter_map(|&position| {
+                    let index = if position > 0 { position as usize - 1 } else { dates.len().checked_sub(position.unsigned_abs() as usize)? };
+                    dates.get(index).copied()
+                })
+                .collect();
+            selected.into_iter().collect()
+        }
+
+        /// Determines if a date of a period passes every rule part
+        fn matches(&self, date: Date) -> bool {
+            let defaults = self.by_day.is_empty() && self.by_month_day.is_empty()
+                && self.by_year_day.is_empty() && self.by_week_no.is_empty();
+            let by_month: &[Month] = &self.by_month;
+            let month = Month::from_index(date.month() as u8);
+            if !by_month.is_empty() && !month.is_some_and(|month| by_month.contains(&month)) {
+                return false;
+            }
+            if defaults {
+                // Without day selectors the rule repeats the day of DTSTART
+                return match self.frequency {
+                    Frequency::Daily => true,
+                    Frequency::Weekly => date.weekday() == self.dtstart.weekday(),
+                    Frequency::Monthly => date.day() == self.dtstart.day(),
+                    Frequency::Yearly => {
+                        date.day() == self.dtstart.day() && (!by_month.is_empty() || date.month() == self.dtstart.month())
+                    },
+                };
+            }
+            if !self.by_week_no.is_empty() {
+                let (week, weeks) = week_number(date, self.week_start);
+                if !self.by_week_no.iter().any(|&n| i32::from(n) == week || i32::from(n) == week - weeks - 1) {
+                    return false;
+                }
+            }
+            if !self.by_year_day.is_empty() {
+                let ordinal = i32::from(date.ordinal());
+                let length = if time::util::is_leap_year(date.year()) { 366 } else { 365 };
+                if !self.by_year_day.iter().any(|&n| i32::from(n) == ordinal || i32::from(n) == ordinal - length - 1) {
+                    return false;
+                }
+            }
+            if !self.by_month_day.is_empty() {
+                let day = i32::from(date.day());
+                let length = i32::from(last_of_month(date).day());
+                if !self.by_month_day.iter().any(|&n| i32::from(n) == day || i32::from(n) == day - length - 1) {
+                    return false;
+                }
+            }
+            if !self.by_day.is_empty() {
+                // Occurrences count within the month for MONTHLY rules and YEARLY rules with BYMONTH
+                let (index, length) = if self.frequency == Frequency::Monthly || !by_month.is_empty() {
+                    (i32::from(date.day()) - 1, i32::from(last_of_month(date).day()))
+                } else {
+                    let length = if time::util::is_leap_year(date.year()) { 366 } else { 365 };
+                    (i32::from(date.ordinal()) - 1, length)
+                };
+                let nth = index / 7 + 1;
+                let nth_last = -((length - index - 1) / 7 + 1);
+                let weekday = date.weekday();
+                if !self.by_day.iter().any(|&(n, w)| w == weekday && (n == 0 || i32::from(n) == nth || i32::from(n) == nth_last)) {
+                    return false;
+                }
+            }
+            true
+        }
+
+        /// Materializes the days of a single year month in which the rule occurs
+        pub fn materialize(&self, year: Year, month: Month) -> Result<Vec<BiDay>, DayendarError> {
+            let calendar = self.evaluate_months(&[(year, month)])?;
+            Ok(calendar.days_calendar.into_iter().next().map(|(_, _, days)| days).unwrap_or_default())
+        }
+
+        /// Materializes the rule for an ordered list of year months
+        pub fn evaluate_months(&self, year_months: &[(Year, Month)]) -> Result<DaysCalendar<BiDay>, DayendarError> {
+            let mut horizon: Date = self.dtstart;
+            for &(year, month) in year_months {
+                let (_, last): (Date, Date) = month_bounds(year, month)?;
+                horizon = horizon.max(last);
+            }
+            let dates: BTreeSet<Date> = self.occurrences_until(horizon).collect();
+
+            let mut days_calendar: Vec<(Year, Month, Vec<BiDay>)> = Vec::with_capacity(year_months.len());
+            for &(year, month) in year_months {
+                let (first, last): (Date, Date) = month_bounds(year, month)?;
+                let mut days: Vec<BiDay> = vec![BiDay::Zero; last.day() as usize];
+                for date in dates.range(first..=last) {
+                    days[date.day() as usize - 1] = BiDay::One;
+                }
+                days_calendar.push((year, month, days));
+            }
+            Ok(DaysCalendar { days_calendar })
+        }
+
+        /// Materializes the rule for every month of a `DateSpan`
+        pub fn evaluate(&self, span: &DateSpan) -> Result<DaysCalendar<BiDay>, DayendarError> {
+            let mut year_months: Vec<(Year, Month)> = span.to_year_month().into_iter().collect();
+            year_months.sort_unstable();
+            self.evaluate_months(&year_months)
+        }
+    }
+
+    impl FromStr for RRule {
+        type Err = DayendarError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            RRule::parse(s)
+        }
+    }
+
+    /// Iterator over the occurrences of a `RRule`
+    #[derive(Debug, Clone)]
+    pub struct Occurrences<'a> {
+        rule: &'a RRule,
+        /// Number of periods already expanded
+        period: u32,
+        period_start: Option<Date>,
+        /// Occurrences of the current period not yet returned, in reverse order
+        pending: Vec<Date>,
+        emitted: u32,
+        /// Periods starting after this date are not expanded
+        horizon: Date,
+    }
+
+    impl Occurrences<'_> {
+        /// Start of the period `n` periods after the one of DTSTART
+        fn nth_period_start(&self, n: u32) -> Option<Date> {
+            let rule = self.rule;
+            let steps: i64 = i64::from(n) * i64::from(rule.interval);
+            match rule.frequency {
+                Frequency::Daily => rule.dtstart.checked_add(Duration::days(steps)),
+                Frequency::Weekly => week_start(rule.dtstart, rule.week_start).checked_add(Duration::weeks(steps)),
+                Frequency::Monthly => {
+                    let months: i64 = i64::from(rule.dtstart.year()) * 12 + i64::from(rule.dtstart.month() as u8 - 1) + steps;
+                    let month = time::Month::try_from((months % 12) as u8 + 1).ok()?;
+                    Date::from_calendar_date(i32::try_from(months / 12).ok()?, month, 1).ok()
+                },
+                Frequency::Yearly => Date::from_ordinal_date(i32::try_from(i64::from(rule.dtstart.year()) + steps).ok()?, 1).ok(),
+            }
+        }
+    }
+
+    impl Iterator for Occurrences<'_> {
+        type Item = Date;
+
+        fn next(&mut self) -> Option<Date> {
+            loop {
+                if self.rule.count.is_some_and(|count| self.emitted >= count) {
+                    return None;
+                }
+                if let Some(date) = self.pending.pop() {
+                    if self.rule.until.is_some_and(|until| date > until) {
+                        self.pending.clear();
+                        self.period_start = None;
+                        return None;
+                    }
+                    self.emitted += 1;
+                    return Some(date);
+                }
+                let start: Date = self.period_start.filter(|start| *start <= self.horizon && *start <= MAX_DATE)?;
+                let dtstart: Date = self.rule.dtstart;
+                self.pending = self.rule.period_dates(start).into_iter().filter(|date| *date >= dtstart).rev().collect();
+                self.period += 1;
+                self.period_start = self.nth_period_start(self.period);
+            }
+        }
+    }
+
+    /// Byte offset of a slice of `input` inside it
+    fn offset_of(input: &str, part: &str) -> usize {
+        (part.as_ptr() as usize).saturating_sub(input.as_ptr() as usize).min(input.len())
+    }
+
+    fn signed<T: TryFrom<i32> + Copy>(value: &str, max: i32) -> Option<T> {
+        let number: i32 = value.trim_start_matches('+').parse().ok()?;
+        if number == 0 || number.abs() > max {
+            return None;
+        }
+        T::try_from(number).ok()
+    }
+
+    fn parse_weekday(value: &str) -> Option<Weekday> {
+        let index = WEEKDAY_NAMES.iter().position(|name| name.eq_ignore_ascii_case(value))?;
+        Some(Weekday::Sunday.nth_next(index as u8 + 1))
+    }
+
+    /// Parses a BYDAY item such as `TU`, `2MO` or `-1FR`
+    fn parse_by_day(value: &str) -> Option<(i16, Weekday)> {
+        let split = value.len().checked_sub(2)?;
+        let weekday = parse_weekday(value.get(split..)?)?;
+        let n: i16 = match &value[..split] {
+            "" => 0,
+            n => signed(n, 53)?,
+        };
+        Some((n, weekday))
+    }
+
+    fn last_of_month(date: Date) -> Date {
+        let last: u8 = date.month().length(date.year());
+        date.replace_day(last).unwrap_or(date)
+    }
+
+    /// First day of the week of `date`, weeks starting on `first`
+    fn week_start(date: Date, first: Weekday) -> Date {
+        let offset = (7 + date.weekday().number_days_from_monday() - first.number_days_from_monday()) % 7;
+        date - Duration::days(offset.into())
+    }
+
+    /// Week number of a date and number of weeks of its week numbering year, weeks starting on `first`.
+    /// Week 1 is the first week with at least four days in the year.
+    fn week_number(date: Date, first: Weekday) -> (i32, i32) {
+        let start: i32 = week_start(date, first).to_julian_day();
+        let year: i32 = Date::from_julian_day(start + 3).map_or(date.year() + 1, |thursday| thursday.year());
+        let Ok(january_4) = Date::from_calendar_date(year, time::Month::January, 4) else {
+            return (0, 0);
+        };
+        // Julian day numbers are used for the next year, which can be past the last supported date
+        let week_1: i32 = week_start(january_4, first).to_julian_day();
+        let next_january_4: i32 = january_4.to_julian_day() + if time::util::is_leap_year(year) { 366 } else { 365 };
+        let next_week_1: i32 = next_january_4 - (7 + next_january_4.rem_euclid(7) - i32::from(first.number_days_from_monday())) % 7;
+        ((start - week_1) / 7 + 1, (next_week_1 - week_1) / 7)
+    }
+
+}
+
+
+/// The `dsl` module provides a text syntax for abstract calendars.
+///
+/// The syntax is the one produced by the `Display` implementations of `AbstractCalendar`,
+/// `CalendarOperation` and `CalendarPattern`, so any calendar can be written to a file and
+/// parsed back with `str::parse`. Besides the functional forms `Invert(a)`, `And(a, b)`,
+/// `Or(a, b)` and `Minus(a, b)` the following infix operators are accepted:
+///
+/// | Operator | Meaning        | Precedence |
+/// |----------|----------------|------------|
+/// | `!a`     | `Invert(a)`    | highest    |
+/// | `a & b`  | `And(a, b)`    |            |
+/// | `a \| b` | `Or(a, b)`     | lowest     |
+/// | `a - b`  | `Minus(a, b)`  | lowest     |
+///
+/// Binary operators are left associative and parentheses can be used for grouping.
+/// Everything after a `#` up to the end of the line is a comment.
+///
+/// # Examples
+///
+/// ```rust
+/// use dayendar::abstracto::AbstractCalendar;
+///
+/// let workdays: AbstractCalendar = "!(Weekdays([Saturday, Sunday]) | CustomDays([1, 25]))".parse().unwrap();
+/// let parsed: AbstractCalendar = workdays.to_string().parse().unwrap();
+/// assert_eq!(parsed, workdays);
+/// ```
+pub mod dsl {
+
+    use std::str::FromStr;
+
+    use crate::types::{BiDay, Date, Weekday, TimeMonth, Computus, CalendarSystem, Year, Month, MonthSpec, YearSpec};
+    use crate::abstracto::{AbstractCalendar, CalendarOperation, CalendarPattern};
+    use crate::calendar::ObservanceRule;
+    use crate::hijri::{HijriAdjustments, MAX_ADJUSTMENT};
+    use crate::chinese::ChineseFestival;
+    use crate::error::DayendarError;
+
+    impl FromStr for AbstractCalendar {
+        type Err = DayendarError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let mut parser = Parser::new(s);
+            let calendar = parser.expression()?;
+            parser.end()?;
+            Ok(calendar)
+        }
+    }
+
+    impl FromStr for CalendarPattern {
+        type Err = DayendarError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let mut parser = Parser::new(s);
+            parser.skip_whitespace();
+            let start = parser.pos;
+            let name = parser.identifier()?;
+            let pattern = parser.pattern(name, start)?;
+            parser.end()?;
+            Ok(pattern)
+        }
+    }
+
+    /// Recursive descent parser over the input text
+    pub(crate) struct Parser<'a> {
+        input: &'a str,
+        pub(crate) pos: usize,
+    }
+
+    impl<'a> Parser<'a> {
+        pub(crate) fn new(input: &'a str) -> Self {
+            Parser { input, pos: 0 }
+        }
+
+        /// Builds an error located at the given byte offset
+        pub(crate) fn error_at(&self, pos: usize, message: impl Into<String>) -> DayendarError {
+            DayendarError::parse_error(self.input, pos, message)
+        }
+
+        fn error(&self, message: impl Into<String>) -> DayendarError {
+            self.error_at(self.pos, message)
+        }
+
+        fn peek(&self) -> Option<char> {
+            self.input[self.pos..].chars().next()
+        }
+
+        fn bump(&mut self) -> Option<char> {
+            let c = self.peek()?;
+            self.pos += c.len_utf8();
+            Some(c)
+        }
+
+        pub(crate) fn skip_whitespace(&mut self) {
+            while let Some(c) = self.peek() {
+                if c.is_whitespace() {
+                    self.bump();
+                } else if c == '#' {
+                    while let Some(c) = self.bump() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                } else {
+                    break;
+                }
+            }
+        }
+
+        /// Consumes the next non blank character if it is `c`
+        pub(crate) fn eat(&mut self, c: char) -> bool {
+            self.skip_whitespace();
+            if self.peek() == Some(c) {
+                self.bump();
+                true
+            } else {
+                false
+            }
+        }
+
+        pub(crate) fn expect(&mut self, c: char) -> Result<(), DayendarError> {
+            if self.eat(c) {
+                Ok(())
+            } else {
+                Err(self.unexpected(&format!("`{}`", c)))
+            }
+        }
+
+        fn unexpected(&self, expected: &str) -> DayendarError {
+            match self.peek() {
+                Some(found) => self.error(format!("expected {}, found `{}`", expected, found)),
+                None => self.error(format!("expected {}, found end of input", expected)),
+            }
+        }
+
+        /// Checks that only blanks and a comment remain on the current line, the line break is not consumed
+        pub(crate) fn end_of_line(&mut self) -> Result<(), DayendarError> {
+            // The blanks skipped after the last token may already hold the line break
+            if self.input[..self.pos].chars().rev().take_while(|c| c.is_whitespace()).any(|c| c == '\n') {
+                return Ok(());
+            }
+            while self.peek().is_some_and(|c| c.is_whitespace() && c != '\n') {
+                self.bump();
+            }
+            match self.peek() {
+                None | Some('\n' | '#') => Ok(()),
+                Some(_) => Err(self.unexpected("end of line")),
+            }
+        }
+
+        /// Determines whether only blanks and comments remain
+        pub(crate) fn at_end(&mut self) -> bool {
+            self.skip_whitespace();
+            self.peek().is_none()
+        }
+
+        /// Checks that only blanks and comments remain
+        pub(crate) fn end(&mut self) -> Result<(), DayendarError> {
+            self.skip_whitespace();
+            match self.peek() {
+                None => Ok(()),
+                Some(_) => Err(self.unexpected("end of input")),
+            }
+        }
+
+        pub(crate) fn identifier(&mut self) -> Result<&'a str, DayendarError> {
+            self.skip_whitespace();
+            let start = self.pos;
+            while let Some(c) = self.peek() {
+                if c.is_ascii_alphanumeric() || c == '_' {
+                    self.bump();
+                } else {
+                    break;
+                }
+            }
+            let ident = &self.input[start..self.pos];
+            if ident.is_empty() || !ident.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+                self.pos = start;
+                return Err(self.unexpected("an identifier"));
+            }
+            Ok(ident)
+        }
+
+        /// Gets the next identifier without consuming it
+        pub(crate) fn peek_identifier(&mut self) -> Option<&'a str> {
+            let start = self.pos;
+            let ident = self.identifier().ok();
+            self.pos = start;
+            ident
+        }
+
+        /// expression := difference ( ( `|` | `-` ) difference )*
+        pub(crate) fn expression(&mut self) -> Result<AbstractCalendar, DayendarError> {
+            let mut left = self.intersection()?;
+            loop {
+                if self.eat('|') {
+                    left = left.or(self.intersection()?);
+                } else if self.eat('-') {
+                    left = left.minus(self.intersection()?);
+                } else {
+                    return Ok(left);
+                }
+            }
+        }
+
+        /// intersection := unary ( `&` unary )*
+        fn intersection(&mut self) -> Result<AbstractCalendar, DayendarError> {
+            let mut left = self.unary()?;
+            while self.eat('&') {
+                left = left.and(self.unary()?);
+            }
+            Ok(left)
+        }
+
+        /// unary := `!` unary | primary
+        fn unary(&mut self) -> Result<AbstractCalendar, DayendarError> {
+            if self.eat('!') {
+                Ok(self.unary()?.invert())
+            } else {
+                self.primary()
+            }
+        }
+
+        /// primary := `(` expression `)` | operation | pattern
+        fn primary(&mut self) -> Result<AbstractCalendar, DayendarError> {
+            if self.eat('(') {
+                let calendar = self.expression()?;
+                self.expect(')')?;
+                return Ok(calendar);
+            }
+
+            self.skip_whitespace();
+            let start = self.pos;
+            let name = self.identifier()?;
+            match name {
+                "Invert" => {
+                    self.expect('(')?;
+                    let calendar = self.expression()?;
+                    self.expect(')')?;
+                    Ok(AbstractCalendar::Operation(CalendarOperation::Invert(Box::new(calendar))))
+                },
+                "And" | "Or" | "Minus" => {
+                    self.expect('(')?;
+                    let first = Box::new(self.expression()?);
+                    self.expect(',')?;
+                    let second = Box::new(self.expression()?);
+                    self.expect(')')?;
+                    Ok(AbstractCalendar::Operation(match name {
+                        "And" => CalendarOperation::And(first, second),
+                        "Or" => CalendarOperation::Or(first, second),
+                        _ => CalendarOperation::Minus(first, second),
+                    }))
+                },
+                "Observed" => {
+                    self.expect('(')?;
+                    let calendar = Box::new(self.expression()?);
+                    self.expect(',')?;
+                    let weekend = self.list(Parser::weekday)?;
+                    self.expect(',')?;
+                    let rule = self.observance_rule()?;
+                    self.expect(')')?;
+                    Ok(AbstractCalendar::Operation(CalendarOperation::Observed(calendar, weekend, rule)))
+                },
+                _ => Ok(AbstractCalendar::Pattern(self.pattern(name, start)?)),
+            }
+        }
+
+        /// Parses the arguments of the pattern called `name`, which starts at `start`
+        pub(crate) fn pattern(&mut self, name: &str, start: usize) -> Result<CalendarPattern, DayendarError> {
+            let pattern = match name {
+                "None" => CalendarPattern::None,
+                "Everyday" => CalendarPattern::Everyday,
+                "OddDays" => CalendarPattern::OddDays,
+                "EvenDays" => CalendarPattern::EvenDays,
+                "Weekdays" => CalendarPattern::Weekdays(self.arguments(|p| p.list(Parser::weekday))?),
+                // Values that match no day still evaluate, so the whole domain of each type is accepted
+                // and every pattern written by `Display` parses back
+                "CustomWeekDays" => {
+                    CalendarPattern::CustomWeekDays(self.arguments(|p| p.list(|p| p.unsigned(0, u8::MAX.into())))?)
+                },
+                "CustomDays" => CalendarPattern::CustomDays(self.arguments(|p| p.list(|p| p.unsigned(0, u8::MAX.into())))?),
+                "CustomBiDay" => CalendarPattern::CustomBiDay(self.arguments(|p| p.list(Parser::biday))?),
+                "SpecificDayOfMonth" => CalendarPattern::SpecificDayOfMonth(self.arguments(|p| p.unsigned(0, u8::MAX.into()))?),
+                "SpecificWeekOfMonth" => CalendarPattern::SpecificWeekOfMonth(self.arguments(|p| p.unsigned(0, u8::MAX.into()))?),
+                "FixedHolidays" => CalendarPattern::FixedHolidays(self.arguments(|p| p.list(Parser::any_date))?),
+                "FloatingHolidays" => CalendarPattern::FloatingHolidays(self.arguments(|p| p.list(Parser::any_date))?),
+                "SpecificWeekOfYear" => {
+                    CalendarPattern::SpecificWeekOfYear(self.arguments(|p| p.unsigned(0, u32::MAX.into()))?)
+                },
+                "CronPattern" => CalendarPattern::CronPattern(self.arguments(Parser::string)?),
+                "RRule" => CalendarPattern::RRule(self.arguments(Parser::string)?),
+                "EasterOffset" => self.arguments(|p| {
+                    let offset = p.integer(i16::MIN.into(), i16::MAX.into())? as i16;
+                    p.expect(',')?;
+                    Ok(CalendarPattern::EasterOffset { offset, computus: p.computus()? })
+                })?,
+                "NthWeekdayOfMonth" => self.arguments(|p| {
+                    let n = p.integer(i8::MIN.into(), i8::MAX.into())? as i8;
+                    p.expect(',')?;
+                    let weekday = p.weekday()?;
+                    p.expect(',')?;
+                    Ok(CalendarPattern::NthWeekdayOfMonth { n, weekday, months: p.month_spec()? })
+                })?,
+                "AnnualDate" => self.arguments(|p| {
+                    let month = p.month()?;
+                    p.expect(',')?;
+                    Ok(CalendarPattern::AnnualDate { month, day: p.unsigned(0, u8::MAX.into())? })
+                })?,
+                "Years" => CalendarPattern::Years(self.arguments(Parser::year_spec)?),
+                "Hijri" => self.arguments(|p| {
+                    let month = p.unsigned(1, 12)?;
+                    p.expect(',')?;
+                    let day = p.unsigned(0, 30)?;
+                    p.expect(',')?;
+                    let offset = p.integer(i16::MIN.into(), i16::MAX.into())? as i16;
+                    let mut adjustments = HijriAdjustments::new();
+                    if p.eat(',') {
+                        for (year, month, days) in p.list(Parser::hijri_adjustment)? {
+                            adjustments.insert(year, month, days).map_err(|error| p.error(error.to_string()))?;
+                        }
+                    }
+                    Ok(CalendarPattern::Hijri { month, day, offset, adjustments })
+                })?,
+                "Hebrew" => self.arguments(|p| {
+                    let month = p.unsigned(1, 13)?;
+                    p.expect(',')?;
+                    Ok(CalendarPattern::Hebrew { month, day: p.unsigned(1, 30)? })
+                })?,
+                "ChineseFestival" => CalendarPattern::ChineseFestival(self.arguments(Parser::chinese_festival)?),
+                "ForeignDate" => self.arguments(|p| {
+                    let system = p.calendar_system()?;
+                    p.expect(',')?;
+                    let month = p.unsigned(1, system.months_in_year().into())?;
+                    p.expect(',')?;
+                    Ok(CalendarPattern::ForeignDate { system, month, day: p.unsigned(0, 31)? })
+                })?,
+                "CustomFunction" => {
+                    return Err(self.error_at(start, "custom functions can not be parsed from text"));
+                },
+                _ => return Err(self.error_at(start, format!("unknown calendar pattern `{}`", name))),
+            };
+            Ok(pattern)
+        }
+
+        /// Parses `(` argument `)`
+        fn arguments<T, F>(&mut self, argument: F) -> Result<T, DayendarError>
+        where
+            F: FnOnce(&mut Self) -> Result<T, DayendarError>,
+        {
+            self.expect('(')?;
+            let value = argument(self)?;
+            self.expect(')')?;
+            Ok(value)
+        }
+
+        /// Parses `[` item (`,` item)* `]`, a trailing comma is allowed
+        fn list<T, F>(&mut self, item: F) -> Result<Vec<T>, DayendarError>
+        where
+            F: Fn(&mut Self) -> Result<T, DayendarError>,
+        {
+            self.expect('[')?;
+            let mut items = Vec::new();
+            loop {
+                if self.eat(']') {
+                    return Ok(items);
+                }
+                items.push(item(self)?);
+                if !self.eat(',') {
+                    self.expect(']')?;
+                    return Ok(items);
+                }
+            }
+        }
+
+        fn digits(&mut self) -> &'a str {
+            let start = self.pos;
+            while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.bump();
+            }
+            &self.input[start..self.pos]
+        }
+
+        /// Parses an optionally signed integer in the `min..=max` range
+        pub(crate) fn integer(&mut self, min: i64, max: i64) -> Result<i64, DayendarError> {
+            self.skip_whitespace();
+            let start = self.pos;
+            let negative = self.peek() == Some('-');
+            if negative || self.peek() == Some('+') {
+                self.bump();
+            }
+            let digits = self.digits();
+            if digits.is_empty() {
+                self.pos = start;
+                return Err(self.unexpected("a number"));
+            }
+            let value: i64 = match digits.parse::<i64>() {
+                Ok(value) if negative => -value,
+                Ok(value) => value,
+                Err(_) => return Err(self.error_at(start, "number too large")),
+            };
+            if value < min || value > max {
+                return Err(self.error_at(start, format!("number {} out of range {}..={}", value, min, max)));
+            }
+            Ok(value)
+        }
+
+        /// Parses an unsigned integer in the `min..=max` range
+        pub(crate) fn unsigned<T: TryFrom<i64>>(&mut self, min: i64, max: i64) -> Result<T, DayendarError> {
+            self.skip_whitespace();
+            if self.peek() == Some('-') {
+                return Err(self.unexpected("a positive number"));
+            }
+            let start = self.pos;
+            let value = self.integer(min, max)?;
+            T::try_from(value).map_err(|_| self.error_at(start, "number out of range"))
+        }
+
+        /// Parses a Hijri month adjustment `year-month: days`
+        fn hijri_adjustment(&mut self) -> Result<(i32, u8, i8), DayendarError> {
+            let year = self.unsigned(1, 9666)?;
+            self.expect('-')?;
+            let month = self.unsigned(1, 12)?;
+            self.expect(':')?;
+            let max = i64::from(MAX_ADJUSTMENT);
+            Ok((year, month, self.integer(-max, max)? as i8))
+        }
+
+        fn biday(&mut self) -> Result<BiDay, DayendarError> {
+            Ok(if self.unsigned::<u8>(0, 1)? == 1 { BiDay::One } else { BiDay::Zero })
+        }
+
+        /// Parses a weekday name, either full or abbreviated to three letters
+        pub(crate) fn weekday(&mut self) -> Result<Weekday, DayendarError> {
+            self.skip_whitespace();
+            let start = self.pos;
+            let name = self.identifier()?;
+            let weekday = match name.to_lowercase().as_str() {
+                "monday" | "mon" => Weekday::Monday,
+                "tuesday" | "tue" => Weekday::Tuesday,
+                "wednesday" | "wed" => Weekday::Wednesday,
+                "thursday" | "thu" => Weekday::Thursday,
+                "friday" | "fri" => Weekday::Friday,
+                "saturday" | "sat" => Weekday::Saturday,
+                "sunday" | "sun" => Weekday::Sunday,
+                _ => return Err(self.error_at(start, format!("unknown weekday `{}`", name))),
+            };
+            Ok(weekday)
+        }
+
+        /// Parses a month name, either full or abbreviated to three letters
+        pub(crate) fn month(&mut self) -> Result<Month, DayendarError> {
+            self.skip_whitespace();
+            let start = self.pos;
+            let name = self.identifier()?;
+            name.parse().map_err(|_| self.error_at(start, format!("unknown month `{}`", name)))
+        }
+
+        /// Parses a month spec written as `May`, `March-May` or `[January, July]`
+        fn month_spec(&mut self) -> Result<MonthSpec, DayendarError> {
+            self.skip_whitespace();
+            if self.peek() == Some('[') {
+                return Ok(MonthSpec::List(self.list(Parser::month)?.into_iter().collect()));
+            }
+            let month = self.month()?;
+            if self.eat('-') {
+                Ok(MonthSpec::Range(month..=self.month()?))
+            } else {
+                Ok(MonthSpec::Single(month))
+            }
+        }
+
+        /// Parses a year spec written as `2024`, `1974-9999` or `[2023, 2025]`
+        fn year_spec(&mut self) -> Result<YearSpec, DayendarError> {
+            self.skip_whitespace();
+            if self.peek() == Some('[') {
+                return Ok(YearSpec::List(self.list(|p| p.unsigned(0, Year::MAX.into()))?.into_iter().collect()));
+            }
+            let year: Year = self.unsigned(0, Year::MAX.into())?;
+            if self.eat('-') {
+                Ok(YearSpec::Range(year..=self.unsigned(0, Year::MAX.into())?))
+            } else {
+                Ok(YearSpec::Single(year))
+            }
+        }
+
+        /// Parses the name of an observance rule
+        pub(crate) fn observance_rule(&mut self) -> Result<ObservanceRule, DayendarError> {
+            self.skip_whitespace();
+            let start = self.pos;
+            match self.identifier()? {
+                "NearestWeekday" => Ok(ObservanceRule::NearestWeekday),
+                "NextWeekday" => Ok(ObservanceRule::NextWeekday),
+                "NextNonHoliday" => Ok(ObservanceRule::NextNonHoliday),
+                "PreviousWeekday" => Ok(ObservanceRule::PreviousWeekday),
+                name => Err(self.error_at(start, format!("unknown observance rule `{}`", name))),
+            }
+        }
+
+        /// Parses the name of a computus
+        pub(crate) fn computus(&mut self) -> Result<Computus, DayendarError> {
+            self.skip_whitespace();
+            let start = self.pos;
+            match self.identifier()? {
+                "Western" => Ok(Computus::Western),
+                "Orthodox" => Ok(Computus::Orthodox),
+                name => Err(self.error_at(start, format!("unknown computus `{}`, expected Western or Orthodox", name))),
+            }
+        }
+
+        fn calendar_system(&mut self) -> Result<CalendarSystem, DayendarError> {
+            self.skip_whitespace();
+            let start = self.pos;
+            match self.identifier()? {
+                "Persian" => Ok(CalendarSystem::Persian),
+                "Ethiopian" => Ok(CalendarSystem::Ethiopian),
+                "Coptic" => Ok(CalendarSystem::Coptic),
+                "Julian" => Ok(CalendarSystem::Julian),
+                name => Err(self.error_at(start, format!(
+                    "unknown calendar system `{}`, expected Persian, Ethiopian, Coptic or Julian", name
+                ))),
+            }
+        }
+
+        fn chinese_festival(&mut self) -> Result<ChineseFestival, DayendarError> {
+            self.skip_whitespace();
+            let start = self.pos;
+            match self.identifier()? {
+                "NewYear" => Ok(ChineseFestival::NewYear),
+                "Qingming" => Ok(ChineseFestival::Qingming),
+                "DragonBoat" => Ok(ChineseFestival::DragonBoat),
+                "MidAutumn" => Ok(ChineseFestival::MidAutumn),
+                name => Err(self.error_at(start, format!(
+                    "unknown festival `{}`, expected NewYear, Qingming, DragonBoat or MidAutumn", name
+                ))),
+            }
+        }
+
+        /// Parses a `YYYY-MM-DD` date
+        pub(crate) fn date(&mut self) -> Result<Date, DayendarError> {
+            self.skip_whitespace();
+            let start = self.pos;
+            let date = self.any_date()?;
+            if !(1..=9999).contains(&date.year()) {
+                return Err(self.error_at(start, format!("invalid date `{}`", &self.input[start..self.pos])));
+            }
+            Ok(date)
+        }
+
+        /// Parses a date as `Date` displays it, the year can be signed and out of the supported range
+        fn any_date(&mut self) -> Result<Date, DayendarError> {
+            self.skip_whitespace();
+            let start = self.pos;
+            let negative = self.peek() == Some('-');
+            if negative || self.peek() == Some('+') {
+                self.bump();
+            }
+            let year = self.digits();
+            let month = if self.peek() == Some('-') { self.bump(); self.digits() } else { "" };
+            let day = if self.peek() == Some('-') { self.bump(); self.digits() } else { "" };
+            if year.is_empty() || month.is_empty() || day.is_empty() {
+                self.pos = start;
+                return Err(self.unexpected("a date as YYYY-MM-DD"));
+            }
+
+            let invalid = || self.error_at(start, format!("invalid date `{}`", &self.input[start..self.pos]));
+            let year: i32 = year.parse().map_err(|_| invalid())?;
+            let month: u8 = month.parse().map_err(|_| invalid())?;
+            let day: u8 = day.parse().map_err(|_| invalid())?;
+            let month = TimeMonth::try_from(month).map_err(|_| invalid())?;
+            Date::from_calendar_date(if negative { -year } else { year }, month, day).map_err(|_| invalid())
+        }
+
+        /// Parses a double quoted string, `\"` and `\\` are the only escapes
+        pub(crate) fn string(&mut self) -> Result<String, DayendarError> {
+            self.skip_whitespace();
+            let start = self.pos;
+            if self.bump() != Some('"') {
+                self.pos = start;
+                return Err(self.unexpected("a string"));
+            }
+            let mut value = String::new();
+            loop {
+                match self.bump() {
+                    Some('"') => return Ok(value),
+                    Some('\\') => match self.bump() {
+                        Some(c @ ('"' | '\\')) => value.push(c),
+                        _ => return Err(self.error_at(self.pos, "invalid escape sequence")),
+                    },
+                    Some(c) => value.push(c),
+                    None => return Err(self.error_at(start, "unterminated string")),
+                }
+            }
+        }
+    }
+
+}
+
+/// The `rules` module loads holiday calendars from a plain text rule file.
+///
+/// A rule file is a sequence of statements, `#` starts a comment that runs to the end of the line:
+///
+/// ```text
+/// calendar "England and Wales"
+/// weekend Saturday, Sunday
+///
+/// holiday "New Year's Day": fixed January 1, observe NextNonHoliday
+/// holiday "Good Friday": easter -2
+/// holiday "Early May bank holiday": nth 1 Monday May, from 1978
+/// holiday "Platinum Jubilee": date 2022-06-03
+/// ```
+///
+/// A holiday is one of `fixed <month> <day>`, `nth <n> <weekday> <month>`, `easter <offset> [Western|Orthodox]`
+/// or `date <YYYY-MM-DD>`, followed by the optional `observe <rule>`, `from <year>` and `until <year>` options.
+/// The weekend defaults to Saturday and Sunday.
+pub mod rules {
+
+    use std::str::FromStr;
+
+    use crate::types::{Year, Month, Day, BiDay, Weekday, Date, DateSpan, Computus, MonthSpec, YearSpec};
+    use crate::abstracto::{AbstractCalendar, CalendarPattern};
+    use crate::calendar::{DaysCalendar, ObservanceRule};
+    use crate::error::DayendarError;
+    use crate::utils::days_in_month;
+    use crate::dsl::Parser;
+
+    /// When a holiday takes place
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum HolidayKind {
+        /// The same day of the month every year
+        Fixed { month: Month, day: Day },
+        /// The n-th occurrence of a weekday in a month, negative `n` counts from the end of the month
+        NthWeekday { n: i8, weekday: Weekday, month: Month },
+        /// A day at `offset` days from Easter Sunday
+        Easter { offset: i16, computus: Computus },
+        /// A one-off holiday
+        Date(Date),
+    }
+
+    impl HolidayKind {
+        /// The pattern of the days on which the holiday takes place
+        pub(crate) fn to_pattern(self) -> CalendarPattern {
+            match self {
+                HolidayKind::Fixed { month, day } => CalendarPattern::AnnualDate { month, day },
+                HolidayKind::NthWeekday { n, weekday, month } => {
+                    CalendarPattern::NthWeekdayOfMonth { n, weekday, months: MonthSpec::Single(month) }
+                },
+                HolidayKind::Easter { offset, computus } => CalendarPattern::EasterOffset { offset, computus },
+                HolidayKind::Date(date) => CalendarPattern::FixedHolidays(vec![date]),
+            }
+        }
+    }
+
+    /// A named holiday rule of a rule file
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct HolidayRule {
+        pub name: String,
+        pub kind: HolidayKind,
+        /// Where the holiday is observed when it falls on a weekend day
+        pub observance: Option<ObservanceRule>,
+        /// First year in which the rule applies
+        pub valid_from: Option<Year>,
+        /// Last year in which the rule applies
+        pub valid_to: Option<Year>,
+    }
+
+    /// A holiday calendar loaded from a rule file
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct HolidayCalendar {
+        pub name: Option<String>,
+        pub weekend: Vec<Weekday>,
+        pub rules: Vec<HolidayRule>,
+    }
+
+    impl HolidayRule {
+        /// Builds the abstract calendar of the rule, without observance
+        fn to_abstract_calendar(&self) -> AbstractCalendar {
+            let calendar = AbstractCalendar::Pattern(self.kind.to_pattern());
+            match (self.valid_from, self.valid_to) {
+                (None, None) => calendar,
+                (from, to) => {
+                    let years = YearSpec::Range(from.unwrap_or(1)..=to.unwrap_or(9999));
+                    calendar.and(AbstractCalendar::Pattern(CalendarPattern::Years(years)))
+                },
+            }
+        }
+    }
+
+    impl HolidayCalendar {
+        /// Parses the text of a rule file
+        pub fn parse(input: &str) -> Result<Self, DayendarError> {
+            let mut parser = Parser::new(input);
+            let mut calendar = HolidayCalendar {
+                name: None,
+                weekend: vec![Weekday::Saturday, Weekday::Sunday],
+                rules: Vec::new(),
+            };
+
+            while !parser.at_end() {
+                let start = parser.pos;
+                match parser.identifier()? {
+                    "calendar" => calendar.name = Some(parser.string()?),
+                    "weekend" => {
+                        let mut weekend = vec![parser.weekday()?];
+                        while parser.eat(',') {
+                            weekend.push(parser.weekday()?);
+                        }
+                        calendar.weekend = weekend;
+                    },
+                    "holiday" => calendar.rules.push(holiday(&mut parser)?),
+                    keyword => {
+                        return Err(parser.error_at(start, format!("unknown statement `{}`, expected calendar, weekend or holiday", keyword)));
+                    },
+                }
+                parser.end_of_line()?;
+            }
+            Ok(calendar)
+        }
+
+        /// Builds the abstract calendar of the holidays.
+        /// Rules that share an observance rule are observed together, so they do not collide with each other.
+        /// The rules observed on the next non holiday are observed last, skipping the weekday holidays of the other rules.
+        pub fn to_abstract_calendar(&self) -> AbstractCalendar {
+            let mut groups: Vec<(Option<ObservanceRule>, AbstractCalendar)> = Vec::new();
+            for rule in &self.rules {
+                let calendar = rule.to_abstract_calendar();
+                match groups.iter_mut().find(|(observance, _)| *observance == rule.observance) {
+                    Some((_, group)) => *group = group.clone().or(calendar),
+                    None => groups.push((rule.observance, calendar)),
+                }
+            }
+            let (next_non_holiday, groups): (Vec<_>, Vec<_>) = groups.into_iter()
+                .partition(|(observance, _)| *observance == Some(ObservanceRule::NextNonHoliday));
+            let others: Option<AbstractCalendar> = groups.into_iter()
+                .map(|(observance, calendar)| match observance {
+                    Some(observance) => calendar.observed(self.weekend.clone(), observance),
+                    None => calendar,
+                })
+                .reduce(AbstractCalendar::or);
+            let next_non_holiday: Option<AbstractCalendar> = next_non_holiday.into_iter().map(|(_, calendar)| {
+                let Some(others) = &others else {
+                    return calendar.observed(self.weekend.clone(), ObservanceRule::NextNonHoliday);
+                };
+                // Holidays of the other rules on weekdays block the observed days, then they are taken out again
+                let weekend = AbstractCalendar::Pattern(CalendarPattern::Weekdays(self.weekend.clone()));
+                let blocked: AbstractCalendar = others.clone().minus(weekend);
+                calendar.or(blocked.clone())
+                    .observed(self.weekend.clone(), ObservanceRule::NextNonHoliday)
+                    .minus(blocked)
+            }).next();
+            others.into_iter()
+                .chain(next_non_holiday)
+                .reduce(AbstractCalendar::or)
+                .unwrap_or(AbstractCalendar::Pattern(CalendarPattern::None))
+        }
+
+        /// Builds the abstract calendar of the business days, the days that are neither weekend days nor holidays
+        pub fn business_days(&self) -> AbstractCalendar {
+            AbstractCalendar::Pattern(CalendarPattern::Weekdays(self.weekend.clone()))
+                .or(self.to_abstract_calendar())
+                .invert()
+        }
+
+        /// Materializes the holidays for every month of a `DateSpan`
+        pub fn evaluate(&self, span: &DateSpan) -> Result<DaysCalendar<BiDay>, DayendarError> {
+            self.to_abstract_calendar().evaluate(span)
+        }
+    }
+
+    impl FromStr for HolidayCalendar {
+        type Err = DayendarError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            HolidayCalendar::parse(s)
+        }
+    }
+
+    /// Parses a year in the supported range
+    fn year(parser: &mut Parser) -> Result<Year, DayendarError> {
+        parser.unsigned(1, 9999)
+    }
+
+    /// holiday := string `:` kind ( `,` option )*
+    fn holiday(parser: &mut Parser) -> Result<HolidayRule, DayendarError> {
+        let name = parser.string()?;
+        parser.expect(':')?;
+        parser.skip_whitespace();
+        let start = parser.pos;
+        let kind = match parser.identifier()? {
+            "fixed" => {
+                let month = parser.month()?;
+                parser.skip_whitespace();
+                let day_start = parser.pos;
+                let day: Day = parser.unsigned(1, 31)?;
+                // Any leap year gives the longest February
+                if day > days_in_month(2000, month).unwrap_or(31) {
+                    return Err(parser.error_at(day_start, format!("{:?} has no day {}", month, day)));
+                }
+                HolidayKind::Fixed { month, day }
+            },
+            "nth" => {
+                parser.skip_whitespace();
+                let n_start = parser.pos;
+                let n = parser.integer(-5, 5)? as i8;
+                if n == 0 {
+                    return Err(parser.error_at(n_start, "the occurrence must not be zero"));
+                }
+                HolidayKind::NthWeekday { n, weekday: parser.weekday()?, month: parser.month()? }
+            },
+            "easter" => {
+                let offset = parser.integer(i16::MIN.into(), i16::MAX.into())? as i16;
+                // The computus is optional, an identifier left on the line must name one
+                let computus = if parser.end_of_line().is_err() && parser.peek_identifier().is_some() {
+                    parser.computus()?
+                } else {
+                    Computus::Western
+                };
+                HolidayKind::Easter { offset, computus }
+            },
+            "date" => HolidayKind::Date(parser.date()?),
+            kind => {
+                return Err(parser.error_at(start, format!("unknown holiday kind `{}`, expected fixed, nth, easter or date", kind)));
+            },
+        };
+
+        let mut rule = HolidayRule { name, kind, observance: None, valid_from: None, valid_to: None };
+        while parser.eat(',') {
+            parser.skip_whitespace();
+            let option_start = parser.pos;
+            match parser.identifier()? {
+                "observe" => rule.observance = Some(parser.observance_rule()?),
+                "from" => rule.valid_from = Some(year(parser)?),
+                "until" => rule.valid_to = Some(year(parser)?),
+                option => {
+                    return Err(parser.error_at(option_start, format!("unknown option `{}`, expected observe, from or until", option)));
+                },
+            }
+        }
+        if let (Some(from), Some(to)) = (rule.valid_from, rule.valid_to) {
+            if from > to {
+                return Err(parser.error_at(start, format!("valid years {}-{} are not in order", from, to)));
+            }
+        }
+        Ok(rule)
+    }
+
+}
+
+// =========================================
+// SECTION FOR UNIT TEST CODE ...
+// =========================================
+#[cfg(test)]
+mod tests_cron {
+
+    use crate::cron::*;
+    use crate::types::*;
+    use crate::error::DayendarError;
+    use crate::calendar::biday_to_vec_day;
+
+    fn days_of(expression: &str, year: Year, month: Month) -> Vec<Day> {
+        let cron = CronExpression::parse(expression).unwrap();
+        biday_to_vec_day(cron.evaluate_months(&[(year, month)]).unwrap()).remove(0).2
+    }
+
+    #[test]
+    fn test_every_day() {
+        assert_eq!(days_of("* * * ? * *", 2024, Month::February).len(), 29);
+        assert_eq!(days_of("0 0 0 * * ?", 2023, Month::February).len(), 28);
+    }
+
+    #[test]
+    fn test_day_of_month_lists_ranges_steps() {
+        assert_eq!(days_of("0 0 0 1,15 * ?", 2024, Month::May), vec![1, 15]);
+        assert_eq!(days_of("0 0 0 10-12 * ?", 2024, Month::May), vec![10, 11, 12]);
+        assert_eq!(days_of("0 0 0 1/10 * ?", 2024, Month::May), vec![1, 11, 21, 31]);
+        assert_eq!(days_of("0 0 0 */15 * ?", 2024, Month::May), vec![1, 16, 31]);
+        assert_eq!(days_of("0 0 0 5-20/5 * ?", 2024, Month::May), vec![5, 10, 15, 20]);
+    }
+
+    #[test]
+    fn test_last_day_variants() {
+        assert_eq!(days_of("0 0 0 L * ?", 2024, Month::February), vec![29]);
+        assert_eq!(days_of("0 0 0 L-2 * ?", 2023, Month::February), vec![26]);
+        // March 2024 ends on a Sunday
+        assert_eq!(days_of("0 0 0 LW * ?", 2024, Month::March), vec![29]);
+    }
+
+    #[test]
+    fn test_nearest_weekday() {
+        // 2024-06-15 is a Saturday, 2024-06-16 a Sunday
+        assert_eq!(days_of("0 0 0 15W * ?", 2024, Month::June), vec![14]);
+        assert_eq!(days_of("0 0 0 16W * ?", 2024, Month::June), vec![17]);
+        // 2024-06-01 is a Saturday, the nearest weekday stays inside the month
+        assert_eq!(days_of("0 0 0 1W * ?", 2024, Month::June), vec![3]);
+        // 2024-03-31 is a Sunday
+        assert_eq!(days_of("0 0 0 31W * ?", 2024, Month::March), vec![29]);
+        // Day 31 does not exist in June
+        assert_eq!(days_of("0 0 0 31W * ?", 2024, Month::June), Vec::<Day>::new());
+    }
+
+    #[test]
+    fn test_day_of_week() {
+        // Mondays and Fridays of January 2024
+        assert_eq!(days_of("0 0 0 ? * MON,FRI", 2024, Month::January), vec![1, 5, 8, 12, 15, 19, 22, 26, 29]);
+        assert_eq!(days_of("0 0 0 ? * 2-3", 2024, Month::January), days_of("0 0 0 ? * MON-TUE", 2024, Month::January));
+        assert_eq!(days_of("0 0 0 ? * L", 2024, Month::January), vec![6, 13, 20, 27]);
+    }
+
+    #[test]
+    fn test_nth_and_last_weekday() {
+        // Thanksgiving: 4th Thursday of November
+        assert_eq!(days_of("0 0 0 ? NOV 5#4", 2024, Month::November), vec![28]);
+        assert_eq!(days_of("0 0 0 ? * THU#4", 2023, Month::November), vec![23]);
+        // Memorial Day: last Monday of May
+        assert_eq!(days_of("0 0 0 ? * 2L", 2024, Month::May), vec![27]);
+        assert_eq!(days_of("0 0 0 ? * MONL", 2021, Month::May), vec![31]);
+    }
+
+    #[test]
+    fn test_month_and_year_fields() {
+        let cron = CronExpression::parse("0 0 0 1 JAN-MAR,DEC ? 2024/2").unwrap();
+        assert!(cron.matches(date!(2024 - 02 - 01)));
+        assert!(cron.matches(date!(2026 - 12 - 01)));
+        assert!(!cron.matches(date!(2025 - 01 - 01)));
+        assert!(!cron.matches(date!(2024 - 04 - 01)));
+        assert!(!cron.matches(date!(2024 - 02 - 02)));
+    }
+
+    #[test]
+    fn test_evaluate_span() {
+        let cron: CronExpression = "0 0 0 1 * ?".parse().unwrap();
+        let calendar = cron.evaluate(&DateSpan::Year(YearSpec::Single(2023))).unwrap();
+        assert_eq!(calendar.days_calendar.len(), 12);
+        assert!(biday_to_vec_day(calendar).iter().all(|(_, _, days)| days == &vec![1]));
+    }
+
+    fn error_of(expression: &str) -> (usize, String) {
+        match CronExpression::parse(expression).unwrap_err() {
+            DayendarError::ParseError { input, position, message } => {
+                assert_eq!(input, expression);
+                (position, message)
+            },
+            error => panic!("unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = CronExpression::parse("0 0 0 32 * ?").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 7: invalid day-of-month field (#4) `32`: value out of range");
+
+        assert_eq!(error_of("0 0 0 1 FOO ?"), (8, String::from("invalid month field (#5) `FOO`: not a number or a valid name")));
+        assert_eq!(error_of("0 0 0 ? * 8").0, 10);
+        assert_eq!(error_of("0 0 0 ? * mon#6").0, 14);
+        assert_eq!(error_of("0 0 25 1 * ?").0, 4);
+        assert_eq!(error_of("0 0 0 1 * ? 10000").0, 12);
+        assert!(error_of("0 0 0 5-1 * ?").1.ends_with("range start is greater than range end"));
+        assert!(error_of("0 0 0 1 * MON").1.starts_with("invalid day-of-week field (#6)"));
+        assert!(error_of("0 0 0 ? * ?").1.starts_with("invalid day-of-week field (#6)"));
+        assert_eq!(error_of("0 0 0 1 *"), (0, String::from("invalid cron expression `0 0 0 1 *`: expected 6 or 7 fields")));
+
+        assert_eq!(error_of("0 0 0 1/4294967295 * ?").0, 6);
+        assert_eq!(error_of("0 0 0 ? 1/4294967295 *").0, 8);
+        assert_eq!(
+            error_of("0 0 0 1 * ? 2000/65537"),
+            (12, String::from("invalid year field (#7) `2000/65537`: step is greater than the maximum value"))
+        );
+        let cron = CronExpression::parse("0 0 0 1 * ? 2000/9999").unwrap();
+        assert!(cron.matches_year(2000) && !cron.matches_year(2001));
+    }
+
+    #[test]
+    fn test_evaluate_invalid_year() {
+        let cron = CronExpression::parse("0 0 0 1 * ?").unwrap();
+        assert_eq!(cron.evaluate_months(&[(0, Month::January)]), Err(DayendarError::YearOutOfRange(0)));
+    }
+}
+
+#[cfg(test)]
+mod tests_rrule {
+
+    use crate::rrule::*;
+    use crate::abstracto::*;
+    use crate::error::DayendarError;
+    use crate::types::*;
+
+    fn first(rule: &str, n: usize) -> Vec<Date> {
+        RRule::parse(rule).unwrap().occurrences().take(n).collect()
+    }
+
+    #[test]
+    fn test_rfc_5545_examples() {
+        assert_eq!(
+            first("DTSTART:20240109 RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU", 3),
+            vec![date!(2024 - 01 - 09), date!(2024 - 01 - 23), date!(2024 - 02 - 06)]
+        );
+        assert_eq!(
+            first("DTSTART:19970519T090000\nRRULE:FREQ=YEARLY;BYDAY=20MO", 3),
+            vec![date!(1997 - 05 - 19), date!(1998 - 05 - 18), date!(1999 - 05 - 17)]
+        );
+        assert_eq!(
+            first("DTSTART:19970512 RRULE:FREQ=YEARLY;BYWEEKNO=20;BYDAY=MO", 3),
+            vec![date!(1997 - 05 - 12), date!(1998 - 05 - 11), date!(1999 - 05 - 17)]
+        );
+        assert_eq!(
+            first("DTSTART:19970902 RRULE:FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13", 3),
+            vec![date!(1998 - 02 - 13), date!(1998 - 03 - 13), date!(1998 - 11 - 13)]
+        );
+        assert_eq!(
+            first("DTSTART:19970904 RRULE:FREQ=MONTHLY;COUNT=3;BYDAY=TU,WE,TH;BYSETPOS=3", 10),
+            vec![date!(1997 - 09 - 04), date!(1997 - 10 - 07), date!(1997 - 11 - 06)]
+        );
+        assert_eq!(
+            first("DTSTART:19970928 RRULE:FREQ=MONTHLY;BYMONTHDAY=-3", 3),
+            vec![date!(1997 - 09 - 28), date!(1997 - 10 - 29), date!(1997 - 11 - 28)]
+        );
+        assert_eq!(
+            first("DTSTART:19970101 RRULE:FREQ=YEARLY;INTERVAL=3;COUNT=10;BYYEARDAY=1,100,200", 5),
+            vec![
+                date!(1997 - 01 - 01), date!(1997 - 04 - 10), date!(1997 - 07 - 19),
+                date!(2000 - 01 - 01), date!(2000 - 04 - 09),
+            ]
+        );
+        assert_eq!(
+            first("DTSTART:19970310 RRULE:FREQ=YEARLY;BYMONTH=1,2,3,4,5,6,7,8,9,10,11,12;COUNT=2", 5),
+            vec![date!(1997 - 03 - 10), date!(1997 - 04 - 10)]
+        );
+    }
+
+    #[test]
+    fn test_week_start_count_and_until() {
+        let rule = "DTSTART:19970805 RRULE:FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=TU,SU;WKST=";
+        assert_eq!(
+            first(&format!("{}SU", rule), 10),
+            vec![date!(1997 - 08 - 05), date!(1997 - 08 - 17), date!(1997 - 08 - 19), date!(1997 - 08 - 31)]
+        );
+        assert_eq!(
+            first(&format!("{}MO", rule), 10),
+            vec![date!(1997 - 08 - 05), date!(1997 - 08 - 10), date!(1997 - 08 - 19), date!(1997 - 08 - 24)]
+        );
+
+        let until = RRule::parse("DTSTART:19970902T090000Z RRULE:FREQ=DAILY;UNTIL=19971224T000000Z").unwrap();
+        assert_eq!(until.occurrences().count(), 114);
+        assert_eq!(until.occurrences().last(), Some(date!(1997 - 12 - 24)));
+
+        let rule = RRule::with_start(date!(2024 - 01 - 31), "RRULE:FREQ=MONTHLY").unwrap();
+        assert_eq!(rule.dtstart(), date!(2024 - 01 - 31));
+        assert_eq!(rule.frequency(), Frequency::Monthly);
+        assert_eq!(
+            rule.occurrences().take(3).collect::<Vec<Date>>(),
+            vec![date!(2024 - 01 - 31), date!(2024 - 03 - 31), date!(2024 - 05 - 31)]
+        );
+    }
+
+    #[test]
+    fn test_evaluate_pattern() {
+        let text = "DTSTART:20240101 RRULE:FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1";
+        let span = DateSpan::YearMonth(YearMonthSpec::parse("2024-February").unwrap());
+        let calendar = CalendarPattern::RRule(String::from(text)).evaluate(&span).unwrap();
+        assert_eq!(calendar, RRule::parse(text).unwrap().evaluate(&span).unwrap());
+        let (year, month, days) = &calendar.days_calendar[0];
+        assert_eq!((*year, *month), (2024, Month::February));
+        assert_eq!(days.iter().filter(|day| **day == BiDay::One).count(), 1);
+        assert_eq!(days[28], BiDay::One);
+
+        let before = DateSpan::YearMonth(YearMonthSpec::parse("2023-December").unwrap());
+        let empty = CalendarPattern::RRule(String::from(text)).evaluate(&before).unwrap();
+        assert!(empty.days_calendar[0].2.iter().all(|day| *day == BiDay::Zero));
+
+        let never = CalendarPattern::RRule(String::from("DTSTART:20240101 RRULE:FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30"));
+        assert!(never.materialize(2024, Month::February).unwrap().iter().all(|day| *day == BiDay::Zero));
+    }
+
+    #[test]
+    fn test_last_supported_year() {
+        let weekly: RRule = "DTSTART:99991201 RRULE:FREQ=WEEKLY".parse().unwrap();
+        assert_eq!(weekly.occurrences().count(), 5);
+        assert_eq!(weekly.occurrences().last(), Some(date!(9999 - 12 - 29)));
+
+        let span = DateSpan::Year(YearSpec::Single(9999));
+        let rules = [
+            "DTSTART:99990101 RRULE:FREQ=DAILY",
+            "DTSTART:99990101 RRULE:FREQ=WEEKLY",
+            "DTSTART:99990101 RRULE:FREQ=YEARLY;BYWEEKNO=52;BYDAY=FR",
+        ];
+        let counts: Vec<usize> = rules.iter()
+            .map(|rule| CalendarPattern::RRule(rule.to_string()).evaluate(&span).unwrap().dates().count())
+            .collect();
+        assert_eq!(counts, vec![365, 53, 1]);
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let error = RRule::parse("DTSTART:20240101 RRULE:FREQ=WEEKLY;BYDAY=2TU").unwrap_err();
+        assert!(error.to_string().contains("BYDAY occurrences are only allowed"));
+        let error = RRule::parse("DTSTART:20240101 RRULE:FREQ=DAILY;COUNT=x").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 35: invalid COUNT `x`");
+        assert!(RRule::parse("RRULE:FREQ=DAILY").is_err());
+        assert!(RRule::parse("DTSTART:20240230 RRULE:FREQ=DAILY").is_err());
+        assert!(RRule::parse("DTSTART:20240101 RRULE:INTERVAL=2").is_err());
+        assert!(RRule::parse("DTSTART:20240101 RRULE:FREQ=HOURLY").is_err());
+        assert!(RRule::parse("DTSTART:20240101 RRULE:FREQ=DAILY;COUNT=2;UNTIL=20240201").is_err());
+        assert!(RRule::parse("DTSTART:20240101 RRULE:FREQ=MONTHLY;BYMONTHDAY=0").is_err());
+        assert!(RRule::parse("DTSTART:20240101 RRULE:FREQ=MONTHLY;BYWEEKNO=1").is_err());
+
+        // Non ASCII dates are rejected instead of being sliced inside a character
+        let error = RRule::parse("DTSTART:20240101 RRULE:FREQ=DAILY;UNTIL=20240é1").unwrap_err();
+        assert!(matches!(error, DayendarError::ParseError { .. }), "{:?}", error);
+        assert!(error.to_string().ends_with("invalid UNTIL `20240é1`"), "{}", error);
+        let pattern = CalendarPattern::RRule(String::from("DTSTART:20240é1 RRULE:FREQ=DAILY"));
+        let span = DateSpan::YearMonth(YearMonthSpec::parse("2024-January").unwrap());
+        assert!(matches!(pattern.evaluate(&span), Err(DayendarError::ParseError { .. })));
+    }
+}
+
+#[cfg(test)]
+mod tests_dsl {
+
+    use crate::abstracto::*;
+    use crate::error::DayendarError;
+    use crate::hijri::HijriAdjustments;
+    use crate::chinese::ChineseFestival;
+    use crate::types::*;
+
+    fn pattern(pattern: CalendarPattern) -> AbstractCalendar {
+        AbstractCalendar::Pattern(pattern)
+    }
+
+    fn round_trip(calendar: &AbstractCalendar) {
+        let text = calendar.to_string();
+        let parsed: AbstractCalendar = text.parse().unwrap_or_else(|e| panic!("{}\n{}", e, text));
+        assert_eq!(&parsed, calendar);
+    }
+
+    #[test]
+    fn test_round_trip_patterns() {
+        let mut adjustments = HijriAdjustments::new();
+        adjustments.insert(1445, 12, -1).unwrap();
+        adjustments.insert(1446, 9, 1).unwrap();
+        let patterns = vec![
+            CalendarPattern::None,
+            CalendarPattern::Everyday,
+            CalendarPattern::OddDays,
+            CalendarPattern::EvenDays,
+            CalendarPattern::Weekdays(vec![Weekday::Saturday, Weekday::Sunday]),
+            CalendarPattern::Weekdays(vec![]),
+            CalendarPattern::CustomWeekDays(vec![1, 7]),
+            CalendarPattern::CustomDays(vec![1, 25]),
+            CalendarPattern::CustomBiDay(vec![BiDay::One, BiDay::Zero, BiDay::Zero]),
+            CalendarPattern::SpecificDayOfMonth(15),
+            CalendarPattern::SpecificWeekOfMonth(2),
+            CalendarPattern::FixedHolidays(vec![date!(2024 - 01 - 01), date!(2024 - 12 - 25)]),
+            CalendarPattern::FloatingHolidays(vec![date!(0999 - 03 - 31)]),
+            CalendarPattern::SpecificWeekOfYear(53),
+            CalendarPattern::CronPattern(String::from("0 0 0 ? NOV THU#4")),
+            CalendarPattern::CronPattern(String::from("with \"quotes\" and \\ backslash")),
+            CalendarPattern::RRule(String::from("DTSTART:20240109\nRRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU")),
+            CalendarPattern::Hijri { month: 9, day: 0, offset: 0, adjustments: HijriAdjustments::new() },
+            CalendarPattern::Hijri { month: 12, day: 10, offset: -1, adjustments: adjustments.clone() },
+            CalendarPattern::Hebrew { month: 7, day: 1 },
+            CalendarPattern::ChineseFestival(ChineseFestival::MidAutumn),
+            CalendarPattern::ForeignDate { system: CalendarSystem::Persian, month: 1, day: 1 },
+            CalendarPattern::EasterOffset { offset: -2, computus: Computus::Western },
+            CalendarPattern::EasterOffset { offset: 50, computus: Computus::Orthodox },
+            CalendarPattern::NthWeekdayOfMonth { n: 4, weekday: Weekday::Thursday, months: MonthSpec::Single(Month::November) },
+            CalendarPattern::NthWeekdayOfMonth { n: -1, weekday: Weekday::Monday, months: MonthSpec::Range(Month::March..=Month::May) },
+            CalendarPattern::NthWeekdayOfMonth {
+                n: 1, weekday: Weekday::Monday, months: MonthSpec::List([Month::January, Month::May].into_iter().collect()),
+            },
+            CalendarPattern::AnnualDate { month: Month::February, day: 29 },
+            CalendarPattern::Years(YearSpec::Single(2024)),
+            CalendarPattern::Years(YearSpec::Range(1974..=9999)),
+            CalendarPattern::Years(YearSpec::List([2025, 2023].into_iter().collect())),
+        ];
+        for p in patterns {
+            let text = p.to_string();
+            assert_eq!(text.parse::<CalendarPattern>().unwrap(), p);
+            round_trip(&pattern(p));
+        }
+    }
+
+    #[test]
+    fn test_round_trip_boundary_values() {
+        let span = DateSpan::Year(YearSpec::Single(2024));
+        let mut patterns = Vec::new();
+        for n in [0, 1, 5, 6, 7, 8, 31, 32, 53, 54, u8::MAX] {
+            patterns.push(CalendarPattern::CustomWeekDays(vec![n]));
+            patterns.push(CalendarPattern::CustomDays(vec![n, 5]));
+            patterns.push(CalendarPattern::SpecificDayOfMonth(n));
+            patterns.push(CalendarPattern::SpecificWeekOfMonth(n));
+            patterns.push(CalendarPattern::SpecificWeekOfYear(n.into()));
+            patterns.push(CalendarPattern::AnnualDate { month: Month::December, day: n });
+        }
+        patterns.push(CalendarPattern::SpecificWeekOfYear(u32::MAX));
+        for n in [i8::MIN, -6, -5, -1, 0, 1, 5, 6, i8::MAX] {
+            patterns.push(CalendarPattern::NthWeekdayOfMonth { n, weekday: Weekday::Monday, months: MonthSpec::Single(Month::May) });
+        }
+        for offset in [i16::MIN, 0, i16::MAX] {
+            patterns.push(CalendarPattern::EasterOffset { offset, computus: Computus::Western });
+        }
+        let dates = vec![date!(-9999 - 01 - 01), date!(-0001 - 01 - 01), date!(0000 - 01 - 01), date!(0001 - 01 - 01), date!(9999 - 12 - 31)];
+        patterns.push(CalendarPattern::FixedHolidays(dates.clone()));
+        patterns.push(CalendarPattern::FloatingHolidays(dates));
+        patterns.push(CalendarPattern::Years(YearSpec::Range(0..=u16::MAX)));
+        // Every value that evaluates must also parse back
+        for p in patterns {
+            assert!(p.evaluate(&span).is_ok(), "{}", p);
+            round_trip(&pattern(p));
+        }
+    }
+
+    #[test]
+    fn test_round_trip_operations() {
+        let weekends = pattern(CalendarPattern::Weekdays(vec![Weekday::Saturday, Weekday::Sunday]));
+        let holidays = pattern(CalendarPattern::CustomDays(vec![1, 25]));
+        let cron = pattern(CalendarPattern::CronPattern(String::from("* * * ? * *")));
+        let calendar = holidays.clone().or(weekends.clone()).or(cron).invert()
+            .and(pattern(CalendarPattern::OddDays))
+            .minus(holidays.clone().and(weekends.invert()));
+        round_trip(&calendar);
+
+        let observed = holidays.observed(vec![Weekday::Saturday, Weekday::Sunday], crate::calendar::ObservanceRule::NextNonHoliday);
+        round_trip(&observed.clone().invert());
+        assert_eq!(
+            format!("{:#}", observed),
+            "Observed(CustomDays([1, 25]), [Saturday, Sunday], NextNonHoliday)"
+        );
+        assert_eq!(format!("{:#}", observed).parse::<AbstractCalendar>().unwrap(), observed);
+    }
+
+    #[test]
+    fn test_display_parse_compact_form() {
+        let parsed: AbstractCalendar = "Invert(Or(CustomDays([1, 25]), Weekdays([Saturday, Sunday])))".parse().unwrap();
+        let expected = pattern(CalendarPattern::CustomDays(vec![1, 25]))
+            .or(pattern(CalendarPattern::Weekdays(vec![Weekday::Saturday, Weekday::Sunday])))
+            .invert();
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn test_infix_operators() {
+        let odd = pattern(CalendarPattern::OddDays);
+        let even = pattern(CalendarPattern::EvenDays);
+        let everyday = pattern(CalendarPattern::Everyday);
+
+        // `&` binds tighter than `|`
+        let parsed: AbstractCalendar = "OddDays | EvenDays & Everyday".parse().unwrap();
+        assert_eq!(parsed, odd.clone().or(even.clone().and(everyday.clone())));
+
+        // `|` and `-` are left associative
+        let parsed: AbstractCalendar = "OddDays-EvenDays | Everyday".parse().unwrap();
+        assert_eq!(parsed, odd.clone().minus(even.clone()).or(everyday.clone()));
+
+        let parsed: AbstractCalendar = "!(OddDays | EvenDays) & !Everyday".parse().unwrap();
+        assert_eq!(parsed, odd.or(even).invert().and(everyday.invert()));
+    }
+
+    #[test]
+    fn test_comments_and_whitespace() {
+        let text = "# Working days\n!(\n  Weekdays([Sat, Sun])   # weekends\n  | FixedHolidays([2024-12-25])\n)\n";
+        let parsed: AbstractCalendar = text.parse().unwrap();
+        let expected = pattern(CalendarPattern::Weekdays(vec![Weekday::Saturday, Weekday::Sunday]))
+            .or(pattern(CalendarPattern::FixedHolidays(vec![date!(2024 - 12 - 25)])))
+            .invert();
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        let text = "Or(OddDays,\n    Weekdays([Funday]))";
+        let error = text.parse::<AbstractCalendar>().unwrap_err();
+        assert_eq!(error, DayendarError::ParseError {
+            input: String::from(text), position: 26, message: String::from("unknown weekday `Funday`"),
+        });
+        assert_eq!(error.line_column(), Some((2, 15)));
+        assert_eq!(error.to_string(), "line 2, column 15: unknown weekday `Funday`");
+
+        let error = "OddDays |".parse::<AbstractCalendar>().unwrap_err();
+        assert_eq!(error.line_column(), Some((1, 10)));
+
+        let error = "Foo".parse::<AbstractCalendar>().unwrap_err();
+        assert!(error.to_string().ends_with("unknown calendar pattern `Foo`"));
+
+        let error = "CustomDays([1, 256])".parse::<AbstractCalendar>().unwrap_err();
+        assert_eq!(error.line_column(), Some((1, 16)));
+
+        let error = "FixedHolidays([2023-02-29])".parse::<AbstractCalendar>().unwrap_err();
+        assert!(error.to_string().ends_with("invalid date `2023-02-29`"));
+
+        let error = "OddDays EvenDays".parse::<AbstractCalendar>().unwrap_err();
+        assert!(error.to_string().ends_with("expected end of input, found `E`"));
+
+        let error = "CustomFunction(<fn>)".parse::<AbstractCalendar>().unwrap_err();
+        assert!(error.to_string().ends_with("custom functions can not be parsed from text"));
+
+        let error = "EasterOffset(1, Eastern)".parse::<AbstractCalendar>().unwrap_err();
+        assert_eq!(error.line_column(), Some((1, 17)));
+        assert!("EasterOffset(40000, Western)".parse::<AbstractCalendar>().is_err());
+        let error = "Observed(OddDays, [Sunday], Nearest)".parse::<AbstractCalendar>().unwrap_err();
+        assert!(error.to_string().ends_with("unknown observance rule `Nearest`"));
+        let error = "NthWeekdayOfMonth(128, Monday, May)".parse::<AbstractCalendar>().unwrap_err();
+        assert!(error.to_string().ends_with("number 128 out of range -128..=127"));
+        let error = "NthWeekdayOfMonth(1, Monday, Mayo)".parse::<AbstractCalendar>().unwrap_err();
+        assert!(error.to_string().ends_with("unknown month `Mayo`"));
+
+        assert!("CronPattern(\"* * *".parse::<AbstractCalendar>().is_err());
+        assert!("(OddDays".parse::<AbstractCalendar>().is_err());
+    }
+}
+
+#[cfg(test)]
+mod tests_rules {
+
+    use crate::calendar::*;
+    use crate::error::DayendarError;
+    use crate::rules::*;
+    use crate::types::*;
+
+    const ENGLAND_AND_WALES: &str = r#"
+# Bank holidays of England and Wales
+calendar "England and Wales"
+weekend Saturday, Sunday
+
+holiday "New Year's Day": fixed January 1, observe NextNonHoliday, from 1974
+holiday "Good Friday": easter -2
+holiday "Easter Monday": easter 1 Western
+holiday "Early May bank holiday": nth 1 Monday May, from 1978
+holiday "Spring bank holiday": nth -1 Monday May, from 1971
+holiday "Summer bank holiday": nth -1 Monday August, from 1971
+holiday "Christmas Day": fixed December 25, observe NextNonHoliday
+holiday "Boxing Day": fixed December 26, observe NextNonHoliday
+holiday "Platinum Jubilee": date 2022-06-03
+"#;
+
+    fn holidays_of(calendar: &HolidayCalendar, year: Year) -> Vec<Date> {
+        to_date(calendar.evaluate(&DateSpan::Year(YearSpec::Single(year))).unwrap())
+    }
+
+    #[test]
+    fn test_parse_rule_file() {
+        let calendar: HolidayCalendar = ENGLAND_AND_WALES.parse().unwrap();
+        assert_eq!(calendar.name.as_deref(), Some("England and Wales"));
+        assert_eq!(calendar.rules.len(), 9);
+        assert_eq!(calendar.rules[0], HolidayRule {
+            name: String::from("New Year's Day"),
+            kind: HolidayKind::Fixed { month: Month::January, day: 1 },
+            observance: Some(ObservanceRule::NextNonHoliday),
+            valid_from: Some(1974),
+            valid_to: None,
+        });
+        assert_eq!(calendar.rules[2].kind, HolidayKind::Easter { offset: 1, computus: Computus::Western });
+    }
+
+    #[test]
+    fn test_evaluate_rule_file() {
+        let calendar: HolidayCalendar = ENGLAND_AND_WALES.parse().unwrap();
+        assert_eq!(holidays_of(&calendar, 2022), vec![
+            date!(2022 - 01 - 03), date!(2022 - 04 - 15), date!(2022 - 04 - 18), date!(2022 - 05 - 02),
+            date!(2022 - 05 - 30), date!(2022 - 06 - 03), date!(2022 - 08 - 29), date!(2022 - 12 - 26),
+            date!(2022 - 12 - 27),
+        ]);
+        // Rules outside their valid years are skipped
+        assert_eq!(holidays_of(&calendar, 1970).len(), 4);
+
+        let business_days = calendar.business_days().evaluate(&DateSpan::Year(YearSpec::Single(2022))).unwrap();
+        assert_eq!(business_days.next_day(2022, Month::December, 23), Some(date!(2022 - 12 - 28)));
+    }
+
+    #[test]
+    fn test_custom_weekend() {
+        let calendar: HolidayCalendar = "weekend Friday, Saturday\nholiday \"Day\": date 2024-03-01, observe NextWeekday"
+            .parse()
+            .unwrap();
+        assert_eq!(holidays_of(&calendar, 2024), vec![date!(2024 - 03 - 03)]);
+    }
+
+    #[test]
+    fn test_errors_report_lines() {
+        let line_of = |text: &str| match text.parse::<HolidayCalendar>() {
+            Err(error @ DayendarError::ParseError { .. }) => (error.line_column().unwrap().0, error.to_string()),
+            other => panic!("expected a parse error, found {:?}", other),
+        };
+        let (line, message) = line_of("calendar \"X\"\n\nholiday \"Bad\": fixed February 30");
+        assert_eq!(line, 3);
+        assert!(message.ends_with("February has no day 30"));
+
+        let (line, message) = line_of("holiday \"A\": easter 1\nholiday \"B\": nth 1 Monday May, since 2000");
+        assert_eq!(line, 2);
+        assert!(message.ends_with("unknown option `since`, expected observe, from or until"));
+
+        let (line, message) = line_of("# comment\nholidays \"A\": easter 1");
+        assert_eq!(line, 2);
+        assert!(message.contains("unknown statement `holidays`"));
+
+        let (line, message) = line_of("holiday \"A\": easter 1\nholiday \"B\": fixed May 1, from 2000, until 1999");
+        assert_eq!(line, 2);
+        assert!(message.ends_with("valid years 2000-1999 are not in order"));
+        assert_eq!(line_of("holiday \"A\" fixed May 1").0, 1);
+        assert_eq!(line_of("\n\nholiday \"A\": weekly Monday").0, 3);
+
+        let (line, message) = line_of("holiday \"A\": easter 1 Westrn\nholiday \"B\": easter 2");
+        assert_eq!(line, 1);
+        assert!(message.ends_with("unknown computus `Westrn`, expected Western or Orthodox"));
+        let (line, message) = line_of("holiday \"A\": fixed May 1 2\n");
+        assert_eq!(line, 1);
+        assert!(message.ends_with("expected end of line, found `2`"));
+    }
+
+    #[test]
+    fn test_rules_lower_to_typed_patterns() {
+        let calendar: HolidayCalendar = "holiday \"Leap Day\": fixed February 29, from 2000, until 2024".parse().unwrap();
+        assert_eq!(format!("{:#}", calendar.to_abstract_calendar()), "And(AnnualDate(February, 29), Years(2000-2024))");
+        assert_eq!(holidays_of(&calendar, 2024), vec![date!(2024 - 02 - 29)]);
+        assert_eq!(holidays_of(&calendar, 2023), vec![]);
+        assert_eq!(holidays_of(&calendar, 2028), vec![]);
+    }
+
+    #[test]
+    fn test_next_non_holiday_skips_other_rules() {
+        let calendar: HolidayCalendar = "holiday \"X\": fixed April 20, observe NextNonHoliday\n\
+                                         holiday \"Easter Monday\": easter 1"
+            .parse()
+            .unwrap();
+        // 2025-04-20 is Easter Sunday, so X is observed after Easter Monday
+        assert_eq!(holidays_of(&calendar, 2025), vec![date!(2025 - 04 - 21), date!(2025 - 04 - 22)]);
+    }
+}
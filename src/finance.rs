@@ -0,0 +1,733 @@
+/// The `business` module implements business day arithmetic over a `DaysCalendar<BiDay>`,
+/// where `BiDay::One` marks a good business day.
+pub mod business {
+
+    use crate::types::{Year, Month, BiDay, Date};
+    use crate::calendar::DaysCalendar;
+    use crate::error::DayendarError;
+
+    /// Conventions to adjust a date that does not fall on a business day
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum RollConvention {
+        /// The first business day on or after the date
+        Following,
+        /// The first business day on or after the date, unless it falls in the next month,
+        /// then the last business day before it
+        ModifiedFollowing,
+        /// The last business day on or before the date
+        Preceding,
+        /// The last business day on or before the date, unless it falls in the previous month,
+        /// then the first business day after it
+        ModifiedPreceding,
+        /// The last business day of the month of the date
+        EndOfMonth,
+        /// The date is kept as it is
+        Unadjusted,
+    }
+
+    /// Determines whether a date is a business day in the calendar, failing when the calendar
+    /// does not hold the month of the date
+    pub fn is_business_day(date: Date, calendar: &DaysCalendar<BiDay>) -> Result<bool, DayendarError> {
+        let days: &Vec<BiDay> = Year::try_from(date.year()).ok()
+            .zip(Month::from_index(date.month() as u8))
+            .and_then(|(year, month)| calendar.get_days(year, month))
+            .ok_or(DayendarError::HorizonExceeded(date))?;
+        Ok(days.get(usize::from(date.day()) - 1) == Some(&BiDay::One))
+    }
+
+    /// Walks day by day from a date to the nearest business day in one direction
+    fn seek(date: Date, forward: bool, calendar: &DaysCalendar<BiDay>) -> Result<Date, DayendarError> {
+        let horizon = || DayendarError::HorizonExceeded(date);
+        let mut current: Date = date;
+        while !is_business_day(current, calendar).map_err(|_| horizon())? {
+            current = if forward { current.next_day() } else { current.previous_day() }.ok_or_else(horizon)?;
+        }
+        Ok(current)
+    }
+
+    fn following(date: Date, calendar: &DaysCalendar<BiDay>) -> Result<Date, DayendarError> {
+        seek(date, true, calendar)
+    }
+
+    fn preceding(date: Date, calendar: &DaysCalendar<BiDay>) -> Result<Date, DayendarError> {
+        seek(date, false, calendar)
+    }
+
+    fn same_month(date: Date, other: Date) -> bool {
+        date.year() == other.year() && date.month() == other.month()
+    }
+
+    /// Adjusts a date to a business day of the calendar following a roll convention
+    pub fn roll(date: Date, convention: RollConvention, calendar: &DaysCalendar<BiDay>) -> Result<Date, DayendarError> {
+        match convention {
+            RollConvention::Following => following(date, calendar),
+            RollConvention::Preceding => preceding(date, calendar),
+            // Running out of the horizon in one direction still tries the other one
+            RollConvention::ModifiedFollowing => match following(date, calendar) {
+                Ok(rolled) if same_month(date, rolled) => Ok(rolled),
+                _ => preceding(date, calendar),
+            },
+            RollConvention::ModifiedPreceding => match preceding(date, calendar) {
+                Ok(rolled) if same_month(date, rolled) => Ok(rolled),
+                _ => following(date, calendar),
+            },
+            RollConvention::EndOfMonth => {
+                let month_end = date.replace_day(date.month().length(date.year())).unwrap_or(date);
+                preceding(month_end, calendar)
+            },
+            RollConvention::Unadjusted => Ok(date),
+        }
+    }
+
+    /// Moves a date by `n` business days of the calendar, forward when `n` is positive
+    /// and backward when it is negative.
+    /// The date itself does not count as a step; with `n = 0` it is rolled to the following business day.
+    pub fn add_business_days(date: Date, n: isize, calendar: &DaysCalendar<BiDay>) -> Result<Date, DayendarError> {
+        let horizon = || DayendarError::HorizonExceeded(date);
+        is_business_day(date, calendar)?;
+        let mut current: Date = date;
+        for _ in 0..n.unsigned_abs() {
+            current = if n > 0 {
+                following(current.next_day().ok_or_else(horizon)?, calendar)
+            } else {
+                preceding(current.previous_day().ok_or_else(horizon)?, calendar)
+            }.map_err(|_| horizon())?;
+        }
+        if n == 0 {
+            current = following(date, calendar)?;
+        }
+        Ok(current)
+    }
+
+}
+
+/// The `daycount` module implements the day count conventions used to turn the period
+/// between two dates into a number of days and a fraction of a year.
+pub mod daycount {
+
+    use crate::types::{Year, Month, BiDay, Date};
+    use crate::calendar::DaysCalendar;
+    use crate::business::is_business_day;
+    use crate::utils::{is_leap, days_in_month};
+    use crate::error::DayendarError;
+
+    /// Day count conventions
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum DayCount<'a> {
+        /// Actual days over 360
+        Act360,
+        /// Actual days over 365
+        Act365Fixed,
+        /// Actual days in leap years over 366 plus actual days in other years over 365
+        ActActIsda,
+        /// Actual days over the actual days of the reference coupon period times the coupon frequency
+        ActActIcma { reference_start: Date, reference_end: Date, frequency: u8 },
+        /// 30/360 Bond Basis, also known as 30A/360
+        Thirty360BondBasis,
+        /// 30/360 US, with the end of February rules
+        Thirty360Us,
+        /// 30E/360, also known as Eurobond Basis
+        Thirty360E,
+        /// 30E+/360, the 31st of the end date moves to the 1st of the next month
+        Thirty360EPlus,
+        /// 30E/360 ISDA, the end of February is not adjusted when it is the maturity date
+        Thirty360Isda { maturity: Date },
+        /// Business days of the calendar over 252
+        Bus252(&'a DaysCalendar<BiDay>),
+    }
+
+    /// Determines whether the year of a date is a leap year, for any proleptic year
+    fn is_leap_date(date: Date) -> bool {
+        is_leap(date.year().rem_euclid(400) as Year + 400)
+    }
+
+    /// Determines whether a date is the last day of its month
+    fn is_month_end(date: Date) -> bool {
+        let year = date.year().rem_euclid(400) as Year + 400;
+        Month::from_index(date.month() as u8)
+            .and_then(|month| days_in_month(year, month))
+            .is_some_and(|n_days| n_days == date.day())
+    }
+
+    fn is_february_end(date: Date) -> bool {
+        date.month() == time::Month::February && is_month_end(date)
+    }
+
+    /// Checks that the calendar holds every month from `start` up to the day before `end`
+    fn check_horizon(calendar: &DaysCalendar<BiDay>, start: Date, end: Date) -> Result<(), DayendarError> {
+        let mut date: Date = start;
+        while date < end {
+            is_business_day(date, calendar)?;
+            let month_end: Date = date.replace_day(date.month().length(date.year())).unwrap_or(date);
+            match month_end.next_day() {
+                Some(next_month) => date = next_month,
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// 30/360 day count from adjusted day numbers
+    fn thirty_360(start: Date, end: Date, d1: u8, d2: u8) -> i64 {
+        360 * i64::from(end.year() - start.year())
+            + 30 * (i64::from(end.month() as u8) - i64::from(start.month() as u8))
+            + (i64::from(d2) - i64::from(d1))
+    }
+
+    impl DayCount<'_> {
+        /// Counts the days from `start` to `end` under the convention, negative when `end` is before `start`.
+        /// `Bus252` only counts the business days the calendar holds, `year_fraction` checks its horizon.
+        pub fn day_count(&self, start: Date, end: Date) -> i64 {
+            if start > end {
+                return -self.day_count(end, start);
+            }
+            let (d1, d2) = (start.day(), end.day());
+            match self {
+                DayCount::Act360 | DayCount::Act365Fixed | DayCount::ActActIsda | DayCount::ActActIcma { .. } => {
+                    (end - start).whole_days()
+                },
+                DayCount::Thirty360BondBasis => {
+                    let d1 = d1.min(30);
+                    let d2 = if d1 == 30 { d2.min(30) } else { d2 };
+                    thirty_360(start, end, d1, d2)
+                },
+                DayCount::Thirty360Us => {
+                    let d2 = if is_february_end(start) && is_february_end(end) { 30 } else { d2 };
+                    let d1 = if is_february_end(start) { 30 } else { d1 };
+                    let d2 = if d2 == 31 && d1 >= 30 { 30 } else { d2 };
+                    thirty_360(start, end, d1.min(30), d2)
+                },
+                DayCount::Thirty360E => thirty_360(start, end, d1.min(30), d2.min(30)),
+                DayCount::Thirty360EPlus => {
+                    let extra = if d2 == 31 { 1 } else { 0 };
+                    thirty_360(start, end, d1.min(30), d2 - 30 * extra) + 30 * i64::from(extra)
+                },
+                DayCount::Thirty360Isda { maturity } => {
+                    let d1 = if is_month_end(start) { 30 } else { d1 };
+                    let keep_end = end == *maturity && end.month() == time::Month::February;
+                    let d2 = if is_month_end(end) && !keep_end { 30 } else { d2 };
+                    thirty_360(start, end, d1, d2)
+                },
+                DayCount::Bus252(calendar) => {
+                    calendar.dates_from(start).take_while(|date| *date < end).count() as i64
+                },
+            }
+        }
+
+        /// Gets the fraction of a year from `start` to `end` under the convention.
+        /// `ActActIcma` fails with an empty reference period or a zero frequency and `Bus252` with
+        /// `HorizonExceeded` when the calendar does not hold every month of the period.
+        pub fn year_fraction(&self, start: Date, end: Date) -> Result<f64, DayendarError> {
+            if start > end {
+                return self.year_fraction(end, start).map(|fraction| -fraction);
+            }
+            let days = self.day_count(start, end) as f64;
+            Ok(match self {
+                DayCount::Act360 => days / 360.0,
+                DayCount::Act365Fixed => days / 365.0,
+                DayCount::ActActIsda => {
+                    let mut fraction = 0.0;
+                    let mut from = start;
+                    while from < end {
+                        let next_year = Date::from_ordinal_date(from.year() + 1, 1).map_or(end, |date| date.min(end));
+                        let basis = if is_leap_date(from) { 366.0 } else { 365.0 };
+                        fraction += (next_year - from).whole_days() as f64 / basis;
+                        from = next_year;
+                    }
+                    fraction
+                },
+                DayCount::ActActIcma { reference_start, reference_end, frequency } => {
+                    let period = (*reference_end - *reference_start).whole_days();
+                    if period <= 0 || *frequency == 0 {
+                        return Err(DayendarError::InvalidReferencePeriod {
+                            start: *reference_start, end: *reference_end, frequency: *frequency,
+                        });
+                    }
+                    days / (f64::from(*frequency) * period as f64)
+                },
+                DayCount::Thirty360BondBasis
+                | DayCount::Thirty360Us
+                | DayCount::Thirty360E
+                | DayCount::Thirty360EPlus
+                | DayCount::Thirty360Isda { .. } => days / 360.0,
+                DayCount::Bus252(calendar) => {
+                    check_horizon(calendar, start, end)?;
+                    days / 252.0
+                },
+            })
+        }
+    }
+
+}
+
+/// The `fiscal` module implements 52/53-week fiscal calendars, such as the 4-4-5 calendar of
+/// financial reporting and the 4-5-4 retail calendar of the NRF.
+///
+/// A fiscal year ends on the weekday given by a `YearEnd` rule, so it has 52 weeks or 53 when the
+/// rule moves the year end a week later. The year has 12 periods grouped in 4 quarters, their
+/// weeks follow a `WeekPattern` and the 53rd week, when present, goes to the last period.
+pub mod fiscal {
+
+    use std::fmt;
+
+    use crate::types::{Year, Month, BiDay, Date, DateSpan, Duration, Weekday};
+    use crate::calendar::{DaysCalendar, from_date, from_day};
+    use crate::utils::month_bounds;
+    use crate::error::DayendarError;
+
+    /// Rule fixing the last day of a fiscal year
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum YearEnd {
+        /// The last `weekday` of `month`, e.g., the last Saturday of January
+        LastWeekday { weekday: Weekday, month: Month },
+        /// The `weekday` nearest to the last day of `month`, it can fall early in the next month,
+        /// e.g., the Saturday nearest to January 31
+        NearestWeekday { weekday: Weekday, month: Month },
+    }
+
+    /// Number of weeks of the three periods of every quarter
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum WeekPattern {
+        FourFourFive,
+        FourFiveFour,
+        FiveFourFour,
+    }
+
+    /// Gregorian year that names a fiscal year
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum YearLabel {
+        /// The year of the year end month, e.g., FY2025 ends in 2025
+        EndYear,
+        /// The year before the year end month, as the NRF names a fiscal year ending in January
+        StartYear,
+    }
+
+    /// A 52/53-week fiscal calendar
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct FiscalCalendar {
+        pub year_end: YearEnd,
+        pub weeks: WeekPattern,
+        pub label: YearLabel,
+    }
+
+    /// A year, quarter, period or week of a fiscal calendar, numbered from 1
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum FiscalPeriod {
+        Year(Year),
+        Quarter(Year, u8),
+        Period(Year, u8),
+        Week(Year, u8),
+    }
+
+    /// Position of a date in a fiscal calendar, `week` is the week of the fiscal year
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct FiscalDate {
+        pub year: Year,
+        pub quarter: u8,
+        pub period: u8,
+        pub week: u8,
+    }
+
+    impl YearEnd {
+        /// Last day of the fiscal year whose year end month is in `year`
+        pub fn date(&self, year: Year) -> Result<Date, DayendarError> {
+            let (weekday, month) = match self {
+                YearEnd::LastWeekday { weekday, month } | YearEnd::NearestWeekday { weekday, month } => (weekday, month),
+            };
+            let (_, last): (Date, Date) = month_bounds(year, *month)?;
+            let ahead = (7 + weekday.number_days_from_monday() - last.weekday().number_days_from_monday()) % 7;
+            let days: i64 = match self {
+                YearEnd::NearestWeekday { .. } if ahead <= 3 => ahead.into(),
+                _ => i64::from(ahead) - 7 * i64::from(ahead > 0),
+            };
+            last.checked_add(Duration::days(days)).ok_or(DayendarError::YearOutOfRange(year.into()))
+        }
+    }
+
+    impl WeekPattern {
+        fn weeks(&self) -> [u8; 3] {
+            match self {
+                WeekPattern::FourFourFive => [4, 4, 5],
+                WeekPattern::FourFiveFour => [4, 5, 4],
+                WeekPattern::FiveFourFour => [5, 4, 4],
+            }
+        }
+    }
+
+    impl FiscalCalendar {
+        pub fn new(year_end: YearEnd, weeks: WeekPattern, label: YearLabel) -> Self {
+            FiscalCalendar { year_end, weeks, label }
+        }
+
+        /// The NRF retail calendar, 4-5-4 weeks ending on the Saturday nearest to January 31
+        pub fn nrf() -> Self {
+            FiscalCalendar::new(
+                YearEnd::NearestWeekday { weekday: Weekday::Saturday, month: Month::January },
+                WeekPattern::FourFiveFour,
+                YearLabel::StartYear,
+            )
+        }
+
+        /// First and last days of a fiscal year
+        pub fn year_bounds(&self, year: Year) -> Result<(Date, Date), DayendarError> {
+            let end_year: Year = match self.label {
+                YearLabel::EndYear => year,
+                YearLabel::StartYear => year.checked_add(1).ok_or(DayendarError::YearOutOfRange(year.into()))?,
+            };
+            let previous: Year = end_year.checked_sub(1).ok_or(DayendarError::YearOutOfRange(0))?;
+            let first: Date = self.year_end.date(previous)?.next_day().ok_or(DayendarError::YearOutOfRange(year.into()))?;
+            Ok((first, self.year_end.date(end_year)?))
+        }
+
+        /// Number of weeks of a fiscal year, 52 or 53
+        pub fn weeks_in_year(&self, year: Year) -> Result<u8, DayendarError> {
+            let (first, last) = self.year_bounds(year)?;
+            Ok(((last - first).whole_days() / 7 + 1) as u8)
+        }
+
+        /// First week and number of weeks of the periods of a fiscal year
+        fn periods(&self, year: Year) -> Result<[(u8, u8); 12], DayendarError> {
+            let long_year: bool = self.weeks_in_year(year)? == 53;
+            let mut periods = [(0, 0); 12];
+            let mut week: u8 = 1;
+            for (index, length) in self.weeks.weeks().into_iter().cycle().take(12).enumerate() {
+                let length = length + u8::from(long_year && index == 11);
+                periods[index] = (week, length);
+                week += length;
+            }
+            Ok(periods)
+        }
+
+        /// First and last days of a fiscal year, quarter, period or week
+        pub fn bounds(&self, period: FiscalPeriod) -> Result<(Date, Date), DayendarError> {
+            let invalid = || DayendarError::InvalidFiscalPeriod(period);
+            let (year, first_week, last_week) = match period {
+                FiscalPeriod::Year(year) => return self.year_bounds(year),
+                FiscalPeriod::Quarter(year, quarter) => {
+                    if !(1..=4).contains(&quarter) {
+                        return Err(invalid());
+                    }
+                    let periods = self.periods(year)?;
+                    let (start, _) = periods[usize::from(3 * quarter - 3)];
+                    let (last, length) = periods[usize::from(3 * quarter - 1)];
+                    (year, start, last + length - 1)
+                },
+                FiscalPeriod::Period(year, number) => {
+                    if !(1..=12).contains(&number) {
+                        return Err(invalid());
+                    }
+                    let (start, length) = self.periods(year)?[usize::from(number - 1)];
+                    (year, start, start + length - 1)
+                },
+                FiscalPeriod::Week(year, week) => {
+                    if week == 0 || week > self.weeks_in_year(year)? {
+                        return Err(invalid());
+                    }
+                    (year, week, week)
+                },
+            };
+            let (first, _) = self.year_bounds(year)?;
+            let day = |week: u8, day: i64| first + Duration::days(7 * i64::from(week - 1) + day);
+            Ok((day(first_week, 0), day(last_week, 6)))
+        }
+
+        /// Gets the fiscal year, quarter, period and week of a date
+        pub fn fiscal_date(&self, date: Date) -> Result<FiscalDate, DayendarError> {
+            let calendar_year = Year::try_from(date.year()).map_err(|_| DayendarError::YearOutOfRange(date.year()))?;
+            let mut year: Year = match self.label {
+                YearLabel::EndYear => calendar_year,
+                YearLabel::StartYear => calendar_year.saturating_sub(1),
+            };
+            let (mut first, mut last) = self.year_bounds(year)?;
+            if date > last {
+                year += 1;
+                (first, last) = self.year_bounds(year)?;
+            } else if date < first {
+                year -= 1;
+                (first, last) = self.year_bounds(year)?;
+            }
+            debug_assert!(first <= date && date <= last);
+
+            let week = ((date - first).whole_days() / 7 + 1) as u8;
+            let index = self.periods(year)?.iter().rposition(|(start, _)| *start <= week).unwrap_or(0) as u8;
+            Ok(FiscalDate { year, quarter: index / 3 + 1, period: index + 1, week })
+        }
+
+        /// Span of the months that hold a fiscal period
+        pub fn span(&self, period: FiscalPeriod) -> Result<DateSpan, DayendarError> {
+            self.bounds(period)?;
+            Ok(DateSpan::Fiscal(*self, period))
+        }
+
+        /// Calendar of the months that hold a fiscal period, where the days of the period are `BiDay::One`
+        pub fn days_calendar(&self, period: FiscalPeriod) -> Result<DaysCalendar<BiDay>, DayendarError> {
+            let (first, last) = self.bounds(period)?;
+            let days = (last - first).whole_days();
+            from_day(from_date((0..=days).map(|day| first + Duration::days(day)).collect())?)
+        }
+    }
+
+    impl fmt::Display for FiscalPeriod {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                FiscalPeriod::Year(year) => write!(f, "FY{}", year),
+                FiscalPeriod::Quarter(year, quarter) => write!(f, "FY{} Q{}", year, quarter),
+                FiscalPeriod::Period(year, period) => write!(f, "FY{} P{}", year, period),
+                FiscalPeriod::Week(year, week) => write!(f, "FY{} W{}", year, week),
+            }
+        }
+    }
+
+    impl fmt::Display for FiscalDate {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "FY{} Q{} P{:02} W{:02}", self.year, self.quarter, self.period, self.week)
+        }
+    }
+
+}
+
+// =========================================
+// SECTION FOR UNIT TEST CODE ...
+// =========================================
+#[cfg(test)]
+mod tests_business {
+
+    use crate::business::*;
+    use crate::calendar::*;
+    use crate::error::DayendarError;
+    use crate::types::*;
+    use std::collections::HashSet;
+
+    /// Weekdays of March to May 2024 without Good Friday and Easter Monday
+    fn business_days() -> DaysCalendar<BiDay> {
+        let weekdays: HashSet<Weekday> = [Weekday::Monday, Weekday::Tuesday, Weekday::Wednesday, Weekday::Thursday, Weekday::Friday]
+            .into_iter()
+            .collect();
+        let calendar = DaysCalendar::singleton(2024, Month::March).unwrap()
+            .append(&DaysCalendar::singleton(2024, Month::April).unwrap())
+            .append(&DaysCalendar::singleton(2024, Month::May).unwrap())
+            .and_weekdays(weekdays)
+            .unwrap();
+        let holidays = from_date(vec![date!(2024 - 03 - 29), date!(2024 - 04 - 01)]).unwrap();
+        calendar.minus(&from_day(holidays).unwrap())
+    }
+
+    #[test]
+    fn test_roll_conventions() {
+        let calendar = business_days();
+        let saturday = date!(2024 - 03 - 30);
+        assert_eq!(roll(saturday, RollConvention::Following, &calendar), Ok(date!(2024 - 04 - 02)));
+        assert_eq!(roll(saturday, RollConvention::ModifiedFollowing, &calendar), Ok(date!(2024 - 03 - 28)));
+        assert_eq!(roll(saturday, RollConvention::Preceding, &calendar), Ok(date!(2024 - 03 - 28)));
+        assert_eq!(roll(saturday, RollConvention::Unadjusted, &calendar), Ok(saturday));
+        assert_eq!(roll(date!(2024 - 04 - 01), RollConvention::ModifiedPreceding, &calendar), Ok(date!(2024 - 04 - 02)));
+        assert_eq!(roll(date!(2024 - 04 - 06), RollConvention::ModifiedPreceding, &calendar), Ok(date!(2024 - 04 - 05)));
+        assert_eq!(roll(date!(2024 - 03 - 04), RollConvention::EndOfMonth, &calendar), Ok(date!(2024 - 03 - 28)));
+        assert_eq!(roll(date!(2024 - 04 - 10), RollConvention::Following, &calendar), Ok(date!(2024 - 04 - 10)));
+        assert_eq!(is_business_day(date!(2024 - 04 - 10), &calendar), Ok(true));
+        assert_eq!(is_business_day(date!(2024 - 03 - 29), &calendar), Ok(false));
+    }
+
+    #[test]
+    fn test_add_business_days() {
+        let calendar = business_days();
+        assert_eq!(add_business_days(date!(2024 - 03 - 27), 2, &calendar), Ok(date!(2024 - 04 - 02)));
+        assert_eq!(add_business_days(date!(2024 - 03 - 30), 1, &calendar), Ok(date!(2024 - 04 - 02)));
+        assert_eq!(add_business_days(date!(2024 - 04 - 02), -1, &calendar), Ok(date!(2024 - 03 - 28)));
+        assert_eq!(add_business_days(date!(2024 - 03 - 30), 0, &calendar), Ok(date!(2024 - 04 - 02)));
+        assert_eq!(add_business_days(date!(2024 - 04 - 10), 0, &calendar), Ok(date!(2024 - 04 - 10)));
+    }
+
+    #[test]
+    fn test_horizon_exceeded() {
+        let calendar = business_days();
+        let last = date!(2024 - 05 - 31);
+        assert_eq!(add_business_days(last, 1, &calendar), Err(DayendarError::HorizonExceeded(last)));
+        assert_eq!(add_business_days(date!(2024 - 03 - 01), -1, &calendar), Err(DayendarError::HorizonExceeded(date!(2024 - 03 - 01))));
+        assert_eq!(roll(date!(2024 - 06 - 01), RollConvention::Following, &calendar), Err(DayendarError::HorizonExceeded(date!(2024 - 06 - 01))));
+        assert_eq!(roll(date!(2024 - 06 - 01), RollConvention::ModifiedFollowing, &calendar), Err(DayendarError::HorizonExceeded(date!(2024 - 06 - 01))));
+
+        // Months the calendar does not hold are outside its horizon, not holidays
+        let february = date!(2024 - 02 - 15);
+        assert_eq!(is_business_day(february, &calendar), Err(DayendarError::HorizonExceeded(february)));
+        assert_eq!(roll(february, RollConvention::Following, &calendar), Err(DayendarError::HorizonExceeded(february)));
+        assert_eq!(add_business_days(date!(2024 - 01 - 10), 1, &calendar), Err(DayendarError::HorizonExceeded(date!(2024 - 01 - 10))));
+        assert_eq!(add_business_days(date!(2024 - 05 - 30), 2, &calendar), Err(DayendarError::HorizonExceeded(date!(2024 - 05 - 30))));
+
+        // The modified conventions fall back to the other direction at the horizon
+        let march = from_day(from_date(vec![date!(2024 - 03 - 04), date!(2024 - 03 - 28)]).unwrap()).unwrap();
+        assert_eq!(roll(date!(2024 - 03 - 30), RollConvention::ModifiedFollowing, &march), Ok(date!(2024 - 03 - 28)));
+        assert_eq!(roll(date!(2024 - 03 - 02), RollConvention::ModifiedPreceding, &march), Ok(date!(2024 - 03 - 04)));
+        assert_eq!(
+            DayendarError::HorizonExceeded(last).to_string(),
+            "calendar horizon exceeded moving from 2024-05-31"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_daycount {
+
+    use crate::calendar::*;
+    use crate::daycount::*;
+    use crate::error::DayendarError;
+    use crate::types::*;
+    use std::collections::HashSet;
+
+    fn assert_close(left: f64, right: f64) {
+        assert!((left - right).abs() < 1e-12, "{} != {}", left, right);
+    }
+
+    #[test]
+    fn test_actual_conventions() {
+        let (start, end) = (date!(2023 - 11 - 15), date!(2024 - 03 - 15));
+        assert_eq!(DayCount::Act360.day_count(start, end), 121);
+        assert_close(DayCount::Act360.year_fraction(start, end).unwrap(), 121.0 / 360.0);
+        assert_close(DayCount::Act365Fixed.year_fraction(start, end).unwrap(), 121.0 / 365.0);
+        assert_close(DayCount::ActActIsda.year_fraction(start, end).unwrap(), 47.0 / 365.0 + 74.0 / 366.0);
+        assert_close(DayCount::ActActIsda.year_fraction(end, start).unwrap(), -(47.0 / 365.0 + 74.0 / 366.0));
+
+        let icma = DayCount::ActActIcma { reference_start: date!(2023 - 09 - 15), reference_end: date!(2024 - 03 - 15), frequency: 2 };
+        assert_close(icma.year_fraction(start, end).unwrap(), 121.0 / (2.0 * 182.0));
+
+        let reference = date!(2024 - 03 - 15);
+        let empty = DayCount::ActActIcma { reference_start: reference, reference_end: reference, frequency: 2 };
+        assert_eq!(
+            empty.year_fraction(start, end),
+            Err(DayendarError::InvalidReferencePeriod { start: reference, end: reference, frequency: 2 })
+        );
+        let no_coupons = DayCount::ActActIcma { reference_start: date!(2023 - 09 - 15), reference_end: reference, frequency: 0 };
+        assert!(no_coupons.year_fraction(start, end).is_err());
+    }
+
+    #[test]
+    fn test_thirty_360_conventions() {
+        let cases = [
+            // start, end, bond basis, US, E, E+, ISDA
+            (date!(2024 - 02 - 29), date!(2024 - 03 - 31), 32, 30, 31, 32, 30),
+            (date!(2023 - 01 - 31), date!(2023 - 03 - 31), 60, 60, 60, 61, 60),
+            (date!(2023 - 02 - 28), date!(2023 - 08 - 31), 183, 180, 182, 183, 180),
+            (date!(2023 - 01 - 15), date!(2023 - 02 - 28), 43, 43, 43, 43, 45),
+        ];
+        let maturity = date!(2030 - 01 - 01);
+        for (start, end, bond, us, e, e_plus, isda) in cases {
+            assert_eq!(DayCount::Thirty360BondBasis.day_count(start, end), bond, "bond basis {}", start);
+            assert_eq!(DayCount::Thirty360Us.day_count(start, end), us, "US {}", start);
+            assert_eq!(DayCount::Thirty360E.day_count(start, end), e, "E {}", start);
+            assert_eq!(DayCount::Thirty360EPlus.day_count(start, end), e_plus, "E+ {}", start);
+            assert_eq!(DayCount::Thirty360Isda { maturity }.day_count(start, end), isda, "ISDA {}", start);
+        }
+        let at_maturity = DayCount::Thirty360Isda { maturity: date!(2023 - 02 - 28) };
+        assert_eq!(at_maturity.day_count(date!(2023 - 01 - 15), date!(2023 - 02 - 28)), 43);
+        assert_close(DayCount::Thirty360E.year_fraction(date!(2023 - 01 - 31), date!(2023 - 03 - 31)).unwrap(), 60.0 / 360.0);
+    }
+
+    #[test]
+    fn test_bus_252() {
+        let weekdays: HashSet<Weekday> = [Weekday::Monday, Weekday::Tuesday, Weekday::Wednesday, Weekday::Thursday, Weekday::Friday]
+            .into_iter()
+            .collect();
+        let calendar = DaysCalendar::singleton(2024, Month::January).unwrap().and_weekdays(weekdays).unwrap();
+        let convention = DayCount::Bus252(&calendar);
+        assert_eq!(convention.day_count(date!(2024 - 01 - 01), date!(2024 - 01 - 08)), 5);
+        assert_eq!(convention.day_count(date!(2024 - 01 - 06), date!(2024 - 01 - 08)), 0);
+        assert_close(convention.year_fraction(date!(2024 - 01 - 01), date!(2024 - 02 - 01)).unwrap(), 23.0 / 252.0);
+
+        // The calendar only holds January 2024
+        assert_eq!(
+            convention.year_fraction(date!(2024 - 01 - 15), date!(2026 - 01 - 15)),
+            Err(DayendarError::HorizonExceeded(date!(2024 - 02 - 01)))
+        );
+        assert_eq!(
+            convention.year_fraction(date!(2026 - 01 - 15), date!(2024 - 01 - 15)),
+            Err(DayendarError::HorizonExceeded(date!(2024 - 02 - 01)))
+        );
+        assert_eq!(
+            convention.year_fraction(date!(2023 - 12 - 31), date!(2024 - 01 - 08)),
+            Err(DayendarError::HorizonExceeded(date!(2023 - 12 - 31)))
+        );
+        assert_eq!(convention.year_fraction(date!(2024 - 03 - 01), date!(2024 - 03 - 01)), Ok(0.0));
+    }
+}
+
+#[cfg(test)]
+mod tests_fiscal {
+
+    use crate::fiscal::*;
+    use crate::error::DayendarError;
+    use crate::types::*;
+    use crate::utils::for_each_test_day;
+
+    #[test]
+    fn test_year_end_rules() {
+        let nearest = YearEnd::NearestWeekday { weekday: Weekday::Saturday, month: Month::January };
+        assert_eq!(nearest.date(2023).unwrap(), date!(2023 - 01 - 28));
+        assert_eq!(nearest.date(2024).unwrap(), date!(2024 - 02 - 03));
+        assert_eq!(nearest.date(2025).unwrap(), date!(2025 - 02 - 01));
+        let last = YearEnd::LastWeekday { weekday: Weekday::Saturday, month: Month::January };
+        assert_eq!(last.date(2024).unwrap(), date!(2024 - 01 - 27));
+        assert_eq!(last.date(2022).unwrap(), date!(2022 - 01 - 29));
+    }
+
+    #[test]
+    fn test_nrf_calendar() {
+        let nrf = FiscalCalendar::nrf();
+        assert_eq!(nrf.year_bounds(2023).unwrap(), (date!(2023 - 01 - 29), date!(2024 - 02 - 03)));
+        assert_eq!((nrf.weeks_in_year(2023).unwrap(), nrf.weeks_in_year(2024).unwrap()), (53, 52));
+        assert_eq!(nrf.bounds(FiscalPeriod::Period(2024, 1)).unwrap(), (date!(2024 - 02 - 04), date!(2024 - 03 - 02)));
+        assert_eq!(nrf.bounds(FiscalPeriod::Period(2024, 2)).unwrap(), (date!(2024 - 03 - 03), date!(2024 - 04 - 06)));
+        assert_eq!(nrf.bounds(FiscalPeriod::Quarter(2024, 1)).unwrap(), (date!(2024 - 02 - 04), date!(2024 - 05 - 04)));
+        // The 53rd week goes to the last period
+        assert_eq!(nrf.bounds(FiscalPeriod::Period(2023, 12)).unwrap(), (date!(2023 - 12 - 31), date!(2024 - 02 - 03)));
+        assert_eq!(nrf.bounds(FiscalPeriod::Week(2023, 53)).unwrap(), (date!(2024 - 01 - 28), date!(2024 - 02 - 03)));
+        assert_eq!(nrf.bounds(FiscalPeriod::Week(2024, 53)), Err(DayendarError::InvalidFiscalPeriod(FiscalPeriod::Week(2024, 53))));
+        assert!(nrf.bounds(FiscalPeriod::Quarter(2024, 5)).is_err());
+        assert!(nrf.bounds(FiscalPeriod::Period(2024, 0)).is_err());
+    }
+
+    #[test]
+    fn test_fiscal_date() {
+        let nrf = FiscalCalendar::nrf();
+        assert_eq!(nrf.fiscal_date(date!(2024 - 02 - 03)).unwrap(), FiscalDate { year: 2023, quarter: 4, period: 12, week: 53 });
+        assert_eq!(nrf.fiscal_date(date!(2024 - 02 - 04)).unwrap(), FiscalDate { year: 2024, quarter: 1, period: 1, week: 1 });
+        assert_eq!(nrf.fiscal_date(date!(2024 - 05 - 05)).unwrap().to_string(), "FY2024 Q2 P04 W14");
+
+        let calendar = FiscalCalendar::new(
+            YearEnd::LastWeekday { weekday: Weekday::Saturday, month: Month::December },
+            WeekPattern::FourFourFive,
+            YearLabel::EndYear,
+        );
+        for_each_test_day(|date| {
+            let fiscal = calendar.fiscal_date(date).unwrap();
+            let (first, last) = calendar.bounds(FiscalPeriod::Week(fiscal.year, fiscal.week)).unwrap();
+            assert!(first <= date && date <= last);
+            let (first, last) = calendar.bounds(FiscalPeriod::Period(fiscal.year, fiscal.period)).unwrap();
+            assert!(first <= date && date <= last);
+            let (first, last) = calendar.bounds(FiscalPeriod::Quarter(fiscal.year, fiscal.quarter)).unwrap();
+            assert!(first <= date && date <= last);
+        });
+    }
+
+    #[test]
+    fn test_fiscal_span_and_calendar() {
+        let nrf = FiscalCalendar::nrf();
+        let span = nrf.span(FiscalPeriod::Quarter(2024, 1)).unwrap();
+        assert_eq!(span, DateSpan::Fiscal(nrf, FiscalPeriod::Quarter(2024, 1)));
+        let mut months: Vec<(Year, Month)> = span.to_year_month().into_iter().collect();
+        months.sort();
+        assert_eq!(months, vec![(2024, Month::February), (2024, Month::March), (2024, Month::April), (2024, Month::May)]);
+        assert!(nrf.span(FiscalPeriod::Week(2024, 53)).is_err());
+        assert!(DateSpan::Fiscal(nrf, FiscalPeriod::Week(2024, 53)).to_year_month().is_empty());
+
+        let days = nrf.days_calendar(FiscalPeriod::Period(2024, 1)).unwrap();
+        let dates: Vec<Date> = days.dates().collect();
+        assert_eq!(dates.len(), 28);
+        assert_eq!((dates[0], dates[27]), (date!(2024 - 02 - 04), date!(2024 - 03 - 02)));
+    }
+}
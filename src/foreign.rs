@@ -0,0 +1,1251 @@
+/// Implements the `year`, `month` and `day` getters and the `Display` of a foreign date, which is
+/// written as the year, month and day followed by the era
+macro_rules! date_accessors {
+    ($date:ident, $era:literal) => {
+        impl $date {
+            pub fn year(&self) -> i32 {
+                self.year
+            }
+
+            pub fn month(&self) -> u8 {
+                self.month
+            }
+
+            pub fn day(&self) -> u8 {
+                self.day
+            }
+        }
+
+        impl fmt::Display for $date {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, concat!("{:04}-{:02}-{:02} ", $era), self.year, self.month, self.day)
+            }
+        }
+    };
+}
+
+/// The `hijri` module converts dates of the tabular Islamic (Hijri) calendar.
+///
+/// The tabular calendar has 12 months of 30 and 29 days alternately, the last month gets a 30th day
+/// in 11 leap years of every 30 year cycle (2, 5, 7, 10, 13, 16, 18, 21, 24, 26 and 29). The epoch is
+/// 1 Muharram 1 AH, 19 July 622 of the proleptic Gregorian calendar. Authorities that start the
+/// months by moon sighting can differ by a day or two, a `HijriAdjustments` table moves the start
+/// of the months they announce.
+///
+/// | Holiday          | Month | Day |
+/// |------------------|-------|-----|
+/// | Islamic New Year | 1     | 1   |
+/// | Ramadan          | 9     | all |
+/// | Eid al-Fitr      | 10    | 1   |
+/// | Eid al-Adha      | 12    | 10  |
+pub mod hijri {
+
+    use std::collections::BTreeMap;
+    use std::fmt;
+
+    use crate::types::{Date, Duration};
+    use crate::error::DayendarError;
+
+    /// Julian day number of 1 Muharram 1 AH
+    const EPOCH: i32 = 1_948_440;
+
+    /// Largest number of days a month start can be moved by an adjustment
+    pub const MAX_ADJUSTMENT: i8 = 3;
+
+    /// A date of the tabular Islamic calendar
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct HijriDate {
+        year: i32,
+        month: u8,
+        day: u8,
+    }
+
+    /// Days added to the tabular start of some months, keyed by Hijri year and month
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    pub struct HijriAdjustments {
+        adjustments: BTreeMap<(i32, u8), i8>,
+    }
+
+    impl HijriAdjustments {
+        /// Creates an empty table, every month starts on its tabular date
+        pub fn new() -> Self {
+            HijriAdjustments::default()
+        }
+
+        /// Moves the start of a month by `days`, from -3 to 3
+        pub fn insert(&mut self, year: i32, month: u8, days: i8) -> Result<(), DayendarError> {
+            if !(1..=12).contains(&month) {
+                return Err(DayendarError::InvalidMonth(month));
+            }
+            if year < 1 {
+                return Err(DayendarError::YearOutOfRange(year));
+            }
+            if days.abs() > MAX_ADJUSTMENT {
+                return Err(DayendarError::InvalidAdjustment(days));
+            }
+            if days == 0 {
+                self.adjustments.remove(&(year, month));
+            } else {
+                self.adjustments.insert((year, month), days);
+            }
+            Ok(())
+        }
+
+        /// Days added to the tabular start of a month
+        pub fn get(&self, year: i32, month: u8) -> i8 {
+            self.adjustments.get(&(year, month)).copied().unwrap_or(0)
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.adjustments.is_empty()
+        }
+
+        /// Iterates the (year, month, days) adjustments in chronological order
+        pub fn iter(&self) -> impl Iterator<Item = (i32, u8, i8)> + '_ {
+            self.adjustments.iter().map(|(&(year, month), &days)| (year, month, days))
+        }
+
+        /// Julian day number of the first day of a month
+        fn month_start(&self, year: i32, month: u8) -> i32 {
+            tabular_julian_day(year, month, 1) + i32::from(self.get(year, month))
+        }
+
+        /// Number of days of a month
+        fn month_length(&self, year: i32, month: u8) -> u8 {
+            let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+            (self.month_start(next_year, next_month) - self.month_start(year, month)) as u8
+        }
+    }
+
+    impl fmt::Display for HijriAdjustments {
+        /// Writes the table as `[1445-12: -1, 1446-9: 1]`
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let items: Vec<String> = self.iter().map(|(year, month, days)| format!("{}-{}: {}", year, month, days)).collect();
+            write!(f, "[{}]", items.join(", "))
+        }
+    }
+
+    /// Determines whether the year has a 30th day in its last month
+    pub fn is_leap_year(year: i32) -> bool {
+        (14 + 11 * year).rem_euclid(30) < 11
+    }
+
+    /// Number of days of a month of the tabular calendar
+    pub fn days_in_month(year: i32, month: u8) -> u8 {
+        if month % 2 == 1 || (month == 12 && is_leap_year(year)) { 30 } else { 29 }
+    }
+
+    /// Julian day number of a date of the tabular calendar
+    fn tabular_julian_day(year: i32, month: u8, day: u8) -> i32 {
+        let month = i32::from(month);
+        EPOCH - 1 + i32::from(day) + (59 * (month - 1) + 1) / 2 + (year - 1) * 354 + (3 + 11 * year).div_euclid(30)
+    }
+
+    impl HijriDate {
+        /// Creates a date of the tabular calendar, the year must be at least 1
+        pub fn new(year: i32, month: u8, day: u8) -> Result<Self, DayendarError> {
+            Self::with_adjustments(year, month, day, &HijriAdjustments::new())
+        }
+
+        /// Creates a date, checking the day against the month length given by the adjustments
+        pub fn with_adjustments(year: i32, month: u8, day: u8, adjustments: &HijriAdjustments) -> Result<Self, DayendarError> {
+            if !(1..=12).contains(&month) {
+                return Err(DayendarError::InvalidMonth(month));
+            }
+            if !(1..=9666).contains(&year) || day == 0 || day > adjustments.month_length(year, month) {
+                return Err(DayendarError::InvalidDate { year, month, day });
+            }
+            Ok(HijriDate { year, month, day })
+        }
+
+        /// Converts a Gregorian date, `None` before the epoch
+        pub fn from_gregorian(date: Date) -> Option<Self> {
+            Self::from_gregorian_adjusted(date, &HijriAdjustments::new())
+        }
+
+        /// Converts a Gregorian date using the month starts of the adjustments
+        pub fn from_gregorian_adjusted(date: Date, adjustments: &HijriAdjustments) -> Option<Self> {
+            let julian_day: i32 = date.to_julian_day();
+            // Estimate with the tabular calendar, then follow the adjusted month starts
+            let mut year: i32 = (30 * (julian_day - EPOCH) + 10646).div_euclid(10631);
+            let mut month: u8 = ((2 * (julian_day - 29 - tabular_julian_day(year, 1, 1)) + 58).div_euclid(59) + 1).clamp(1, 12) as u8;
+            while julian_day < adjustments.month_start(year, month) {
+                (year, month) = if month == 1 { (year - 1, 12) } else { (year, month - 1) };
+            }
+            loop {
+                let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+                if julian_day < adjustments.month_start(next_year, next_month) {
+                    break;
+                }
+                (year, month) = (next_year, next_month);
+            }
+            if year < 1 {
+                return None;
+            }
+            let day = (julian_day - adjustments.month_start(year, month) + 1) as u8;
+            Some(HijriDate { year, month, day })
+        }
+
+        /// Converts the date to the Gregorian calendar
+        pub fn to_gregorian(&self) -> Result<Date, DayendarError> {
+            self.to_gregorian_adjusted(&HijriAdjustments::new())
+        }
+
+        /// Converts the date to the Gregorian calendar using the month starts of the adjustments
+        pub fn to_gregorian_adjusted(&self, adjustments: &HijriAdjustments) -> Result<Date, DayendarError> {
+            let julian_day = adjustments.month_start(self.year, self.month) + i32::from(self.day) - 1;
+            Date::from_julian_day(julian_day).map_err(|_| DayendarError::YearOutOfRange(self.year))
+        }
+    }
+
+    date_accessors!(HijriDate, "AH");
+
+    /// Gregorian dates from `first` to `last` that are `offset` days after the given day of a Hijri month,
+    /// `day` 0 stands for every day of the month
+    pub(crate) fn gregorian_dates(
+        month: u8, day: u8, offset: i16, first: Date, last: Date, adjustments: &HijriAdjustments,
+    ) -> Result<Vec<Date>, DayendarError> {
+        if !(1..=12).contains(&month) {
+            return Err(DayendarError::InvalidMonth(month));
+        }
+        if day > 30 {
+            return Err(DayendarError::InvalidDay(day));
+        }
+        let offset = Duration::days(offset.into());
+        let year_of = |date: Option<Date>| {
+            date.and_then(|date| HijriDate::from_gregorian_adjusted(date, adjustments)).map(|date| date.year())
+        };
+        let from: i32 = year_of(first.checked_sub(offset)).unwrap_or(1);
+        let to: i32 = match year_of(last.checked_sub(offset)) {
+            Some(year) => year,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut dates: Vec<Date> = Vec::new();
+        for year in from..=to {
+            let length = adjustments.month_length(year, month);
+            let days = if day == 0 { 1..=length } else { day..=day.min(length) };
+            for day in days {
+                let Ok(date) = HijriDate { year, month, day }.to_gregorian_adjusted(adjustments) else {
+                    continue;
+                };
+                if let Some(date) = date.checked_add(offset).filter(|date| *date >= first && *date <= last) {
+                    dates.push(date);
+                }
+            }
+        }
+        Ok(dates)
+    }
+
+}
+
+/// The `hebrew` module implements the arithmetic of the Hebrew calendar.
+///
+/// Months are numbered from Nisan, as in the Torah: 1 Nisan, 2 Iyyar, 3 Sivan, 4 Tammuz, 5 Av, 6 Elul,
+/// 7 Tishri, 8 Marheshvan, 9 Kislev, 10 Tevet, 11 Shevat, 12 Adar and, in leap years, 12 Adar I and
+/// 13 Adar II. The year starts on 1 Tishri. Leap years are the years 3, 6, 8, 11, 14, 17 and 19 of the
+/// 19 year cycle. New years start on the day of the molad of Tishri, postponed by the dehiyyot.
+///
+/// | Holiday       | Month | Day |
+/// |---------------|-------|-----|
+/// | Rosh Hashanah | 7     | 1   |
+/// | Yom Kippur    | 7     | 10  |
+/// | Sukkot        | 7     | 15  |
+/// | Passover      | 1     | 15  |
+/// | Shavuot       | 3     | 6   |
+pub mod hebrew {
+
+    use std::fmt;
+
+    use crate::types::Date;
+    use crate::error::DayendarError;
+
+    /// Julian day number of 1 Tishri 1 AM
+    const EPOCH: i32 = 347_998;
+
+    /// Parts (halakim) of a day, 1080 parts make an hour
+    const PARTS_PER_DAY: i64 = 25_920;
+
+    /// Length of a lunar month, 29 days 12 hours and 793 parts
+    const MONTH_PARTS: i64 = 29 * PARTS_PER_DAY + 12 * 1080 + 793;
+
+    pub const NISAN: u8 = 1;
+    pub const TISHRI: u8 = 7;
+
+    /// A date of the Hebrew calendar
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct HebrewDate {
+        year: i32,
+        month: u8,
+        day: u8,
+    }
+
+    /// Mean conjunction of a month, in Jerusalem mean time counted from midnight
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub struct Molad {
+        pub date: Date,
+        pub hour: u8,
+        pub minute: u8,
+        /// Parts of a minute, from 0 to 17
+        pub parts: u8,
+    }
+
+    /// Determines whether the year has the month Adar II
+    pub fn is_leap_year(year: i32) -> bool {
+        (7 * i64::from(year) + 1).rem_euclid(19) < 7
+    }
+
+    /// Number of months of the year, 12 or 13
+    pub fn months_in_year(year: i32) -> u8 {
+        if is_leap_year(year) { 13 } else { 12 }
+    }
+
+    /// Lunar months from the epoch to the month Tishri of the year
+    fn months_elapsed(year: i32) -> i64 {
+        (235 * i64::from(year) - 234).div_euclid(19)
+    }
+
+    /// Molad of a month of the year
+    pub fn molad(year: i32, month: u8) -> Result<Molad, DayendarError> {
+        if month == 0 || month > months_in_year(year) {
+            return Err(DayendarError::InvalidMonth(month));
+        }
+        // Months before Tishri belong to the cycle of the next year
+        let cycle_year = if month < TISHRI { year + 1 } else { year };
+        let months = i64::from(month) - i64::from(TISHRI) + months_elapsed(cycle_year);
+        // The molad of Tishri 1 AM was 876 parts before the midnight of the epoch
+        let parts = months * MONTH_PARTS - 876;
+        let days = i32::try_from(parts.div_euclid(PARTS_PER_DAY)).map_err(|_| DayendarError::YearOutOfRange(year))?;
+        let date = Date::from_julian_day(EPOCH + days).map_err(|_| DayendarError::YearOutOfRange(year))?;
+        let remainder = parts.rem_euclid(PARTS_PER_DAY);
+        Ok(Molad {
+            date,
+            hour: (remainder / 1080) as u8,
+            minute: (remainder % 1080 / 18) as u8,
+            parts: (remainder % 18) as u8,
+        })
+    }
+
+    /// Days from the epoch to the molad of Tishri, postponed when the molad falls on
+    /// Sunday, Wednesday or Friday (lo ADU Rosh) or at noon or later (molad zaken)
+    fn elapsed_days(year: i32) -> i64 {
+        let months = months_elapsed(year);
+        let parts = 12_084 + 13_753 * months;
+        let days = 29 * months + parts.div_euclid(PARTS_PER_DAY);
+        if (3 * (days + 1)).rem_euclid(7) < 3 { days + 1 } else { days }
+    }
+
+    /// Postponements that keep the year length within the allowed values (GaTaRaD and BeTUTaKPaT)
+    fn year_length_correction(year: i32) -> i64 {
+        let (previous, current, next) = (elapsed_days(year - 1), elapsed_days(year), elapsed_days(year + 1));
+        if next - current == 356 {
+            2
+        } else if current - previous == 382 {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Julian day number of 1 Tishri of the year
+    fn new_year(year: i32) -> i64 {
+        i64::from(EPOCH) + elapsed_days(year) + year_length_correction(year)
+    }
+
+    /// Number of days of the year, 353 to 355 in common years and 383 to 385 in leap years
+    pub fn days_in_year(year: i32) -> u16 {
+        (new_year(year + 1) - new_year(year)) as u16
+    }
+
+    /// Number of days of a month of the year, 0 when the year has no such month
+    pub fn days_in_month(year: i32, month: u8) -> u8 {
+        let length = days_in_year(year);
+        match month {
+            2 | 4 | 6 | 10 | 13 if month <= months_in_year(year) => 29,
+            12 if !is_leap_year(year) => 29,
+            // Marheshvan is long in complete years, Kislev is short in deficient years
+            8 if length % 10 != 5 => 29,
+            9 if length % 10 == 3 => 29,
+            1..=12 => 30,
+            _ => 0,
+        }
+    }
+
+    impl HebrewDate {
+        /// Creates a date of the Hebrew calendar, the year must be at least 1
+        pub fn new(year: i32, month: u8, day: u8) -> Result<Self, DayendarError> {
+            if month == 0 || month > 13 {
+                return Err(DayendarError::InvalidMonth(month));
+            }
+            if !(1..=13_760).contains(&year) || day == 0 || day > days_in_month(year, month) {
+                return Err(DayendarError::InvalidDate { year, month, day });
+            }
+            Ok(HebrewDate { year, month, day })
+        }
+
+        /// Julian day number of the date
+        fn julian_day(&self) -> i64 {
+            // The year runs from Tishri to Elul, so the months from Nisan come after the last month
+            let length = |month: u8| i64::from(days_in_month(self.year, month));
+            let days: i64 = if self.month < TISHRI {
+                (TISHRI..=months_in_year(self.year)).map(length).sum::<i64>() + (NISAN..self.month).map(length).sum::<i64>()
+            } else {
+                (TISHRI..self.month).map(length).sum()
+            };
+            new_year(self.year) + days + i64::from(self.day) - 1
+        }
+
+        /// Converts a Gregorian date, `None` before the epoch
+        pub fn from_gregorian(date: Date) -> Option<Self> {
+            let julian_day = i64::from(date.to_julian_day());
+            if julian_day < i64::from(EPOCH) {
+                return None;
+            }
+            // The mean year has 35975351 / 98496 days
+            let approximate = ((julian_day - i64::from(EPOCH)) * 98_496).div_euclid(35_975_351) as i32 + 1;
+            let year = (approximate - 1..=approximate + 1).rev().find(|&year| new_year(year) <= julian_day)?;
+            let first_month = if julian_day < (HebrewDate { year, month: NISAN, day: 1 }).julian_day() { TISHRI } else { NISAN };
+            let month = (first_month..=months_in_year(year)).chain(NISAN..TISHRI).find(|&month| {
+                let last = HebrewDate { year, month, day: days_in_month(year, month) };
+                julian_day <= last.julian_day()
+            })?;
+            let day = (julian_day - (HebrewDate { year, month, day: 1 }).julian_day() + 1) as u8;
+            Some(HebrewDate { year, month, day })
+        }
+
+        /// Converts the date to the Gregorian calendar
+        pub fn to_gregorian(&self) -> Result<Date, DayendarError> {
+            i32::try_from(self.julian_day()).ok()
+                .and_then(|julian_day| Date::from_julian_day(julian_day).ok())
+                .ok_or(DayendarError::YearOutOfRange(self.year))
+        }
+    }
+
+    date_accessors!(HebrewDate, "AM");
+
+    /// Gregorian dates from `first` to `last` that fall on the given day of a Hebrew month
+    pub(crate) fn gregorian_dates(month: u8, day: u8, first: Date, last: Date) -> Result<Vec<Date>, DayendarError> {
+        if month == 0 || month > 13 {
+            return Err(DayendarError::InvalidMonth(month));
+        }
+        if day == 0 || day > 30 {
+            return Err(DayendarError::InvalidDay(day));
+        }
+        let year_of = |date: Date| HebrewDate::from_gregorian(date).map(|date| date.year());
+        let from: i32 = year_of(first).unwrap_or(1);
+        let Some(to) = year_of(last) else {
+            return Ok(Vec::new());
+        };
+        Ok((from..=to)
+            .filter_map(|year| HebrewDate::new(year, month, day).ok())
+            .filter_map(|date| date.to_gregorian().ok())
+            .filter(|date| *date >= first && *date <= last)
+            .collect())
+    }
+
+}
+
+/// The `chinese` module derives the Chinese lunisolar calendar from new moons and solar terms.
+///
+/// Months start on the day, in Beijing time, of a new moon. The month holding the winter solstice is
+/// month 11 and, when 13 months run between two months 11, the first month without a major solar term
+/// (zhongqi) is a leap month. Dates are supported for the years 1900 to 2100.
+pub mod chinese {
+
+    use std::ops::RangeInclusive;
+
+    use crate::types::{Date, Year, Month};
+    use crate::astro::{new_moon_at_or_after, new_moon_before, solar_longitude, solar_longitude_after, MEAN_SYNODIC_MONTH};
+    use crate::error::DayendarError;
+
+    /// Gregorian years supported by the calendar
+    pub const SUPPORTED_YEARS: RangeInclusive<i32> = 1900..=2100;
+
+    /// Festivals of the Chinese calendar
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum ChineseFestival {
+        /// Spring Festival, day 1 of month 1
+        NewYear,
+        /// Tomb-Sweeping Day, the solar term at solar longitude 15 degrees
+        Qingming,
+        /// Duanwu, day 5 of month 5
+        DragonBoat,
+        /// Zhongqiu, day 15 of month 8
+        MidAutumn,
+    }
+
+    /// A date of the Chinese calendar
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct ChineseDate {
+        /// Gregorian year in which the Chinese year starts
+        pub year: i32,
+        pub month: u8,
+        /// The month repeats the number of the month before it
+        pub leap: bool,
+        pub day: u8,
+    }
+
+    /// Hours of Beijing time ahead of Universal Time, the local mean time of Beijing before 1929
+    fn zone(date: Date) -> f64 {
+        if date.year() < 1929 { 1397.0 / 180.0 } else { 8.0 }
+    }
+
+    /// Moment of the start of a day in Beijing
+    fn midnight(date: Date) -> f64 {
+        f64::from(date.to_julian_day()) - 0.5 - zone(date) / 24.0
+    }
+
+    /// Day in Beijing of a moment
+    fn day_of(moment: f64) -> Result<Date, DayendarError> {
+        let utc = (moment + 0.5).floor() as i32;
+        let zoned = |hours: f64| (moment + 0.5 + hours / 24.0).floor() as i32;
+        let date = Date::from_julian_day(utc).map_err(|_| DayendarError::YearOutOfRange(0))?;
+        Date::from_julian_day(zoned(zone(date))).map_err(|_| DayendarError::YearOutOfRange(date.year()))
+    }
+
+    fn add_days(date: Date, days: i64) -> Result<Date, DayendarError> {
+        date.checked_add(time::Duration::days(days)).ok_or(DayendarError::YearOutOfRange(date.year()))
+    }
+
+    fn new_moon_on_or_after(date: Date) -> Result<Date, DayendarError> {
+        day_of(new_moon_at_or_after(midnight(date)))
+    }
+
+    fn new_moon_on_or_before(date: Date) -> Result<Date, DayendarError> {
+        day_of(new_moon_before(midnight(add_days(date, 1)?)))
+    }
+
+    /// Number, from 1 to 12, of the last major solar term reached at the start of a day
+    fn major_solar_term(date: Date) -> u8 {
+        ((solar_longitude(midnight(date)) / 30.0).floor() as u8 + 1) % 12 + 1
+    }
+
+    /// Determines if the month starting on `date` has no major solar term
+    fn no_major_solar_term(date: Date) -> Result<bool, DayendarError> {
+        Ok(major_solar_term(date) == major_solar_term(new_moon_on_or_after(add_days(date, 1)?)?))
+    }
+
+    /// Day of the last winter solstice on or before a date
+    fn winter_solstice_on_or_before(date: Date) -> Result<Date, DayendarError> {
+        let mut solstice = solar_longitude_after(270.0, midnight(date) - 370.0);
+        loop {
+            let next = solar_longitude_after(270.0, solstice + 1.0);
+            if day_of(next)? > date {
+                return day_of(solstice);
+            }
+            solstice = next;
+        }
+    }
+
+    /// Number of lunar months between two new moon days
+    fn months_between(from: Date, to: Date) -> i64 {
+        ((to - from).whole_days() as f64 / MEAN_SYNODIC_MONTH).round() as i64
+    }
+
+    /// The year from the month 11 of the solstice before `date` to the next month 11
+    struct Sui {
+        /// Start of the month after month 11
+        month_12: Date,
+        /// Start of the next month 11
+        next_month_11: Date,
+    }
+
+    impl Sui {
+        fn of(date: Date) -> Result<Self, DayendarError> {
+            let solstice = winter_solstice_on_or_before(date)?;
+            let next_solstice = winter_solstice_on_or_before(add_days(solstice, 370)?)?;
+            Ok(Sui {
+                month_12: new_moon_on_or_after(add_days(solstice, 1)?)?,
+                next_month_11: new_moon_on_or_before(next_solstice)?,
+            })
+        }
+
+        fn has_leap_month(&self) -> bool {
+            months_between(self.month_12, self.next_month_11) == 12
+        }
+
+        /// Determines if a leap month starts between month 12 and the month starting on `month`
+        fn leap_month_since(&self, mut month: Date) -> Result<bool, DayendarError> {
+            while month >= self.month_12 {
+                if no_major_solar_term(month)? {
+                    return Ok(true);
+                }
+                month = new_moon_on_or_before(add_days(month, -1)?)?;
+            }
+            Ok(false)
+        }
+
+        /// First day of the year that starts in the sui
+        fn new_year(&self) -> Result<Date, DayendarError> {
+            let month_13 = new_moon_on_or_after(add_days(self.month_12, 1)?)?;
+            if self.has_leap_month() && (no_major_solar_term(self.month_12)? || no_major_solar_term(month_13)?) {
+                new_moon_on_or_after(add_days(month_13, 1)?)
+            } else {
+                Ok(month_13)
+            }
+        }
+    }
+
+    fn check_year(year: i32) -> Result<(), DayendarError> {
+        if SUPPORTED_YEARS.contains(&year) { Ok(()) } else { Err(DayendarError::YearOutOfRange(year)) }
+    }
+
+    /// First day of the Chinese year starting in a Gregorian year
+    pub fn new_year(year: i32) -> Result<Date, DayendarError> {
+        check_year(year)?;
+        // The new year falls between 21 January and 20 February
+        let july = Date::from_calendar_date(year, time::Month::July, 1).map_err(|_| DayendarError::YearOutOfRange(year))?;
+        Sui::of(july)?.new_year()
+    }
+
+    /// Day, in Beijing time, on which the sun reaches a solar longitude during a Gregorian year
+    pub fn solar_term(year: i32, longitude: u16) -> Result<Date, DayendarError> {
+        check_year(year)?;
+        let january = Date::from_calendar_date(year, time::Month::January, 1).map_err(|_| DayendarError::YearOutOfRange(year))?;
+        day_of(solar_longitude_after(f64::from(longitude % 360), midnight(january)))
+    }
+
+    impl ChineseDate {
+        /// Converts a Gregorian date
+        pub fn from_gregorian(date: Date) -> Result<Self, DayendarError> {
+            check_year(date.year())?;
+            let sui = Sui::of(date)?;
+            let month_start = new_moon_on_or_before(date)?;
+            let leap_year = sui.has_leap_month();
+            let after_leap = leap_year && sui.leap_month_since(month_start)?;
+            let month = (months_between(sui.month_12, month_start) - i64::from(after_leap) - 1).rem_euclid(12) + 1;
+            let leap = leap_year && no_major_solar_term(month_start)?
+                && !sui.leap_month_since(new_moon_on_or_before(add_days(month_start, -1)?)?)?;
+            let this_year = new_year(date.year())?;
+            let year = if date >= this_year { date.year() } else { date.year() - 1 };
+            Ok(ChineseDate { year, month: month as u8, leap, day: ((date - month_start).whole_days() + 1) as u8 })
+        }
+    }
+
+    impl ChineseFestival {
+        /// Gregorian date of the festival in a Gregorian year
+        pub fn date(&self, year: i32) -> Result<Date, DayendarError> {
+            let (month, day) = match self {
+                ChineseFestival::NewYear => return new_year(year),
+                ChineseFestival::Qingming => return solar_term(year, 15),
+                ChineseFestival::DragonBoat => (5, 5),
+                ChineseFestival::MidAutumn => (8, 15),
+            };
+            // Walk the months of the year, a leap month repeats the number of the month before it
+            let mut month_start = new_year(year)?;
+            loop {
+                let chinese = ChineseDate::from_gregorian(month_start)?;
+                if chinese.month == month && !chinese.leap {
+                    return add_days(month_start, day - 1);
+                }
+                month_start = new_moon_on_or_after(add_days(month_start, 1)?)?;
+            }
+        }
+
+        /// Gregorian months in which the festival can fall
+        fn months(&self) -> [Month; 2] {
+            match self {
+                ChineseFestival::NewYear => [Month::January, Month::February],
+                ChineseFestival::Qingming => [Month::April, Month::April],
+                ChineseFestival::DragonBoat => [Month::May, Month::June],
+                ChineseFestival::MidAutumn => [Month::September, Month::October],
+            }
+        }
+
+        /// Gregorian date of the festival, if it falls in the given year month, years outside
+        /// [`SUPPORTED_YEARS`] have no festival
+        pub(crate) fn date_in(&self, year: Year, month: Month) -> Result<Option<Date>, DayendarError> {
+            if !self.months().contains(&month) || !SUPPORTED_YEARS.contains(&year.into()) {
+                return Ok(None);
+            }
+            let date = self.date(year.into())?;
+            Ok(Some(date).filter(|date| date.month() as u8 == month.to_index()))
+        }
+    }
+
+}
+
+/// The `persian` module converts dates of the Solar Hijri (Persian) calendar.
+///
+/// The year starts at Nowruz, the day of the March equinox. Months 1 to 6 have 31 days, months 7 to 11
+/// have 30 days and Esfand, month 12, has 29 days or 30 in leap years. Leap years follow the 33 year rule,
+/// years 1, 5, 9, 13, 17, 22, 26 and 30 of each cycle, which matches the astronomical calendar from
+/// 1178 to 1634 AP.
+pub mod persian {
+
+    use std::fmt;
+
+    use crate::types::Date;
+    use crate::error::DayendarError;
+
+    /// Julian day number of 1 Farvardin 1 AP
+    const EPOCH: i32 = 1_948_320;
+
+    /// Leap years of a 33 year cycle
+    const LEAP_YEARS: [i32; 8] = [1, 5, 9, 13, 17, 22, 26, 30];
+
+    /// A date of the Solar Hijri calendar
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct PersianDate {
+        year: i32,
+        month: u8,
+        day: u8,
+    }
+
+    /// Determines whether Esfand has 30 days
+    pub fn is_leap_year(year: i32) -> bool {
+        LEAP_YEARS.contains(&year.rem_euclid(33))
+    }
+
+    /// Number of days of a month, 0 when the month does not exist
+    pub fn days_in_month(year: i32, month: u8) -> u8 {
+        match month {
+            1..=6 => 31,
+            7..=11 => 30,
+            12 if is_leap_year(year) => 30,
+            12 => 29,
+            _ => 0,
+        }
+    }
+
+    /// Julian day number of 1 Farvardin of the year
+    fn new_year(year: i32) -> i32 {
+        let (cycles, rest) = ((year - 1).div_euclid(33), (year - 1).rem_euclid(33));
+        let leap_days = 8 * cycles + LEAP_YEARS.iter().filter(|&&leap| leap <= rest).count() as i32;
+        EPOCH + 365 * (year - 1) + leap_days
+    }
+
+    impl PersianDate {
+        /// Creates a date of the Solar Hijri calendar, the year must be at least 1
+        pub fn new(year: i32, month: u8, day: u8) -> Result<Self, DayendarError> {
+            if month == 0 || month > 12 {
+                return Err(DayendarError::InvalidMonth(month));
+            }
+            if !(1..=9378).contains(&year) || day == 0 || day > days_in_month(year, month) {
+                return Err(DayendarError::InvalidDate { year, month, day });
+            }
+            Ok(PersianDate { year, month, day })
+        }
+
+        /// Converts a Gregorian date, `None` before the epoch
+        pub fn from_gregorian(date: Date) -> Option<Self> {
+            let julian_day = date.to_julian_day();
+            let mut year = date.year() - 621;
+            if julian_day < new_year(year) {
+                year -= 1;
+            }
+            if year < 1 {
+                return None;
+            }
+            let day_of_year = julian_day - new_year(year);
+            let (month, day) = if day_of_year < 186 {
+                (day_of_year / 31 + 1, day_of_year % 31 + 1)
+            } else {
+                ((day_of_year - 186) / 30 + 7, (day_of_year - 186) % 30 + 1)
+            };
+            Some(PersianDate { year, month: month as u8, day: day as u8 })
+        }
+
+        /// Converts the date to the Gregorian calendar
+        pub fn to_gregorian(&self) -> Result<Date, DayendarError> {
+            let month = i32::from(self.month);
+            let days_before = if month <= 7 { 31 * (month - 1) } else { 30 * (month - 1) + 6 };
+            Date::from_julian_day(new_year(self.year) + days_before + i32::from(self.day) - 1)
+                .map_err(|_| DayendarError::YearOutOfRange(self.year))
+        }
+    }
+
+    date_accessors!(PersianDate, "AP");
+
+}
+
+/// The `coptic` module converts dates of the Coptic and Ethiopian calendars.
+///
+/// Both calendars have 12 months of 30 days followed by a short 13th month of 5 days, or 6 days every
+/// fourth year. They only differ in their epochs: the Coptic era starts in 284 and the Ethiopian in 8
+/// of the Julian calendar.
+pub mod coptic {
+
+    use std::fmt;
+
+    use crate::types::Date;
+    use crate::error::DayendarError;
+
+    /// Julian day number of 1 Thout 1 of the Coptic era
+    const COPTIC_EPOCH: i32 = 1_825_030;
+
+    /// Julian day number of 1 Meskerem 1 of the Ethiopian era
+    const ETHIOPIAN_EPOCH: i32 = 1_724_221;
+
+    /// A date of the Coptic calendar
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct CopticDate {
+        year: i32,
+        month: u8,
+        day: u8,
+    }
+
+    /// A date of the Ethiopian calendar
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct EthiopianDate {
+        year: i32,
+        month: u8,
+        day: u8,
+    }
+
+    /// Determines whether the 13th month has 6 days, the same rule holds for both calendars
+    pub fn is_leap_year(year: i32) -> bool {
+        year.rem_euclid(4) == 3
+    }
+
+    /// Number of days of a month, 0 when the month does not exist
+    pub fn days_in_month(year: i32, month: u8) -> u8 {
+        match month {
+            1..=12 => 30,
+            13 if is_leap_year(year) => 6,
+            13 => 5,
+            _ => 0,
+        }
+    }
+
+    fn check(year: i32, month: u8, day: u8) -> Result<(), DayendarError> {
+        if month == 0 || month > 13 {
+            return Err(DayendarError::InvalidMonth(month));
+        }
+        if !(1..=9999).contains(&year) || day == 0 || day > days_in_month(year, month) {
+            return Err(DayendarError::InvalidDate { year, month, day });
+        }
+        Ok(())
+    }
+
+    fn to_julian_day(epoch: i32, year: i32, month: u8, day: u8) -> i32 {
+        epoch - 1 + 365 * (year - 1) + year.div_euclid(4) + 30 * (i32::from(month) - 1) + i32::from(day)
+    }
+
+    fn from_julian_day(epoch: i32, julian_day: i32) -> Option<(i32, u8, u8)> {
+        let year = (4 * (julian_day - epoch) + 1463).div_euclid(1461);
+        if year < 1 {
+            return None;
+        }
+        let month = (julian_day - to_julian_day(epoch, year, 1, 1)) / 30 + 1;
+        let day = julian_day - to_julian_day(epoch, year, month as u8, 1) + 1;
+        Some((year, month as u8, day as u8))
+    }
+
+    fn to_date(julian_day: i32, year: i32) -> Result<Date, DayendarError> {
+        Date::from_julian_day(julian_day).map_err(|_| DayendarError::YearOutOfRange(year))
+    }
+
+    impl CopticDate {
+        /// Creates a date of the Coptic calendar, the year must be at least 1
+        pub fn new(year: i32, month: u8, day: u8) -> Result<Self, DayendarError> {
+            check(year, month, day)?;
+            Ok(CopticDate { year, month, day })
+        }
+
+        /// Converts a Gregorian date, `None` before the epoch
+        pub fn from_gregorian(date: Date) -> Option<Self> {
+            from_julian_day(COPTIC_EPOCH, date.to_julian_day()).map(|(year, month, day)| CopticDate { year, month, day })
+        }
+
+        /// Converts the date to the Gregorian calendar
+        pub fn to_gregorian(&self) -> Result<Date, DayendarError> {
+            to_date(to_julian_day(COPTIC_EPOCH, self.year, self.month, self.day), self.year)
+        }
+    }
+
+    impl EthiopianDate {
+        /// Creates a date of the Ethiopian calendar, the year must be at least 1
+        pub fn new(year: i32, month: u8, day: u8) -> Result<Self, DayendarError> {
+            check(year, month, day)?;
+            Ok(EthiopianDate { year, month, day })
+        }
+
+        /// Converts a Gregorian date, `None` before the epoch
+        pub fn from_gregorian(date: Date) -> Option<Self> {
+            from_julian_day(ETHIOPIAN_EPOCH, date.to_julian_day()).map(|(year, month, day)| EthiopianDate { year, month, day })
+        }
+
+        /// Converts the date to the Gregorian calendar
+        pub fn to_gregorian(&self) -> Result<Date, DayendarError> {
+            to_date(to_julian_day(ETHIOPIAN_EPOCH, self.year, self.month, self.day), self.year)
+        }
+    }
+
+    date_accessors!(CopticDate, "AM");
+
+    date_accessors!(EthiopianDate, "EC");
+
+}
+
+/// The `julian` module converts dates of the proleptic Julian calendar, which the Orthodox churches
+/// use for their fixed feasts, e.g., Ethiopian Christmas is 25 December of the Julian calendar.
+pub mod julian {
+
+    use crate::types::Date;
+    use crate::error::DayendarError;
+
+    /// Determines whether February has 29 days
+    pub fn is_leap_year(year: i32) -> bool {
+        year.rem_euclid(4) == 0
+    }
+
+    /// Number of days of a month, 0 when the month does not exist
+    pub fn days_in_month(year: i32, month: u8) -> u8 {
+        match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if is_leap_year(year) => 29,
+            2 => 28,
+            _ => 0,
+        }
+    }
+
+    /// Converts a date of the Julian calendar to the Gregorian calendar
+    pub fn to_gregorian(year: i32, month: u8, day: u8) -> Result<Date, DayendarError> {
+        if month == 0 || month > 12 {
+            return Err(DayendarError::InvalidMonth(month));
+        }
+        if !(1..=9999).contains(&year) || day == 0 || day > days_in_month(year, month) {
+            return Err(DayendarError::InvalidDate { year, month, day });
+        }
+        let a = (14 - i32::from(month)) / 12;
+        let y = year + 4800 - a;
+        let m = i32::from(month) + 12 * a - 3;
+        let julian_day = i32::from(day) + (153 * m + 2) / 5 + 365 * y + y / 4 - 32083;
+        Date::from_julian_day(julian_day).map_err(|_| DayendarError::YearOutOfRange(year))
+    }
+
+    /// Converts a Gregorian date to a (year, month, day) of the Julian calendar
+    pub fn from_gregorian(date: Date) -> (i32, u8, u8) {
+        let c = date.to_julian_day() + 32082;
+        let d = (4 * c + 3).div_euclid(1461);
+        let e = c - 1461 * d / 4;
+        let m = (5 * e + 2) / 153;
+        let day = e - (153 * m + 2) / 5 + 1;
+        let month = m + 3 - 12 * (m / 10);
+        (d - 4800 + m / 10, month as u8, day as u8)
+    }
+
+}
+
+// =========================================
+// SECTION FOR UNIT TEST CODE ...
+// =========================================
+#[cfg(test)]
+mod tests_hijri {
+
+    use crate::hijri::*;
+    use crate::abstracto::*;
+    use crate::calendar::*;
+    use crate::types::*;
+    use crate::utils::for_each_test_day;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(HijriDate::new(1, 1, 1).unwrap().to_gregorian().unwrap(), date!(0622 - 07 - 19));
+        assert_eq!(HijriDate::new(1445, 9, 1).unwrap().to_gregorian().unwrap(), date!(2024 - 03 - 11));
+        assert_eq!(HijriDate::new(1445, 10, 1).unwrap().to_gregorian().unwrap(), date!(2024 - 04 - 10));
+        assert_eq!(HijriDate::from_gregorian(date!(2024 - 07 - 08)), Some(HijriDate::new(1446, 1, 1).unwrap()));
+        assert_eq!(HijriDate::from_gregorian(date!(0622 - 07 - 18)), None);
+        assert_eq!(HijriDate::new(1446, 1, 1).unwrap().to_string(), "1446-01-01 AH");
+
+        for_each_test_day(|date| assert_eq!(HijriDate::from_gregorian(date).unwrap().to_gregorian().unwrap(), date));
+
+        assert!(is_leap_year(1445) ^ is_leap_year(1446));
+        assert_eq!(days_in_month(1445, 2), 29);
+        assert!(HijriDate::new(1445, 13, 1).is_err());
+        assert!(HijriDate::new(1445, 2, 30).is_err());
+    }
+
+    #[test]
+    fn test_adjustments() {
+        // Eid al-Adha 1445 was announced for 16 June 2024, a day before the tabular date
+        let mut adjustments = HijriAdjustments::new();
+        adjustments.insert(1445, 12, -1).unwrap();
+        let eid = HijriDate::new(1445, 12, 10).unwrap();
+        assert_eq!(eid.to_gregorian().unwrap(), date!(2024 - 06 - 17));
+        assert_eq!(eid.to_gregorian_adjusted(&adjustments).unwrap(), date!(2024 - 06 - 16));
+        assert_eq!(HijriDate::from_gregorian_adjusted(date!(2024 - 06 - 07), &adjustments), HijriDate::new(1445, 12, 1).ok());
+        // Dhu al-Qadah 1445 is one day shorter
+        assert_eq!(HijriDate::from_gregorian_adjusted(date!(2024 - 06 - 06), &adjustments), HijriDate::new(1445, 11, 29).ok());
+        assert!(HijriDate::with_adjustments(1445, 11, 30, &adjustments).is_err());
+        assert_eq!(adjustments.to_string(), "[1445-12: -1]");
+        assert_eq!(adjustments.insert(1445, 12, 4), Err(crate::error::DayendarError::InvalidAdjustment(4)));
+        adjustments.insert(1445, 12, 0).unwrap();
+        assert!(adjustments.is_empty());
+    }
+
+    #[test]
+    fn test_hijri_pattern() {
+        let span = DateSpan::Year(YearSpec::Single(2024));
+        let eid = CalendarPattern::Hijri { month: 10, day: 1, offset: 0, adjustments: HijriAdjustments::new() };
+        assert_eq!(eid.evaluate(&span).unwrap().dates().collect::<Vec<Date>>(), vec![date!(2024 - 04 - 10)]);
+
+        let ramadan = CalendarPattern::Hijri { month: 9, day: 0, offset: 0, adjustments: HijriAdjustments::new() };
+        let days: Vec<Date> = ramadan.evaluate(&span).unwrap().dates().collect();
+        assert_eq!(days.len(), 30);
+        assert_eq!((days[0], days[29]), (date!(2024 - 03 - 11), date!(2024 - 04 - 09)));
+
+        // The eve of the Islamic New Year falls twice in 2008
+        let eve = CalendarPattern::Hijri { month: 1, day: 1, offset: -1, adjustments: HijriAdjustments::new() };
+        let eves: DaysCalendar<BiDay> = eve.evaluate(&DateSpan::Year(YearSpec::Single(2008))).unwrap();
+        assert_eq!(eves.dates().collect::<Vec<Date>>(), vec![date!(2008 - 01 - 09), date!(2008 - 12 - 28)]);
+
+        let invalid = CalendarPattern::Hijri { month: 13, day: 1, offset: 0, adjustments: HijriAdjustments::new() };
+        assert_eq!(invalid.materialize(2024, Month::April), Err(crate::error::DayendarError::InvalidMonth(13)));
+        let invalid = CalendarPattern::Hijri { month: 9, day: 31, offset: 0, adjustments: HijriAdjustments::new() };
+        assert_eq!(invalid.materialize(2024, Month::April), Err(crate::error::DayendarError::InvalidDay(31)));
+    }
+}
+
+#[cfg(test)]
+mod tests_hebrew {
+
+    use crate::hebrew::*;
+    use crate::abstracto::*;
+    use crate::types::*;
+    use crate::utils::for_each_test_day;
+
+    #[test]
+    fn test_years_and_months() {
+        assert!(is_leap_year(5784) && !is_leap_year(5785));
+        assert_eq!(months_in_year(5784), 13);
+        assert_eq!((days_in_year(5784), days_in_year(5785), days_in_year(5786)), (383, 355, 354));
+        // 5785 is a complete year, both Marheshvan and Kislev have 30 days
+        assert_eq!((days_in_month(5785, 8), days_in_month(5785, 9)), (30, 30));
+        // 5784 is a deficient year, both have 29 days
+        assert_eq!((days_in_month(5784, 8), days_in_month(5784, 9)), (29, 29));
+        assert_eq!((days_in_month(5784, 12), days_in_month(5784, 13), days_in_month(5785, 13)), (30, 29, 0));
+        let total: u16 = (1..=13).map(|month| u16::from(days_in_month(5784, month))).sum();
+        assert_eq!(total, 383);
+    }
+
+    #[test]
+    fn test_molad() {
+        // Molad Tishri 5784, Friday 5:49 and 0 parts
+        assert_eq!(molad(5784, TISHRI).unwrap(), Molad { date: date!(2023 - 09 - 15), hour: 5, minute: 49, parts: 0 });
+        // Molad Nisan 5784, Monday 22:57 and 7 parts
+        assert_eq!(molad(5784, NISAN).unwrap(), Molad { date: date!(2024 - 04 - 08), hour: 22, minute: 57, parts: 7 });
+        assert!(molad(5785, 13).is_err());
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(HebrewDate::new(1, 7, 1).unwrap().to_gregorian().unwrap(), date!(-3760 - 09 - 07));
+        assert_eq!(HebrewDate::new(5784, 7, 1).unwrap().to_gregorian().unwrap(), date!(2023 - 09 - 16));
+        assert_eq!(HebrewDate::new(5784, 1, 15).unwrap().to_gregorian().unwrap(), date!(2024 - 04 - 23));
+        assert_eq!(HebrewDate::new(5784, 13, 14).unwrap().to_gregorian().unwrap(), date!(2024 - 03 - 24));
+        assert_eq!(HebrewDate::from_gregorian(date!(2024 - 10 - 12)), HebrewDate::new(5785, 7, 10).ok());
+        assert_eq!(HebrewDate::new(5785, 7, 10).unwrap().to_string(), "5785-07-10 AM");
+        assert!(HebrewDate::new(5785, 13, 1).is_err());
+
+        for_each_test_day(|date| assert_eq!(HebrewDate::from_gregorian(date).unwrap().to_gregorian().unwrap(), date));
+    }
+
+    #[test]
+    fn test_hebrew_pattern() {
+        let span = DateSpan::Year(YearSpec::Range(2024..=2025));
+        let holidays = [(7, 1), (7, 10), (7, 15), (1, 15), (3, 6)]
+            .into_iter()
+            .map(|(month, day)| AbstractCalendar::Pattern(CalendarPattern::Hebrew { month, day }))
+            .reduce(AbstractCalendar::or)
+            .unwrap();
+        let dates: Vec<Date> = holidays.evaluate(&span).unwrap().dates().collect();
+        assert_eq!(dates, vec![
+            date!(2024 - 04 - 23), date!(2024 - 06 - 12), date!(2024 - 10 - 03), date!(2024 - 10 - 12),
+            date!(2024 - 10 - 17), date!(2025 - 04 - 13), date!(2025 - 06 - 02), date!(2025 - 09 - 23),
+            date!(2025 - 10 - 02), date!(2025 - 10 - 07),
+        ]);
+
+        // Adar II only exists in leap years, 5785 has a single Adar
+        let purim = CalendarPattern::Hebrew { month: 13, day: 14 };
+        assert_eq!(purim.evaluate(&span).unwrap().dates().collect::<Vec<Date>>(), vec![date!(2024 - 03 - 24)]);
+        assert!(CalendarPattern::Hebrew { month: 14, day: 1 }.materialize(2024, Month::March).is_err());
+        let invalid = CalendarPattern::Hebrew { month: 7, day: 31 };
+        assert_eq!(invalid.materialize(2024, Month::March), Err(crate::error::DayendarError::InvalidDay(31)));
+        let invalid = CalendarPattern::Hebrew { month: 7, day: 0 };
+        assert_eq!(invalid.materialize(2024, Month::March), Err(crate::error::DayendarError::InvalidDay(0)));
+    }
+}
+
+#[cfg(test)]
+mod tests_chinese {
+
+    use crate::chinese::*;
+    use crate::abstracto::*;
+    use crate::types::*;
+
+    /// Published dates of New Year, Qingming, Dragon Boat and Mid-Autumn
+    const KNOWN_DATES: [(i32, [(u8, u8); 4]); 14] = [
+        (1900, [(1, 31), (4, 5), (6, 1), (9, 8)]),
+        (1912, [(2, 18), (4, 5), (6, 19), (9, 25)]),
+        (1950, [(2, 17), (4, 5), (6, 19), (9, 26)]),
+        (1990, [(1, 27), (4, 5), (5, 28), (10, 3)]),
+        (2000, [(2, 5), (4, 4), (6, 6), (9, 12)]),
+        (2017, [(1, 28), (4, 4), (5, 30), (10, 4)]),
+        (2020, [(1, 25), (4, 4), (6, 25), (10, 1)]),
+        (2023, [(1, 22), (4, 5), (6, 22), (9, 29)]),
+        (2024, [(2, 10), (4, 4), (6, 10), (9, 17)]),
+        (2025, [(1, 29), (4, 4), (5, 31), (10, 6)]),
+        (2033, [(1, 31), (4, 4), (6, 1), (9, 8)]),
+        (2050, [(1, 23), (4, 4), (6, 23), (9, 30)]),
+        (2061, [(1, 21), (4, 4), (6, 22), (9, 28)]),
+        (2100, [(2, 9), (4, 5), (6, 12), (9, 18)]),
+    ];
+
+    #[test]
+    fn test_known_festival_dates() {
+        let festivals = [ChineseFestival::NewYear, ChineseFestival::Qingming, ChineseFestival::DragonBoat, ChineseFestival::MidAutumn];
+        for (year, dates) in KNOWN_DATES {
+            for (festival, (month, day)) in festivals.iter().zip(dates) {
+                let expected = Date::from_calendar_date(year, TimeMonth::try_from(month).unwrap(), day).unwrap();
+                assert_eq!(festival.date(year).unwrap(), expected, "{:?} {}", festival, year);
+            }
+        }
+        assert!(new_year(1899).is_err());
+        assert!(ChineseFestival::MidAutumn.date(2101).is_err());
+    }
+
+    #[test]
+    fn test_chinese_dates() {
+        assert_eq!(ChineseDate::from_gregorian(date!(2024 - 02 - 10)).unwrap(), ChineseDate { year: 2024, month: 1, leap: false, day: 1 });
+        assert_eq!(ChineseDate::from_gregorian(date!(2024 - 02 - 09)).unwrap(), ChineseDate { year: 2023, month: 12, leap: false, day: 30 });
+        // 2023 has a leap second month, from 22 March to 19 April
+        assert_eq!(ChineseDate::from_gregorian(date!(2023 - 03 - 22)).unwrap(), ChineseDate { year: 2023, month: 2, leap: true, day: 1 });
+        assert_eq!(ChineseDate::from_gregorian(date!(2023 - 04 - 20)).unwrap(), ChineseDate { year: 2023, month: 3, leap: false, day: 1 });
+        assert_eq!(solar_term(2024, 270).unwrap(), date!(2024 - 12 - 21));
+    }
+
+    #[test]
+    fn test_festival_pattern() {
+        let span = DateSpan::Year(YearSpec::Single(2024));
+        let festivals = AbstractCalendar::Pattern(CalendarPattern::ChineseFestival(ChineseFestival::NewYear))
+            .or(AbstractCalendar::Pattern(CalendarPattern::ChineseFestival(ChineseFestival::MidAutumn)));
+        let dates: Vec<Date> = festivals.evaluate(&span).unwrap().dates().collect();
+        assert_eq!(dates, vec![date!(2024 - 02 - 10), date!(2024 - 09 - 17)]);
+        let out_of_range = CalendarPattern::ChineseFestival(ChineseFestival::Qingming).materialize(2101, Month::April);
+        assert_eq!(out_of_range.unwrap(), vec![BiDay::Zero; 30]);
+
+        // Spans touching unsupported years keep the other operands
+        let odd_days = AbstractCalendar::Pattern(CalendarPattern::OddDays);
+        let span = DateSpan::Year(YearSpec::Range(2100..=2101));
+        let calendar = AbstractCalendar::Pattern(CalendarPattern::ChineseFestival(ChineseFestival::NewYear)).or(odd_days.clone());
+        let evaluated = calendar.evaluate(&span).unwrap();
+        assert!(evaluated.dates().any(|date| date == date!(2100 - 02 - 09)));
+        let odd_2101 = odd_days.evaluate(&DateSpan::Year(YearSpec::Single(2101))).unwrap();
+        assert_eq!(evaluated.dates().filter(|date| date.year() == 2101).count(), odd_2101.dates().count());
+        let span = DateSpan::Year(YearSpec::Range(1899..=1900));
+        assert!(calendar.evaluate(&span).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod tests_persian {
+
+    use crate::persian::*;
+    use crate::abstracto::*;
+    use crate::astro;
+    use crate::types::*;
+    use crate::utils::for_each_test_day;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(PersianDate::new(1403, 1, 1).unwrap().to_gregorian().unwrap(), date!(2024 - 03 - 20));
+        assert_eq!(PersianDate::new(1404, 1, 1).unwrap().to_gregorian().unwrap(), date!(2025 - 03 - 21));
+        assert_eq!(PersianDate::new(1300, 1, 1).unwrap().to_gregorian().unwrap(), date!(1921 - 03 - 21));
+        assert_eq!(PersianDate::new(1403, 12, 30).unwrap().to_gregorian().unwrap(), date!(2025 - 03 - 20));
+        assert_eq!(PersianDate::from_gregorian(date!(2024 - 09 - 22)), PersianDate::new(1403, 7, 1).ok());
+        assert_eq!(PersianDate::new(1403, 7, 1).unwrap().to_string(), "1403-07-01 AP");
+        assert!(is_leap_year(1403) && !is_leap_year(1404));
+        assert!(PersianDate::new(1404, 12, 30).is_err());
+        assert!(PersianDate::new(1404, 13, 1).is_err());
+
+        for_each_test_day(|date| assert_eq!(PersianDate::from_gregorian(date).unwrap().to_gregorian().unwrap(), date));
+    }
+
+    #[test]
+    fn test_astronomical_new_year() {
+        // Nowruz is the day of the March equinox when it happens before noon in Tehran, else the next day
+        for year in 1350..=1450 {
+            let new_year: Date = PersianDate::new(year, 1, 1).unwrap().to_gregorian().unwrap();
+            let equinox: f64 = astro::solar_longitude_after(0.0, f64::from(new_year.to_julian_day() - 10));
+            let nowruz = Date::from_julian_day((equinox + 3.5 / 24.0).floor() as i32 + 1).unwrap();
+            assert_eq!(new_year, nowruz, "year {}", year);
+        }
+    }
+
+    #[test]
+    fn test_nowruz_pattern_and_span() {
+        let nowruz = AbstractCalendar::Pattern(CalendarPattern::ForeignDate { system: CalendarSystem::Persian, month: 1, day: 1 });
+        let dates: Vec<Date> = nowruz.evaluate(&DateSpan::Year(YearSpec::Range(2023..=2026))).unwrap().dates().collect();
+        assert_eq!(dates, vec![date!(2023 - 03 - 21), date!(2024 - 03 - 20), date!(2025 - 03 - 21), date!(2026 - 03 - 21)]);
+
+        let farvardin = DateSpan::foreign_month(CalendarSystem::Persian, 1403, 1).unwrap();
+        assert_eq!(farvardin, DateSpan::Date(DateSpec::Range(date!(2024 - 03 - 20), date!(2024 - 04 - 19))));
+        assert_eq!(farvardin.to_year_month(), [(2024, Month::March), (2024, Month::April)].into_iter().collect());
+        let month = AbstractCalendar::Pattern(CalendarPattern::ForeignDate { system: CalendarSystem::Persian, month: 1, day: 0 });
+        let dates: Vec<Date> = month.evaluate(&farvardin).unwrap().dates().collect();
+        assert_eq!(dates.len(), 31);
+        assert_eq!((dates[0], dates[30]), (date!(2024 - 03 - 20), date!(2024 - 04 - 19)));
+        assert!(DateSpan::foreign_month(CalendarSystem::Persian, 1403, 13).is_err());
+        let invalid = CalendarPattern::ForeignDate { system: CalendarSystem::Persian, month: 1, day: 32 };
+        assert_eq!(invalid.materialize(2024, Month::March), Err(crate::error::DayendarError::InvalidDay(32)));
+    }
+}
+
+#[cfg(test)]
+mod tests_coptic {
+
+    use crate::coptic::*;
+    use crate::julian;
+    use crate::abstracto::*;
+    use crate::types::*;
+    use crate::utils::for_each_test_day;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(EthiopianDate::new(2017, 1, 1).unwrap().to_gregorian().unwrap(), date!(2024 - 09 - 11));
+        assert_eq!(EthiopianDate::new(2016, 1, 1).unwrap().to_gregorian().unwrap(), date!(2023 - 09 - 12));
+        assert_eq!(EthiopianDate::new(2016, 4, 29).unwrap().to_gregorian().unwrap(), date!(2024 - 01 - 08));
+        assert_eq!(EthiopianDate::new(2015, 13, 6).unwrap().to_gregorian().unwrap(), date!(2023 - 09 - 11));
+        assert_eq!(CopticDate::new(1741, 1, 1).unwrap().to_gregorian().unwrap(), date!(2024 - 09 - 11));
+        assert_eq!(CopticDate::from_gregorian(date!(2025 - 01 - 07)), CopticDate::new(1741, 4, 29).ok());
+        assert_eq!(EthiopianDate::new(2017, 1, 1).unwrap().to_string(), "2017-01-01 EC");
+        assert_eq!(CopticDate::new(1741, 1, 1).unwrap().to_string(), "1741-01-01 AM");
+        assert!(EthiopianDate::new(2016, 13, 6).is_err());
+        assert!(CopticDate::new(1741, 14, 1).is_err());
+
+        for_each_test_day(|date| {
+            assert_eq!(EthiopianDate::from_gregorian(date).unwrap().to_gregorian().unwrap(), date);
+            assert_eq!(CopticDate::from_gregorian(date).unwrap().to_gregorian().unwrap(), date);
+            let (year, month, day) = julian::from_gregorian(date);
+            assert_eq!(julian::to_gregorian(year, month, day).unwrap(), date);
+        });
+    }
+
+    #[test]
+    fn test_julian_calendar() {
+        assert_eq!(julian::to_gregorian(2024, 12, 25).unwrap(), date!(2025 - 01 - 07));
+        assert_eq!(julian::to_gregorian(1582, 10, 5).unwrap(), date!(1582 - 10 - 15));
+        assert_eq!(julian::from_gregorian(date!(2100 - 03 - 14)), (2100, 2, 29));
+        assert!(julian::to_gregorian(2023, 2, 29).is_err());
+    }
+
+    #[test]
+    fn test_ethiopian_holidays() {
+        let span = DateSpan::Year(YearSpec::Range(2023..=2025));
+        let enkutatash = AbstractCalendar::Pattern(CalendarPattern::ForeignDate { system: CalendarSystem::Ethiopian, month: 1, day: 1 });
+        let dates: Vec<Date> = enkutatash.evaluate(&span).unwrap().dates().collect();
+        assert_eq!(dates, vec![date!(2023 - 09 - 12), date!(2024 - 09 - 11), date!(2025 - 09 - 11)]);
+
+        // Genna falls on 29 Tahsas, or 28 Tahsas after a leap year, which is always 25 December Julian
+        let genna = AbstractCalendar::Pattern(CalendarPattern::ForeignDate { system: CalendarSystem::Julian, month: 12, day: 25 });
+        let dates: Vec<Date> = genna.evaluate(&span).unwrap().dates().collect();
+        assert_eq!(dates, vec![date!(2023 - 01 - 07), date!(2024 - 01 - 07), date!(2025 - 01 - 07)]);
+
+        let pagume = DateSpan::foreign_month(CalendarSystem::Ethiopian, 2015, 13).unwrap();
+        assert_eq!(pagume, DateSpan::Date(DateSpec::Range(date!(2023 - 09 - 06), date!(2023 - 09 - 11))));
+    }
+}
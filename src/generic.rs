@@ -9,6 +9,8 @@ pub mod types {
     use core::ops::RangeInclusive;
     use std::collections::HashSet;
     use std::str::FromStr;
+    use crate::error::DayendarError;
+    use crate::fiscal::{FiscalCalendar, FiscalPeriod};
  
 
     /// Represents the year
@@ -23,6 +25,7 @@ pub mod types {
 
     /// Enumerator `Month` representing the months of the year
     #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     #[repr(u8)]
     pub enum Month {
         January = 1,
@@ -65,8 +68,8 @@ pub mod types {
         }
 
         /// Converts `Month` type to `TimeMonth` type
-        pub fn to_time_month(&self) -> Result<TimeMonth, &'static str> {
-            TimeMonth::try_from(self.to_index()).map_err(|_| "Invalid month index")
+        pub fn to_time_month(&self) -> Result<TimeMonth, DayendarError> {
+            TimeMonth::try_from(self.to_index()).map_err(|_| DayendarError::InvalidMonth(self.to_index()))
         }
 
         pub fn next(&self) -> Option<Self> {
@@ -79,7 +82,7 @@ pub mod types {
         pub fn prev(&self) -> Option<Self> {
             match self {
                 Month::January => None,
-                _ => Some(unsafe { std::mem::transmute((*self as u8) - 1) }),
+                _ => Month::from_index(self.to_index() - 1),
             }
         }
     
@@ -122,7 +125,7 @@ pub mod types {
     }
 
     impl FromStr for Month {
-        type Err = String;
+        type Err = DayendarError;
 
         /// Converts a string representation of a month into a `Month`.
         /// 
@@ -132,7 +135,8 @@ pub mod types {
         /// 
         /// # Returns
         /// 
-        /// * `Option<Month>` - Returns `Some(Month)` if the string is a valid representation, otherwise returns `None`.
+        /// * `Result<Month, DayendarError>` - Returns `Ok(Month)` if the string is a valid representation,
+        ///   otherwise returns a `ParseError`.
         fn from_str(s: &str) -> Result<Self, Self::Err> {
             match s.to_lowercase().as_str() {
                 "jan" | "january" | "1" | "01" => Ok(Month::January),
@@ -147,13 +151,13 @@ pub mod types {
                 "oct" | "october" | "10" => Ok(Month::October),
                 "nov" | "november" | "11" => Ok(Month::November),
                 "dec" | "december" | "12" => Ok(Month::December),
-                _ => Err(format!("Invalid month representation: {}", s)),
+                _ => Err(DayendarError::parse_error(s, 0, format!("invalid month `{}`", s))),
             }
         }
     }
 
     /// Enumerator representing the binary value of the day
-    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
     pub enum BiDay {
         /// The value `Zero` means that the item does not included in the calendar
         Zero,
@@ -180,19 +184,108 @@ pub mod types {
         }
     }
 
+    /// Method used to compute the date of Easter Sunday
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum Computus {
+        /// Gregorian computus of the Western churches
+        Western,
+        /// Julian computus of the Orthodox churches, given as a Gregorian date
+        Orthodox,
+    }
+
+    /// Solar calendar systems with a fixed arithmetic, used by `CalendarPattern::ForeignDate`
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum CalendarSystem {
+        /// Solar Hijri calendar of Iran and Afghanistan, e.g., Nowruz is month 1 and day 1
+        Persian,
+        /// Ethiopian calendar, e.g., Enkutatash is month 1 and day 1
+        Ethiopian,
+        /// Coptic calendar of the Coptic Orthodox Church
+        Coptic,
+        /// Proleptic Julian calendar, e.g., Ethiopian Christmas is month 12 and day 25
+        Julian,
+    }
+
+    impl CalendarSystem {
+        /// Number of months of a year
+        pub fn months_in_year(&self) -> u8 {
+            match self {
+                CalendarSystem::Persian | CalendarSystem::Julian => 12,
+                CalendarSystem::Ethiopian | CalendarSystem::Coptic => 13,
+            }
+        }
+
+        /// Number of days of a month, 0 when the month does not exist
+        pub fn days_in_month(&self, year: i32, month: u8) -> u8 {
+            match self {
+                CalendarSystem::Persian => crate::persian::days_in_month(year, month),
+                CalendarSystem::Ethiopian | CalendarSystem::Coptic => crate::coptic::days_in_month(year, month),
+                CalendarSystem::Julian => crate::julian::days_in_month(year, month),
+            }
+        }
+
+        /// Converts a date of the system to the Gregorian calendar
+        pub fn to_gregorian(&self, year: i32, month: u8, day: u8) -> Result<Date, DayendarError> {
+            match self {
+                CalendarSystem::Persian => crate::persian::PersianDate::new(year, month, day)?.to_gregorian(),
+                CalendarSystem::Ethiopian => crate::coptic::EthiopianDate::new(year, month, day)?.to_gregorian(),
+                CalendarSystem::Coptic => crate::coptic::CopticDate::new(year, month, day)?.to_gregorian(),
+                CalendarSystem::Julian => crate::julian::to_gregorian(year, month, day),
+            }
+        }
+
+        /// Converts a Gregorian date to a (year, month, day) of the system, `None` before its epoch
+        pub fn from_gregorian(&self, date: Date) -> Option<(i32, u8, u8)> {
+            match self {
+                CalendarSystem::Persian => crate::persian::PersianDate::from_gregorian(date)
+                    .map(|date| (date.year(), date.month(), date.day())),
+                CalendarSystem::Ethiopian => crate::coptic::EthiopianDate::from_gregorian(date)
+                    .map(|date| (date.year(), date.month(), date.day())),
+                CalendarSystem::Coptic => crate::coptic::CopticDate::from_gregorian(date)
+                    .map(|date| (date.year(), date.month(), date.day())),
+                CalendarSystem::Julian => Some(crate::julian::from_gregorian(date)),
+            }
+        }
+
+        /// Gregorian dates between `first` and `last` falling on a day of a month of the system, `day` 0
+        /// selects every day of the month and years in which the day does not exist are skipped
+        pub(crate) fn gregorian_dates(&self, month: u8, day: u8, first: Date, last: Date) -> Result<Vec<Date>, DayendarError> {
+            if month == 0 || month > self.months_in_year() {
+                return Err(DayendarError::InvalidMonth(month));
+            }
+            if day > 31 {
+                return Err(DayendarError::InvalidDay(day));
+            }
+            let from: i32 = self.from_gregorian(first).map_or(1, |(year, _, _)| year);
+            let Some((to, _, _)) = self.from_gregorian(last) else {
+                return Ok(Vec::new());
+            };
+            let mut dates: Vec<Date> = Vec::new();
+            for year in from..=to {
+                let days = if day == 0 { 1..=self.days_in_month(year, month) } else { day..=day };
+                dates.extend(days
+                    .filter_map(|day| self.to_gregorian(year, month, day).ok())
+                    .filter(|date| *date >= first && *date <= last));
+            }
+            Ok(dates)
+        }
+    }
+
     #[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
     pub(crate) struct YearMonth {
         year: Year,
         month: Month,
     }
 
+    #[allow(dead_code)]
     impl YearMonth {
-        #[allow(dead_code)]
-        pub fn new(year: Year, month: Month) -> Result<Self, String> {
+        pub fn new(year: Year, month: Month) -> Result<Self, DayendarError> {
             if month as u8 >= 1 && month as u8 <= 12 {
                 Ok(YearMonth { year, month })
             } else {
-                Err("Month should be between 1 and 12".to_string())
+                Err(DayendarError::InvalidMonth(month as u8))
             }
         }
 
@@ -311,7 +404,19 @@ pub mod types {
         }
     }
 
+    /// Parses the trimmed `item` found at byte `offset` of `input`, reporting its position on error
+    fn parse_item<T, E>(input: &str, offset: usize, item: &str, what: &str, parse: impl Fn(&str) -> Result<T, E>)
+        -> Result<T, DayendarError>
+    {
+        let trimmed = item.trim();
+        parse(trimmed).map_err(|_| {
+            let position = offset + item.len() - item.trim_start().len();
+            DayendarError::parse_error(input, position, format!("invalid {} `{}`", what, trimmed))
+        })
+    }
+
     #[derive(Debug, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     pub enum YearSpec {
         Single(Year),
         Range(RangeInclusive<Year>),
@@ -344,22 +449,27 @@ pub mod types {
         /// set.insert(2027);
         /// assert_eq!(y, YearSpec::List(set));
         /// ```
-        pub fn parse(input: &str) -> Option<Self> {
+        pub fn parse(input: &str) -> Result<Self, DayendarError> {
             // Single year
             if let Ok(year) = input.parse::<Year>() {
-                return Some(YearSpec::Single(year));
+                return Ok(YearSpec::Single(year));
             }
     
             // Year range
             if let Some(idx) = input.find('-') {
-                let start: u16 = input[..idx].trim().parse::<Year>().ok()?;
-                let end: u16 = input[idx+1..].trim().parse::<Year>().ok()?;
-                return Some(YearSpec::Range(start..=end));
+                let start: u16 = parse_item(input, 0, &input[..idx], "year", str::parse::<Year>)?;
+                let end: u16 = parse_item(input, idx + 1, &input[idx+1..], "year", str::parse::<Year>)?;
+                return Ok(YearSpec::Range(start..=end));
             }
     
             // Year list
-            let years: Result<HashSet<Year>, _> = input.split(',').map(str::trim).map(str::parse).collect();
-            years.ok().map(YearSpec::List)
+            let mut years: HashSet<Year> = HashSet::new();
+            let mut offset: usize = 0;
+            for item in input.split(',') {
+                years.insert(parse_item(input, offset, item, "year", str::parse::<Year>)?);
+                offset += item.len() + 1;
+            }
+            Ok(YearSpec::List(years))
         }
     
         /// Determines if the given string can be parsed into a `YearSpec`.
@@ -368,7 +478,16 @@ pub mod types {
         /// 
         /// * `input` - A string slice that might represent a `YearSpec`.
         pub fn is_valid(input: &str) -> bool {
-            Self::parse(input).is_some()
+            Self::parse(input).is_ok()
+        }
+
+        /// Determines whether a year is covered by the spec
+        pub fn contains(&self, year: Year) -> bool {
+            match self {
+                YearSpec::Single(single) => *single == year,
+                YearSpec::Range(range) => range.contains(&year),
+                YearSpec::List(years) => years.contains(&year),
+            }
         }
 
         pub fn to_year_month(&self) -> HashSet<(Year, Month)> {
@@ -438,30 +557,47 @@ pub mod types {
             result
         }
 
-        pub fn range_to(&self, end: &Self) -> Self {
+        /// Checks that the years are supported, the range is in order and the list is not empty
+        pub fn validate(&self) -> Result<(), DayendarError> {
+            let supported = |year: &Year| match year {
+                1..=9999 => Ok(()),
+                _ => Err(DayendarError::YearOutOfRange((*year).into())),
+            };
             match self {
-                YearSpec::Single(year) => YearSpec::Range(*year..=match end {
-                    YearSpec::Single(end_year) => *end_year,
-                    YearSpec::Range(range) => *range.end(),
-                    YearSpec::List(set) => *set.iter().max().unwrap(),
-                }),
-                YearSpec::Range(range) => YearSpec::Range(*range.start()..=match end {
-                    YearSpec::Single(end_year) => *end_year,
-                    YearSpec::Range(end_range) => *end_range.end(),
-                    YearSpec::List(set) => *set.iter().max().unwrap(),
-                }),
-                YearSpec::List(set) => {
-                    let min_year = *set.iter().min().unwrap();
-                    let end_year = match end {
-                        YearSpec::Single(year) => *year,
-                        YearSpec::Range(range) => *range.end(),
-                        YearSpec::List(end_set) => *end_set.iter().max().unwrap(),
-                    };
-                    let new_set: HashSet<_> = (min_year..=end_year).collect();
-                    YearSpec::List(new_set)
-                }
+                YearSpec::Single(year) => supported(year),
+                YearSpec::Range(range) if range.start() > range.end() => Err(DayendarError::InvalidSpec("reversed year range")),
+                YearSpec::Range(range) => supported(range.start()).and(supported(range.end())),
+                YearSpec::List(set) if set.is_empty() => Err(DayendarError::InvalidSpec("empty year list")),
+                YearSpec::List(set) => set.iter().try_for_each(supported),
+            }
+        }
+
+        /// Earliest year of the spec, failing for an empty list
+        fn first(&self) -> Result<Year, DayendarError> {
+            match self {
+                YearSpec::Single(year) => Ok(*year),
+                YearSpec::Range(range) => Ok(*range.start()),
+                YearSpec::List(set) => set.iter().min().copied().ok_or(DayendarError::InvalidSpec("empty year list")),
+            }
+        }
+
+        /// Latest year of the spec, failing for an empty list
+        fn last(&self) -> Result<Year, DayendarError> {
+            match self {
+                YearSpec::Single(year) => Ok(*year),
+                YearSpec::Range(range) => Ok(*range.end()),
+                YearSpec::List(set) => set.iter().max().copied().ok_or(DayendarError::InvalidSpec("empty year list")),
             }
         }
+
+        /// Spans from the first year of the spec to the last year of `end`, a list stays a list
+        pub fn range_to(&self, end: &Self) -> Result<Self, DayendarError> {
+            let (first, last) = (self.first()?, end.last()?);
+            Ok(match self {
+                YearSpec::List(_) => YearSpec::List((first..=last).collect()),
+                _ => YearSpec::Range(first..=last),
+            })
+        }
         
     }
 
@@ -520,6 +656,7 @@ pub mod types {
     impl Eq for YearSpec {}
     
     #[derive(Debug, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     pub enum MonthSpec {
         Single(Month),
         Range(RangeInclusive<Month>),
@@ -601,6 +738,7 @@ pub mod types {
 
 
     #[derive(Debug, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     pub struct YearMonthSpec(pub HashSet<(YearSpec, MonthSpec)>);
 
     impl PartialEq for YearMonthSpec {
@@ -612,6 +750,7 @@ pub mod types {
     impl Eq for YearMonthSpec {}
 
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     pub enum DateSpec {
         Single(Date),
         Range(Date, Date),
@@ -633,37 +772,43 @@ pub mod types {
         /// # Arguments
         /// 
         /// * `input` - A string slice that should represent a `MonthSpec`.
-        pub fn parse(input: &str) -> Result<MonthSpec, &'static str> {
+        pub fn parse(input: &str) -> Result<MonthSpec, DayendarError> {
             match SpecType::from_str(input) {
                 Ok(SpecType::Single) => {
-                    let month: Month = Month::from_str(input).map_err(|_| "Invalid month format")?;
+                    let month: Month = parse_item(input, 0, input, "month", Month::from_str)?;
                     Ok(MonthSpec::Single(month))
                 },
                 Ok(SpecType::Range) => {
                     let parts: Vec<&str> = input.split('-').collect();
                     if parts.len() != 2 {
-                        return Err("Invalid range format");
+                        return Err(DayendarError::parse_error(input, 0, "invalid month range format"));
                     }
-                    let start: Month = Month::from_str(parts[0]).map_err(|_| "Invalid month format")?;
-                    let end = Month::from_str(parts[1]).map_err(|_| "Invalid end month format")?;
+                    let start: Month = parse_item(input, 0, parts[0], "month", Month::from_str)?;
+                    let end: Month = parse_item(input, parts[0].len() + 1, parts[1], "month", Month::from_str)?;
                     Ok(MonthSpec::Range(start..=end))
                 },
                 Ok(SpecType::List) => {
-                    let months: HashSet<Month> = input.split(',')
-                        .map(|s: &str| s.trim())
-                        .filter_map(|input| Month::from_str(input).ok())
-                        .collect::<HashSet<Month>>();
-                    if months.is_empty() {
-                        return Err("Invalid list format");
+                    let mut months: HashSet<Month> = HashSet::new();
+                    let mut offset: usize = 0;
+                    for item in input.split(',') {
+                        months.insert(parse_item(input, offset, item, "month", Month::from_str)?);
+                        offset += item.len() + 1;
                     }
                     Ok(MonthSpec::List(months))
                 },
-                Ok(SpecType::Invalid) => Err("Invalid MonthSpec format"),
-                _ => Err("Invalid MonthSpec format"),
+                _ => Err(DayendarError::parse_error(input, 0, "invalid month spec format")),
+            }
+        }
+
+        /// Determines whether a month is covered by the spec
+        pub fn contains(&self, month: Month) -> bool {
+            match self {
+                MonthSpec::Single(single) => *single == month,
+                MonthSpec::Range(range) => range.contains(&month),
+                MonthSpec::List(months) => months.contains(&month),
             }
         }
 
-        
         pub fn to_year_month(&self, year: Year) -> HashSet<(Year, Month)> {
             match self {
                 MonthSpec::Single(month) => vec![(year, *month)].into_iter().collect(),
@@ -725,37 +870,42 @@ pub mod types {
             result
         }
 
-        pub fn range_to(&self, end: &Self) -> Self {
+        /// Checks that the range is in order and the list is not empty
+        pub fn validate(&self) -> Result<(), DayendarError> {
             match self {
-                MonthSpec::Single(month) => {
-                    let end_month: &Month = match end {
-                        MonthSpec::Single(end_month) => end_month,
-                        MonthSpec::Range(range) => range.end(),
-                        MonthSpec::List(set) => set.iter().max().unwrap(),
-                    };
-                    MonthSpec::Range(*month..=*end_month)
-                }
-                MonthSpec::Range(range) => {
-                    let end_month: &Month = match end {
-                        MonthSpec::Single(end_month) => end_month,
-                        MonthSpec::Range(end_range) => end_range.end(),
-                        MonthSpec::List(set) => set.iter().max().unwrap(),
-                    };
-                    MonthSpec::Range(*range.start()..=*end_month)
-                }
-                MonthSpec::List(set) => {
-                    let start_month = set.iter().min().unwrap();
-                    let end_month: &Month = match end {
-                        MonthSpec::Single(month) => month,
-                        MonthSpec::Range(range) => range.end(),
-                        MonthSpec::List(end_set) => end_set.iter().max().unwrap(),
-                    };
-                    let months_range: Vec<Month> = start_month.range_to(end_month);
-                    MonthSpec::List(months_range.into_iter().collect())
-                }
+                MonthSpec::Range(range) if range.start() > range.end() => Err(DayendarError::InvalidSpec("reversed month range")),
+                MonthSpec::List(set) if set.is_empty() => Err(DayendarError::InvalidSpec("empty month list")),
+                _ => Ok(()),
+            }
+        }
+
+        /// Earliest month of the spec, failing for an empty list
+        fn first(&self) -> Result<Month, DayendarError> {
+            match self {
+                MonthSpec::Single(month) => Ok(*month),
+                MonthSpec::Range(range) => Ok(*range.start()),
+                MonthSpec::List(set) => set.iter().min().copied().ok_or(DayendarError::InvalidSpec("empty month list")),
+            }
+        }
+
+        /// Latest month of the spec, failing for an empty list
+        fn last(&self) -> Result<Month, DayendarError> {
+            match self {
+                MonthSpec::Single(month) => Ok(*month),
+                MonthSpec::Range(range) => Ok(*range.end()),
+                MonthSpec::List(set) => set.iter().max().copied().ok_or(DayendarError::InvalidSpec("empty month list")),
             }
         }
 
+        /// Spans from the first month of the spec to the last month of `end`, a list stays a list
+        pub fn range_to(&self, end: &Self) -> Result<Self, DayendarError> {
+            let (first, last) = (self.first()?, end.last()?);
+            Ok(match self {
+                MonthSpec::List(_) => MonthSpec::List(first.range_to(&last).into_iter().collect()),
+                _ => MonthSpec::Range(first..=last),
+            })
+        }
+
     }
     
 
@@ -770,15 +920,7 @@ pub mod types {
         /// 
         /// * `bool` - Returns `true` if the string is a valid representation, otherwise returns `false`.
         pub fn is_valid(input: &str) -> bool {
-            let parts: Vec<&str> = input.split('-').collect();
-            if parts.len() != 2 {
-                return false;
-            }
-
-            let year = parts[0].parse::<Year>();
-            let month = Month::from_str(parts[1]);
-
-            year.is_ok() && month.is_ok()
+            Self::parse(input).is_ok()
         }
 
         /// Tries to parse the given string into a `YearMonthSpec`.
@@ -789,16 +931,16 @@ pub mod types {
         /// 
         /// # Returns
         /// 
-        /// * `Result<YearMonthSpec, &'static str>` - Returns `Ok(YearMonthSpec)` if the string is a valid representation, 
-        ///   otherwise returns an `Err` with a description of the error.
-        pub fn parse(input: &str) -> Result<Self, &'static str> {
-            if !Self::is_valid(input) {
-                return Err("Invalid YearMonthSpec format");
+        /// * `Result<YearMonthSpec, DayendarError>` - Returns `Ok(YearMonthSpec)` if the string is a valid representation, 
+        ///   otherwise returns a `ParseError` with a description of the error.
+        pub fn parse(input: &str) -> Result<Self, DayendarError> {
+            let parts: Vec<&str> = input.split('-').collect();
+            if parts.len() != 2 {
+                return Err(DayendarError::parse_error(input, 0, "expected a year month as YYYY-Month"));
             }
 
-            let parts: Vec<&str> = input.split('-').collect();
-            let year = parts[0].parse::<Year>().expect("Already validated");
-            let month = Month::from_str(parts[1]).expect("Already validated");
+            let year: Year = parse_item(input, 0, parts[0], "year", str::parse::<Year>)?;
+            let month: Month = parse_item(input, parts[0].len() + 1, parts[1], "month", Month::from_str)?;
 
             let mut set = HashSet::new();
             set.insert((YearSpec::Single(year), MonthSpec::Single(month)));
@@ -806,6 +948,14 @@ pub mod types {
             Ok(YearMonthSpec(set))
         }
 
+        /// Checks that the set is not empty and every pair of specs is valid
+        pub fn validate(&self) -> Result<(), DayendarError> {
+            if self.0.is_empty() {
+                return Err(DayendarError::InvalidSpec("empty year month set"));
+            }
+            self.0.iter().try_for_each(|(year_spec, month_spec)| year_spec.validate().and(month_spec.validate()))
+        }
+
         pub fn to_year_month(&self) -> HashSet<(Year, Month)> {
             let mut result = HashSet::new();
             for (year_spec, month_spec) in &self.0 {
@@ -819,7 +969,23 @@ pub mod types {
     }
 
     impl DateSpec {
-        
+
+        /// Checks that the dates are supported, the range is in order and the list is not empty
+        pub fn validate(&self) -> Result<(), DayendarError> {
+            let supported = |date: &Date| if (MIN_DATE..=MAX_DATE).contains(date) {
+                Ok(())
+            } else {
+                Err(DayendarError::YearOutOfRange(date.year()))
+            };
+            match self {
+                DateSpec::Single(date) => supported(date),
+                DateSpec::Range(start, end) if start > end => Err(DayendarError::InvalidSpec("reversed date range")),
+                DateSpec::Range(start, end) => supported(start).and(supported(end)),
+                DateSpec::List(dates) if dates.is_empty() => Err(DayendarError::InvalidSpec("empty date list")),
+                DateSpec::List(dates) => dates.iter().try_for_each(supported),
+            }
+        }
+
         pub fn to_year_month(&self) -> HashSet<(Year, Month)> {
             match self {
                 DateSpec::Single(date) => {
@@ -857,21 +1023,50 @@ pub mod types {
     }
 
     #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     pub enum DateSpan {
         Year(YearSpec),
         YearMonth(YearMonthSpec),
         Date(DateSpec),
+        /// The months holding a year, quarter, period or week of a fiscal calendar
+        Fiscal(FiscalCalendar, FiscalPeriod),
     }
 
     impl DateSpan {
+        /// Checks the spec of the span, a fiscal span must name a period of its fiscal year
+        pub fn validate(&self) -> Result<(), DayendarError> {
+            match self {
+                DateSpan::Year(year_spec) => year_spec.validate(),
+                DateSpan::YearMonth(year_month_spec) => year_month_spec.validate(),
+                DateSpan::Date(date_spec) => date_spec.validate(),
+                DateSpan::Fiscal(calendar, period) => calendar.bounds(*period).map(|_| ()),
+            }
+        }
+
         pub fn to_year_month(&self) -> HashSet<(Year, Month)> {
             match self {
                 DateSpan::Year(year_spec) => year_spec.to_year_month(),
                 DateSpan::YearMonth(year_month_spec) => year_month_spec.to_year_month(),
                 DateSpan::Date(date_spec) => date_spec.to_year_month(),
+                // A period missing from the fiscal year spans no month
+                DateSpan::Fiscal(calendar, period) => match calendar.bounds(*period) {
+                    Ok((first, last)) => DateSpec::Range(first, last).to_year_month(),
+                    Err(_) => HashSet::new(),
+                },
             }
         }
 
+        /// Range from the first to the last Gregorian date of a month of a foreign calendar, e.g.,
+        /// `DateSpan::foreign_month(CalendarSystem::Persian, 1403, 1)` runs from 20 March to 19 April 2024.
+        /// As for any date range the evaluation materializes whole Gregorian months, so it also holds
+        /// 1 to 19 March and 20 to 30 April, combine it with a `ForeignDate` pattern with `day` 0 to keep
+        /// only the days of the foreign month
+        pub fn foreign_month(system: CalendarSystem, year: i32, month: u8) -> Result<DateSpan, DayendarError> {
+            let first: Date = system.to_gregorian(year, month, 1)?;
+            let last: Date = system.to_gregorian(year, month, system.days_in_month(year, month))?;
+            Ok(DateSpan::Date(DateSpec::Range(first, last)))
+        }
+
         /*pub fn intersect(&self, other: &DateSpan) -> Option<DateSpan> {
             
         }
@@ -887,6 +1082,7 @@ pub mod types {
 mod tests_generic_types {
     use super::*;
     use std::collections::HashSet;
+    use crate::error::DayendarError;
 
 
     #[test]
@@ -1018,7 +1214,7 @@ mod tests_generic_types {
         assert!(invalid_month.is_none());
 
         if let Some(month) = invalid_month {
-            assert_eq!(YearMonth::new(2023, month), Err("Month should be between 1 and 12".to_string()));
+            assert_eq!(YearMonth::new(2023, month), Err(DayendarError::InvalidMonth(month as u8)));
         }
     }
 
@@ -1300,15 +1496,36 @@ mod tests_generic_types {
 
     #[test]
     fn test_yearspec_parse() {
-        assert_eq!(YearSpec::parse("2023"), Some(YearSpec::Single(2023)));
+        assert_eq!(YearSpec::parse("2023"), Ok(YearSpec::Single(2023)));
         
-        assert_eq!(YearSpec::parse("2023-2025"), Some(YearSpec::Range(2023..=2025)));
+        assert_eq!(YearSpec::parse("2023-2025"), Ok(YearSpec::Range(2023..=2025)));
 
         let mut set = HashSet::new();
         set.insert(2023);
         set.insert(2025);
         set.insert(2027);
-        assert_eq!(YearSpec::parse("2023,2025,2027"), Some(YearSpec::List(set)));
+        assert_eq!(YearSpec::parse("2023,2025,2027"), Ok(YearSpec::List(set)));
+    }
+
+    #[test]
+    fn test_spec_parse_errors() {
+        let error = YearSpec::parse("2023, 20x5").unwrap_err();
+        assert_eq!(error, DayendarError::ParseError {
+            input: String::from("2023, 20x5"), position: 6, message: String::from("invalid year `20x5`"),
+        });
+        assert!(YearSpec::parse("2023-").is_err());
+
+        let error = MonthSpec::parse("Jan-Foo").unwrap_err();
+        assert_eq!(error.line_column(), Some((1, 5)));
+        assert!(error.to_string().ends_with("invalid month `Foo`"));
+
+        let error = MonthSpec::parse("Jan, Foo").unwrap_err();
+        assert_eq!(error.line_column(), Some((1, 6)));
+        assert!(error.to_string().ends_with("invalid month `Foo`"));
+
+        let error = YearMonthSpec::parse("2023-Januar").unwrap_err();
+        assert_eq!(error.line_column(), Some((1, 6)));
+        assert!(Month::from_str("13").unwrap_err().to_string().ends_with("invalid month `13`"));
     }
 
     #[test]
@@ -1752,11 +1969,11 @@ mod tests_generic_types {
     #[test]
     fn test_yearspec_range_to() {
         assert_eq!(
-            YearSpec::Single(2020).range_to(&YearSpec::Single(2023)),
+            YearSpec::Single(2020).range_to(&YearSpec::Single(2023)).unwrap(),
             YearSpec::Range(2020..=2023)
         );
         assert_eq!(
-            YearSpec::Single(2020).range_to(&YearSpec::Range(2021..=2023)),
+            YearSpec::Single(2020).range_to(&YearSpec::Range(2021..=2023)).unwrap(),
             YearSpec::Range(2020..=2023)
         );
         let mut end_set = HashSet::new();
@@ -1767,11 +1984,21 @@ mod tests_generic_types {
             expected_set.insert(year);
         }
         assert_eq!(
-            YearSpec::Single(2020).range_to(&YearSpec::List(end_set)),
+            YearSpec::Single(2020).range_to(&YearSpec::List(end_set)).unwrap(),
             YearSpec::List(expected_set)
         );
     }
 
+    #[test]
+    fn test_range_to_empty_list() {
+        let empty_years = YearSpec::List(HashSet::new());
+        assert_eq!(YearSpec::Single(2020).range_to(&empty_years), Err(DayendarError::InvalidSpec("empty year list")));
+        assert!(empty_years.range_to(&YearSpec::Single(2020)).is_err());
+        let empty_months = MonthSpec::List(HashSet::new());
+        assert_eq!(empty_months.range_to(&MonthSpec::Single(Month::May)), Err(DayendarError::InvalidSpec("empty month list")));
+        assert!(MonthSpec::Single(Month::May).range_to(&empty_months).is_err());
+    }
+
     // Pruebas adicionales para MonthSpec
     #[test]
     fn test_monthspec_forward_backward() {
@@ -1796,11 +2023,11 @@ mod tests_generic_types {
     #[test]
     fn test_monthspec_range_to() {
         assert_eq!(
-            MonthSpec::Single(Month::January).range_to(&MonthSpec::Single(Month::March)),
+            MonthSpec::Single(Month::January).range_to(&MonthSpec::Single(Month::March)).unwrap(),
             MonthSpec::Range(Month::January..=Month::March)
         );
         assert_eq!(
-            MonthSpec::Single(Month::January).range_to(&MonthSpec::Range(Month::February..=Month::April)),
+            MonthSpec::Single(Month::January).range_to(&MonthSpec::Range(Month::February..=Month::April)).unwrap(),
             MonthSpec::Range(Month::January..=Month::April)
         );
         let mut end_set = HashSet::new();
@@ -1811,7 +2038,7 @@ mod tests_generic_types {
         expected_set.insert(Month::February);
         expected_set.insert(Month::March);
         assert_eq!(
-            MonthSpec::Single(Month::January).range_to(&MonthSpec::List(end_set)),
+            MonthSpec::Single(Month::January).range_to(&MonthSpec::List(end_set)).unwrap(),
             MonthSpec::List(expected_set)
         );
     }
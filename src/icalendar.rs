@@ -0,0 +1,624 @@
+/// The `ics` module exchanges calendars with iCalendar (RFC 5545) clients.
+pub mod ics {
+
+    use std::collections::HashSet;
+    use std::io::{self, Write};
+
+    use time::{OffsetDateTime, PrimitiveDateTime, Time};
+
+    use crate::types::{Year, Month, Day, BiDay, Date, DateSpan, Duration, MAX_DATE};
+    use crate::calendar::{DaysCalendar, from_date, from_day};
+    use crate::error::DayendarError;
+
+    /// Longest line in octets, without the line break, before it is folded
+    const MAX_LINE_OCTETS: usize = 75;
+
+    /// Options of the iCalendar export
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct IcsOptions {
+        /// SUMMARY of every event
+        pub summary: String,
+        /// Prefix of the UID of every event, the UID ends with the dates of the event
+        pub uid_prefix: String,
+        /// Joins consecutive days into a single multi-day event
+        pub coalesce: bool,
+        /// DTSTAMP of every event, the time of the export by default
+        pub timestamp: OffsetDateTime,
+    }
+
+    impl Default for IcsOptions {
+        fn default() -> Self {
+            IcsOptions {
+                summary: String::from("Calendar day"),
+                uid_prefix: String::from("dayendar-"),
+                coalesce: false,
+                timestamp: OffsetDateTime::now_utc(),
+            }
+        }
+    }
+
+    /// Result of an iCalendar import
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct IcsImport {
+        /// Days covered by the events, with every month of the requested span
+        pub calendar: DaysCalendar<BiDay>,
+        /// Content that was skipped because it is not supported
+        pub warnings: Vec<IcsWarning>,
+    }
+
+    /// Content of an iCalendar input that was skipped
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct IcsWarning {
+        /// 1-based line of the input
+        pub line: usize,
+        pub message: String,
+    }
+
+    /// Properties that describe an event without changing the days it covers
+    const DESCRIPTIVE_PROPERTIES: [&str; 20] = [
+        "UID", "DTSTAMP", "SUMMARY", "DESCRIPTION", "LOCATION", "CATEGORIES", "CLASS", "COMMENT",
+        "CONTACT", "CREATED", "LAST-MODIFIED", "ORGANIZER", "ATTENDEE", "PRIORITY", "SEQUENCE",
+        "STATUS", "TRANSP", "URL", "GEO", "RESOURCES",
+    ];
+
+    /// A content line after unfolding
+    struct ContentLine {
+        name: String,
+        params: Vec<(String, String)>,
+        value: String,
+        /// Byte offset of the line in the input
+        offset: usize,
+        /// 1-based line of the input
+        number: usize,
+    }
+
+    impl ContentLine {
+        fn param(&self, name: &str) -> Option<&str> {
+            self.params.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+        }
+    }
+
+    /// An instant of an event, DATE values are at midnight
+    type Instant = PrimitiveDateTime;
+
+    /// Days covered by an event of length `length` that starts at `start`
+    fn covered_days(start: Instant, length: Duration) -> impl Iterator<Item = Date> {
+        // The end is exclusive, the last covered instant is just before it, which is still
+        // representable when the event runs to the end of 9999-12-31
+        let last: Date = if length <= Duration::ZERO {
+            start.date()
+        } else {
+            start.checked_add(length - Duration::nanoseconds(1)).map_or(MAX_DATE, |end| end.date())
+        };
+        std::iter::successors(Some(start.date()), |date| date.next_day()).take_while(move |date| *date <= last)
+    }
+
+    /// The state of the VEVENT being read
+    #[derive(Default)]
+    struct Event {
+        start: Option<Instant>,
+        all_day: bool,
+        end: Option<Instant>,
+        duration: Option<Duration>,
+        extra: Vec<(Instant, Option<Duration>)>,
+        excluded: HashSet<Date>,
+    }
+
+    /// Reads an iCalendar stream and gets the days covered by its VEVENTs in the months of a `DateSpan`.
+    ///
+    /// Folded lines, DATE and DATE-TIME values of DTSTART and DTEND, DURATION, EXDATE and RDATE are supported.
+    /// Time zones are not converted, a DATE-TIME covers the day written in the value.
+    /// Unsupported properties, such as RRULE, are skipped and reported as warnings.
+    pub fn from_ics(input: &str, span: &DateSpan) -> Result<IcsImport, DayendarError> {
+        let months: HashSet<(Year, Month)> = span.to_year_month();
+        let in_span = |date: &Date| match (Year::try_from(date.year()), Month::from_index(date.month() as u8)) {
+            (Ok(year), Some(month)) => months.contains(&(year, month)),
+            _ => false,
+        };
+
+        let mut warnings: Vec<IcsWarning> = Vec::new();
+        let mut dates: Vec<Date> = Vec::new();
+        let mut components: Vec<(String, usize)> = Vec::new();
+        let mut event: Option<Event> = None;
+
+        for line in unfold(input) {
+            let line = parse_content_line(input, line)?;
+            let error = |message: String| DayendarError::parse_error(input, line.offset, message);
+            let invalid_value = |line: &ContentLine| error(format!("invalid {} `{}`", line.name, line.value));
+            match line.name.as_str() {
+                "BEGIN" => {
+                    let component = line.value.to_ascii_uppercase();
+                    if component == "VEVENT" {
+                        event = Some(Event::default());
+                    }
+                    components.push((component, line.offset));
+                    continue;
+                },
+                "END" => {
+                    let component = line.value.to_ascii_uppercase();
+                    match components.pop() {
+                        Some((open, _)) if open == component => {},
+                        _ => return Err(error(format!("unexpected END:{}", line.value))),
+                    }
+                    if component == "VEVENT" {
+                        let finished = event.take().unwrap_or_default();
+                        let start = finished.start.ok_or_else(|| error(String::from("VEVENT without DTSTART")))?;
+                        let length = match (finished.end, finished.duration) {
+                            (Some(end), _) => end - start,
+                            (None, Some(duration)) => duration,
+                            (None, None) if finished.all_day => Duration::days(1),
+                            (None, None) => Duration::ZERO,
+                        };
+                        let occurrences = std::iter::once((start, None)).chain(finished.extra);
+                        for (occurrence, period) in occurrences {
+                            if finished.excluded.contains(&occurrence.date()) {
+                                continue;
+                            }
+                            dates.extend(covered_days(occurrence, period.unwrap_or(length)).filter(in_span));
+                        }
+                    }
+                    continue;
+                },
+                _ => {},
+            }
+
+            // Properties of nested components, such as VALARM, do not belong to the event
+            let in_event: bool = components.last().is_some_and(|(component, _)| component == "VEVENT");
+            let Some(current) = event.as_mut().filter(|_| in_event) else {
+                continue;
+            };
+            let is_date = line.param("VALUE").is_some_and(|value| value.eq_ignore_ascii_case("DATE"));
+            match line.name.as_str() {
+                "DTSTART" => {
+                    current.all_day = is_date || !line.value.contains('T');
+                    current.start = Some(parse_instant(&line.value).ok_or_else(|| invalid_value(&line))?);
+                },
+                "DTEND" => {
+                    current.end = Some(parse_instant(&line.value).ok_or_else(|| invalid_value(&line))?);
+                },
+                "DURATION" => {
+                    current.duration = Some(parse_duration(&line.value).ok_or_else(|| invalid_value(&line))?);
+                },
+                "EXDATE" => {
+                    for value in line.value.split(',') {
+                        let instant = parse_instant(value).ok_or_else(|| invalid_value(&line))?;
+                        current.excluded.insert(instant.date());
+                    }
+                },
+                "RDATE" => {
+                    for value in line.value.split(',') {
+                        let invalid = || invalid_value(&line);
+                        let occurrence = match value.split_once('/') {
+                            Some((start, end)) => {
+                                let start = parse_instant(start).ok_or_else(invalid)?;
+                                let length = match parse_instant(end) {
+                                    Some(end) => end - start,
+                                    None => parse_duration(end).ok_or_else(invalid)?,
+                                };
+                                (start, Some(length))
+                            },
+                            None => (parse_instant(value).ok_or_else(invalid)?, None),
+                        };
+                        current.extra.push(occurrence);
+                    }
+                },
+                name if DESCRIPTIVE_PROPERTIES.contains(&name) || name.starts_with("X-") => {},
+                name => warnings.push(IcsWarning {
+                    line: line.number,
+                    message: format!("unsupported property {} skipped", name),
+                }),
+            }
+        }
+
+        if let Some((component, offset)) = components.pop() {
+            return Err(DayendarError::parse_error(input, offset, format!("BEGIN:{} without END", component)));
+        }
+
+        let mut span_months: Vec<(Year, Month)> = months.into_iter().collect();
+        span_months.sort_unstable();
+        let empty: DaysCalendar<Day> = DaysCalendar {
+            days_calendar: span_months.into_iter().map(|(year, month)| (year, month, Vec::new())).collect(),
+        };
+        let covered: DaysCalendar<BiDay> = from_day(from_date(dates)?)?;
+        Ok(IcsImport { calendar: from_day(empty)?.or(&covered), warnings })
+    }
+
+    /// A logical line with the byte offset and the 1-based line where it starts
+    type Unfolded = (usize, usize, String);
+
+    /// Joins folded lines, that is the lines starting with a space or a tab, to the line before them
+    fn unfold(input: &str) -> impl Iterator<Item = Unfolded> + '_ {
+        let mut lines = input.split('\n').enumerate().scan(0, |offset, (index, line)| {
+            let start = *offset;
+            *offset += line.len() + 1;
+            Some((start, index + 1, line.strip_suffix('\r').unwrap_or(line)))
+        }).peekable();
+        std::iter::from_fn(move || loop {
+            let (start, number, first) = lines.next()?;
+            let mut logical = String::from(first);
+            while let Some((_, _, next)) = lines.peek() {
+                match next.strip_prefix([' ', '\t']) {
+                    Some(continuation) => {
+                        logical.push_str(continuation);
+                        lines.next();
+                    },
+                    None => break,
+                }
+            }
+            if !logical.is_empty() {
+                return Some((start, number, logical));
+            }
+        })
+    }
+
+    /// Splits a logical line into name, parameters and value
+    fn parse_content_line(input: &str, (offset, number, line): Unfolded) -> Result<ContentLine, DayendarError> {
+        let mut in_quotes = false;
+        let colon = line.char_indices().find(|&(_, c)| {
+            if c == '"' {
+                in_quotes = !in_quotes;
+            }
+            c == ':' && !in_quotes
+        });
+        let Some((colon, _)) = colon else {
+            return Err(DayendarError::parse_error(input, offset, format!("expected `name:value`, found `{}`", line)));
+        };
+        let mut head = line[..colon].split(';');
+        let name = head.next().unwrap_or_default().to_ascii_uppercase();
+        let params = head
+            .filter_map(|param| param.split_once('='))
+            .map(|(key, value)| (key.to_ascii_uppercase(), value.trim_matches('"').to_string()))
+            .collect();
+        Ok(ContentLine { name, params, value: line[colon + 1..].to_string(), offset, number })
+    }
+
+    /// Parses a DATE `YYYYMMDD` or a DATE-TIME `YYYYMMDDTHHMMSS`, with an optional `Z` suffix
+    pub(crate) fn parse_instant(value: &str) -> Option<Instant> {
+        let value = value.trim();
+        // The fields are sliced by byte offsets, which are only char boundaries in ASCII text
+        if !value.is_ascii() {
+            return None;
+        }
+        let number = |text: &str| -> Option<u32> {
+            if text.bytes().all(|b| b.is_ascii_digit()) { text.parse().ok() } else { None }
+        };
+        let (date, time) = match value.split_once('T') {
+            Some((date, time)) => (date, Some(time.strip_suffix('Z').unwrap_or(time))),
+            None => (value, None),
+        };
+        if date.len() != 8 {
+            return None;
+        }
+        let month = time::Month::try_from(number(&date[4..6])? as u8).ok()?;
+        let date = Date::from_calendar_date(number(&date[..4])? as i32, month, number(&date[6..])? as u8).ok()?;
+        let time = match time {
+            None => Time::MIDNIGHT,
+            Some(time) if time.len() == 6 => Time::from_hms(
+                number(&time[..2])? as u8, number(&time[2..4])? as u8, number(&time[4..])? as u8
+            ).ok()?,
+            Some(_) => return None,
+        };
+        Some(PrimitiveDateTime::new(date, time))
+    }
+
+    /// Parses a DURATION such as `P1W`, `-P2D` or `P1DT12H30M`
+    fn parse_duration(value: &str) -> Option<Duration> {
+        let value = value.trim();
+        let (negative, value) = match value.as_bytes().first()? {
+            b'-' => (true, &value[1..]),
+            b'+' => (false, &value[1..]),
+            _ => (false, value),
+        };
+        let value = value.strip_prefix('P')?;
+        let (date, time) = value.split_once('T').unwrap_or((value, ""));
+        let mut seconds: i64 = 0;
+        let mut components = 0;
+        for (part, units) in [(date, "WD"), (time, "HMS")] {
+            let mut digits = String::new();
+            for c in part.chars() {
+                if c.is_ascii_digit() {
+                    digits.push(c);
+                    continue;
+                }
+                if digits.is_empty() || !units.contains(c) {
+                    return None;
+                }
+                let amount: i64 = digits.parse().ok()?;
+                let unit: i64 = match c {
+                    'W' => 604_800,
+                    'D' => 86_400,
+                    'H' => 3_600,
+                    'M' => 60,
+                    _ => 1,
+                };
+                seconds = seconds.checked_add(amount.checked_mul(unit)?)?;
+                digits.clear();
+                components += 1;
+            }
+            if !digits.is_empty() {
+                return None;
+            }
+        }
+        if components == 0 {
+            return None;
+        }
+        Some(Duration::seconds(if negative { -seconds } else { seconds }))
+    }
+
+    /// Escapes a TEXT value
+    fn escape_text(value: &str) -> String {
+        let mut escaped = String::with_capacity(value.len());
+        for c in value.chars() {
+            match c {
+                '\\' | ';' | ',' => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                },
+                '\n' => escaped.push_str("\\n"),
+                '\r' => {},
+                _ => escaped.push(c),
+            }
+        }
+        escaped
+    }
+
+    /// Writes a content line folded at 75 octets and ended with CRLF
+    fn write_line<W: Write>(writer: &mut W, line: &str) -> io::Result<()> {
+        let mut rest = line;
+        let mut limit = MAX_LINE_OCTETS;
+        while rest.len() > limit {
+            let mut split = limit;
+            while !rest.is_char_boundary(split) {
+                split -= 1;
+            }
+            writer.write_all(&rest.as_bytes()[..split])?;
+            writer.write_all(b"\r\n ")?;
+            rest = &rest[split..];
+            // The leading space of a continuation line counts towards its length
+            limit = MAX_LINE_OCTETS - 1;
+        }
+        writer.write_all(rest.as_bytes())?;
+        writer.write_all(b"\r\n")
+    }
+
+    fn format_date(date: Date) -> String {
+        format!("{:04}{:02}{:02}", date.year(), date.month() as u8, date.day())
+    }
+
+    fn write_event<W: Write>(writer: &mut W, options: &IcsOptions, stamp: &str, first: Date, last: Date) -> io::Result<()> {
+        // DTEND is exclusive, an event ending on the last supported date is written with a DURATION
+        let end: String = match last.next_day() {
+            Some(end) => format!("DTEND;VALUE=DATE:{}", format_date(end)),
+            None => format!("DURATION:P{}D", (last - first).whole_days() + 1),
+        };
+        let uid = if first == last {
+            format!("{}{}", options.uid_prefix, format_date(first))
+        } else {
+            format!("{}{}-{}", options.uid_prefix, format_date(first), format_date(last))
+        };
+        write_line(writer, "BEGIN:VEVENT")?;
+        write_line(writer, &format!("UID:{}", escape_text(&uid)))?;
+        write_line(writer, &format!("DTSTAMP:{}", stamp))?;
+        write_line(writer, &format!("DTSTART;VALUE=DATE:{}", format_date(first)))?;
+        write_line(writer, &end)?;
+        write_line(writer, &format!("SUMMARY:{}", escape_text(&options.summary)))?;
+        write_line(writer, "END:VEVENT")
+    }
+
+    /// Writes a VCALENDAR with an all-day VEVENT for every day included in the calendar.
+    /// Events are written as the days are visited, so the output is never held in memory, and every
+    /// content line takes its own `write_all` calls, pass a `BufWriter` when writing to a file or socket.
+    /// The event holding 9999-12-31 has a DURATION instead of a DTEND, as the day after can not be written.
+    pub fn to_ics<W: Write>(calendar: &DaysCalendar<BiDay>, options: &IcsOptions, mut writer: W) -> io::Result<()> {
+        let utc = options.timestamp.to_offset(time::UtcOffset::UTC);
+        let stamp = format!(
+            "{}T{:02}{:02}{:02}Z",
+            format_date(utc.date()), utc.hour(), utc.minute(), utc.second()
+        );
+
+        write_line(&mut writer, "BEGIN:VCALENDAR")?;
+        write_line(&mut writer, "VERSION:2.0")?;
+        write_line(&mut writer, &format!("PRODID:-//dayendar//dayendar {}//EN", env!("CARGO_PKG_VERSION")))?;
+        write_line(&mut writer, "CALSCALE:GREGORIAN")?;
+
+        let mut event: Option<(Date, Date)> = None;
+        for date in calendar.dates() {
+            event = match event {
+                Some((first, last)) if options.coalesce && last.next_day() == Some(date) => Some((first, date)),
+                Some((first, last)) => {
+                    write_event(&mut writer, options, &stamp, first, last)?;
+                    Some((date, date))
+                },
+                None => Some((date, date)),
+            };
+        }
+        if let Some((first, last)) = event {
+            write_event(&mut writer, options, &stamp, first, last)?;
+        }
+
+        write_line(&mut writer, "END:VCALENDAR")?;
+        writer.flush()
+    }
+
+}
+
+// =========================================
+// SECTION FOR UNIT TEST CODE ...
+// =========================================
+#[cfg(test)]
+mod tests_ics {
+
+    use crate::calendar::*;
+    use crate::ics::*;
+    use crate::types::*;
+
+    fn options(coalesce: bool) -> IcsOptions {
+        IcsOptions {
+            summary: String::from("Office; day"),
+            uid_prefix: String::from("team-"),
+            coalesce,
+            timestamp: time::macros::datetime!(2024-01-15 10:30:00 +02:00),
+        }
+    }
+
+    fn export(calendar: &DaysCalendar<BiDay>, options: &IcsOptions) -> String {
+        let mut output: Vec<u8> = Vec::new();
+        to_ics(calendar, options, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    fn sample() -> DaysCalendar<BiDay> {
+        from_day(from_date(vec![
+            date!(2023 - 12 - 30), date!(2023 - 12 - 31), date!(2024 - 01 - 01), date!(2024 - 01 - 03),
+        ]).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_one_event_per_day() {
+        let text = export(&sample(), &options(false));
+        assert!(text.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//dayendar//"));
+        assert!(text.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+        assert_eq!(text.matches("BEGIN:VEVENT").count(), 4);
+        assert!(text.contains(
+            "BEGIN:VEVENT\r\nUID:team-20231231\r\nDTSTAMP:20240115T083000Z\r\nDTSTART;VALUE=DATE:20231231\r\n\
+             DTEND;VALUE=DATE:20240101\r\nSUMMARY:Office\\; day\r\nEND:VEVENT\r\n"
+        ));
+        assert!(text.split_inclusive('\n').all(|line| line.ends_with("\r\n") && line.len() <= 75 + 2));
+    }
+
+    #[test]
+    fn test_coalesce_consecutive_days() {
+        let text = export(&sample(), &options(true));
+        assert_eq!(text.matches("BEGIN:VEVENT").count(), 2);
+        assert!(text.contains("UID:team-20231230-20240101\r\n"));
+        assert!(text.contains("DTSTART;VALUE=DATE:20231230\r\nDTEND;VALUE=DATE:20240102\r\n"));
+        assert!(text.contains("DTSTART;VALUE=DATE:20240103\r\nDTEND;VALUE=DATE:20240104\r\n"));
+
+        let empty = export(&DaysCalendar::empty(), &options(true));
+        assert!(!empty.contains("VEVENT"));
+    }
+
+    #[test]
+    fn test_last_supported_date() {
+        let calendar = from_day(from_date(vec![date!(9999 - 12 - 30), date!(9999 - 12 - 31)]).unwrap()).unwrap();
+        let span = DateSpan::YearMonth(YearMonthSpec::parse("9999-December").unwrap());
+        for coalesce in [false, true] {
+            let text = export(&calendar, &options(coalesce));
+            assert!(text.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+            let imported = from_ics(&text, &span).unwrap();
+            assert_eq!(imported.calendar, calendar);
+            assert!(imported.warnings.is_empty());
+        }
+        assert!(export(&calendar, &options(false)).contains("DTSTART;VALUE=DATE:99991231\r\nDURATION:P1D\r\n"));
+        assert!(export(&calendar, &options(true)).contains("DTSTART;VALUE=DATE:99991230\r\nDURATION:P2D\r\n"));
+    }
+
+    #[test]
+    fn test_line_folding() {
+        let mut options = options(false);
+        options.summary = "Día de oficina ".repeat(10);
+        let calendar = from_day(from_date(vec![date!(2024 - 01 - 03)]).unwrap()).unwrap();
+        let text = export(&calendar, &options);
+        for line in text.split_inclusive('\n') {
+            assert!(line.ends_with("\r\n") && line.len() <= 75 + 2, "{:?}", line);
+        }
+        let unfolded = text.replace("\r\n ", "");
+        assert!(unfolded.contains(&format!("SUMMARY:{}\r\n", options.summary)));
+    }
+
+    fn days(calendar: &DaysCalendar<BiDay>) -> Vec<Date> {
+        calendar.dates().collect()
+    }
+
+    #[test]
+    fn test_import_round_trip() {
+        let span = DateSpan::Date(DateSpec::Range(date!(2023 - 12 - 01), date!(2024 - 01 - 31)));
+        for coalesce in [false, true] {
+            let imported = from_ics(&export(&sample(), &options(coalesce)), &span).unwrap();
+            assert_eq!(imported.calendar, sample());
+            assert!(imported.warnings.is_empty());
+        }
+
+        let january = DateSpan::YearMonth(YearMonthSpec::parse("2024-January").unwrap());
+        let imported = from_ics(&export(&sample(), &options(true)), &january).unwrap();
+        assert_eq!(imported.calendar.days_calendar.len(), 1);
+        assert_eq!(days(&imported.calendar), vec![date!(2024 - 01 - 01), date!(2024 - 01 - 03)]);
+    }
+
+    #[test]
+    fn test_import_times_durations_and_recurrence_dates() {
+        let input = "BEGIN:VCALENDAR\n\
+                     BEGIN:VEVENT\n\
+                     DTSTART;TZID=\"Europe/Madrid\":20240305T220000\n\
+                     DTEND:20240307T000000\n\
+                     END:VEVENT\n\
+                     BEGIN:VEVENT\n\
+                     DTSTART;VALUE=DATE:20240311\n\
+                     DURATION:P1W\n\
+                     EXDATE;VALUE=DATE:20240311\n\
+                     RDATE;VALUE=DATE:20240325,2024\n \
+                     0301\n\
+                     RDATE;VALUE=PERIOD:20240328T090000Z/PT1H\n\
+                     RRULE:FREQ=YEARLY\n\
+                     SUMMARY:Ignored\n\
+                     END:VEVENT\n\
+                     END:VCALENDAR\n";
+        let span = DateSpan::YearMonth(YearMonthSpec::parse("2024-March").unwrap());
+        let imported = from_ics(input, &span).unwrap();
+        let mut expected = vec![date!(2024 - 03 - 05), date!(2024 - 03 - 06), date!(2024 - 03 - 28)];
+        expected.extend((1..=7).map(|day| date!(2024 - 03 - 25) + Duration::days(day - 1)));
+        expected.extend((1..=7).map(|day| date!(2024 - 03 - 01) + Duration::days(day - 1)));
+        expected.sort();
+        expected.dedup();
+        assert_eq!(days(&imported.calendar), expected);
+        assert_eq!(imported.warnings, vec![IcsWarning {
+            line: 13,
+            message: String::from("unsupported property RRULE skipped"),
+        }]);
+
+        // Events running to the end of the last supported date keep their last day
+        let input = "BEGIN:VEVENT\nDTSTART;VALUE=DATE:99991230\nDURATION:P2D\nEND:VEVENT\n\
+                     BEGIN:VEVENT\nDTSTART:99991229T120000\nDURATION:P1W\nEND:VEVENT\n";
+        let span = DateSpan::YearMonth(YearMonthSpec::parse("9999-December").unwrap());
+        let imported = from_ics(input, &span).unwrap();
+        assert_eq!(days(&imported.calendar), vec![date!(9999 - 12 - 29), date!(9999 - 12 - 30), date!(9999 - 12 - 31)]);
+    }
+
+    #[test]
+    fn test_import_errors() {
+        let span = DateSpan::YearMonth(YearMonthSpec::parse("2024-March").unwrap());
+        let error = from_ics("BEGIN:VEVENT\r\nDTSTART:20240230\r\nEND:VEVENT\r\n", &span).unwrap_err();
+        assert_eq!(error.line_column(), Some((2, 1)));
+        assert!(error.to_string().ends_with("invalid DTSTART `20240230`"));
+
+        let error = from_ics("BEGIN:VEVENT\nSUMMARY:x\nEND:VEVENT\n", &span).unwrap_err();
+        assert!(error.to_string().starts_with("line 3, column 1: VEVENT without DTSTART"));
+        assert!(from_ics("BEGIN:VCALENDAR\nEND:VEVENT\n", &span).is_err());
+        assert!(from_ics("BEGIN:VCALENDAR\n", &span).is_err());
+        assert!(from_ics("BEGIN:VEVENT\nDTSTART\nEND:VEVENT\n", &span).is_err());
+
+        let error = from_ics("BEGIN:VEVENT\nDTSTART:20240301\nDURATION:P9999999999999999W\nEND:VEVENT\n", &span).unwrap_err();
+        assert!(error.to_string().ends_with("invalid DURATION `P9999999999999999W`"));
+
+        let error = from_ics("BEGIN:VEVENT\nDTSTART:20240é1\nEND:VEVENT\n", &span).unwrap_err();
+        assert!(error.to_string().ends_with("invalid DTSTART `20240é1`"));
+        assert!(from_ics("BEGIN:VEVENT\nDTSTART:20240301T1é000\nEND:VEVENT\n", &span).is_err());
+    }
+
+    #[test]
+    fn test_import_skips_nested_components() {
+        let input = "BEGIN:VEVENT\n\
+                     DTSTART;VALUE=DATE:20240305\n\
+                     BEGIN:VALARM\n\
+                     ACTION:DISPLAY\n\
+                     TRIGGER:-PT15M\n\
+                     DURATION:P3D\n\
+                     REPEAT:2\n\
+                     END:VALARM\n\
+                     END:VEVENT\n";
+        let span = DateSpan::YearMonth(YearMonthSpec::parse("2024-March").unwrap());
+        let imported = from_ics(input, &span).unwrap();
+        assert_eq!(days(&imported.calendar), vec![date!(2024 - 03 - 05)]);
+        assert_eq!(imported.warnings, Vec::<IcsWarning>::new());
+    }
+}
@@ -0,0 +1,208 @@
+/// The `holidays` module provides the public holidays of common jurisdictions as `AbstractCalendar` values.
+///
+/// Every calendar is defined by rules (fixed dates, n-th weekdays, Easter offsets and observance),
+/// so it can be evaluated for any year from 1 to 9999. The current rules are applied to every year,
+/// one-off holidays and changes of the law over time are not modelled.
+pub mod holidays {
+
+    use crate::types::{Day, Month, Weekday, Computus};
+    use crate::abstracto::{AbstractCalendar, CalendarPattern};
+    use crate::calendar::ObservanceRule;
+    use crate::rules::HolidayKind;
+
+    /// The same day of the month every year
+    fn fixed(month: Month, day: Day) -> AbstractCalendar {
+        AbstractCalendar::Pattern(HolidayKind::Fixed { month, day }.to_pattern())
+    }
+
+    /// The n-th occurrence of a weekday in a month every year
+    fn nth_weekday(n: i8, weekday: Weekday, month: Month) -> AbstractCalendar {
+        AbstractCalendar::Pattern(HolidayKind::NthWeekday { n, weekday, month }.to_pattern())
+    }
+
+    /// A day at `offset` days from Western Easter Sunday
+    fn easter(offset: i16) -> AbstractCalendar {
+        AbstractCalendar::Pattern(HolidayKind::Easter { offset, computus: Computus::Western }.to_pattern())
+    }
+
+    /// Combines calendars with the OR operator
+    fn any_of(calendars: Vec<AbstractCalendar>) -> AbstractCalendar {
+        calendars.into_iter()
+            .reduce(AbstractCalendar::or)
+            .unwrap_or(AbstractCalendar::Pattern(CalendarPattern::None))
+    }
+
+    fn weekend() -> Vec<Weekday> {
+        vec![Weekday::Saturday, Weekday::Sunday]
+    }
+
+    /// US federal holidays, fixed date holidays on a weekend are observed on the nearest weekday
+    #[cfg(feature = "holidays-us")]
+    pub fn us_federal() -> AbstractCalendar {
+        let fixed_dates = any_of(vec![
+            fixed(Month::January, 1),    // New Year's Day
+            fixed(Month::June, 19),      // Juneteenth
+            fixed(Month::July, 4),       // Independence Day
+            fixed(Month::November, 11),  // Veterans Day
+            fixed(Month::December, 25),  // Christmas Day
+        ]);
+        any_of(vec![
+            fixed_dates.observed(weekend(), ObservanceRule::NearestWeekday),
+            nth_weekday(3, Weekday::Monday, Month::January),   // Martin Luther King Jr. Day
+            nth_weekday(3, Weekday::Monday, Month::February),  // Washington's Birthday
+            nth_weekday(-1, Weekday::Monday, Month::May),      // Memorial Day
+            nth_weekday(1, Weekday::Monday, Month::September), // Labor Day
+            nth_weekday(2, Weekday::Monday, Month::October),   // Columbus Day
+            nth_weekday(4, Weekday::Thursday, Month::November), // Thanksgiving Day
+        ])
+    }
+
+    /// New York Stock Exchange holidays.
+    /// New Year's Day on a Saturday is not observed on the previous Friday.
+    #[cfg(feature = "holidays-us")]
+    pub fn us_nyse() -> AbstractCalendar {
+        let fixed_dates = any_of(vec![
+            fixed(Month::June, 19),     // Juneteenth
+            fixed(Month::July, 4),      // Independence Day
+            fixed(Month::December, 25), // Christmas Day
+        ]);
+        any_of(vec![
+            fixed(Month::January, 1).observed(vec![Weekday::Sunday], ObservanceRule::NextWeekday),
+            fixed_dates.observed(weekend(), ObservanceRule::NearestWeekday),
+            nth_weekday(3, Weekday::Monday, Month::January),    // Martin Luther King Jr. Day
+            nth_weekday(3, Weekday::Monday, Month::February),   // Washington's Birthday
+            easter(-2),                                         // Good Friday
+            nth_weekday(-1, Weekday::Monday, Month::May),       // Memorial Day
+            nth_weekday(1, Weekday::Monday, Month::September),  // Labor Day
+            nth_weekday(4, Weekday::Thursday, Month::November), // Thanksgiving Day
+        ])
+    }
+
+    /// Bank holidays of England and Wales, holidays on a weekend move to the next free weekday
+    #[cfg(feature = "holidays-eu")]
+    pub fn uk_england_wales() -> AbstractCalendar {
+        let fixed_dates = any_of(vec![
+            fixed(Month::January, 1),   // New Year's Day
+            fixed(Month::December, 25), // Christmas Day
+            fixed(Month::December, 26), // Boxing Day
+        ]);
+        any_of(vec![
+            fixed_dates.observed(weekend(), ObservanceRule::NextNonHoliday),
+            easter(-2),                                     // Good Friday
+            easter(1),                                      // Easter Monday
+            nth_weekday(1, Weekday::Monday, Month::May),    // Early May bank holiday
+            nth_weekday(-1, Weekday::Monday, Month::May),   // Spring bank holiday
+            nth_weekday(-1, Weekday::Monday, Month::August), // Summer bank holiday
+        ])
+    }
+
+    /// Closing days of the TARGET2 payment system
+    #[cfg(feature = "holidays-eu")]
+    pub fn target2() -> AbstractCalendar {
+        any_of(vec![
+            fixed(Month::January, 1),   // New Year's Day
+            easter(-2),                 // Good Friday
+            easter(1),                  // Easter Monday
+            fixed(Month::May, 1),       // Labour Day
+            fixed(Month::December, 25), // Christmas Day
+            fixed(Month::December, 26), // Christmas Holiday
+        ])
+    }
+
+    /// National public holidays of Germany
+    #[cfg(feature = "holidays-eu")]
+    pub fn germany() -> AbstractCalendar {
+        any_of(vec![
+            fixed(Month::January, 1),   // Neujahr
+            easter(-2),                 // Karfreitag
+            easter(1),                  // Ostermontag
+            fixed(Month::May, 1),       // Tag der Arbeit
+            easter(39),                 // Christi Himmelfahrt
+            easter(50),                 // Pfingstmontag
+            fixed(Month::October, 3),   // Tag der Deutschen Einheit
+            fixed(Month::December, 25), // Erster Weihnachtstag
+            fixed(Month::December, 26), // Zweiter Weihnachtstag
+        ])
+    }
+
+    /// National public holidays of Spain, regional holidays and substitutions are not included
+    #[cfg(feature = "holidays-eu")]
+    pub fn spain() -> AbstractCalendar {
+        any_of(vec![
+            fixed(Month::January, 1),   // Año Nuevo
+            fixed(Month::January, 6),   // Epifanía del Señor
+            easter(-2),                 // Viernes Santo
+            fixed(Month::May, 1),       // Fiesta del Trabajo
+            fixed(Month::August, 15),   // Asunción de la Virgen
+            fixed(Month::October, 12),  // Fiesta Nacional de España
+            fixed(Month::November, 1),  // Todos los Santos
+            fixed(Month::December, 6),  // Día de la Constitución
+            fixed(Month::December, 8),  // Inmaculada Concepción
+            fixed(Month::December, 25), // Navidad
+        ])
+    }
+
+}
+
+// =========================================
+// SECTION FOR UNIT TEST CODE ...
+// =========================================
+#[cfg(test)]
+mod tests_holidays {
+
+    use crate::calendar::*;
+    use crate::holidays::*;
+    use crate::types::*;
+    use crate::abstracto::AbstractCalendar;
+
+    fn holidays_of(calendar: AbstractCalendar, year: Year) -> Vec<Date> {
+        to_date(calendar.evaluate(&DateSpan::Year(YearSpec::Single(year))).unwrap())
+    }
+
+    #[cfg(feature = "holidays-us")]
+    #[test]
+    fn test_us_calendars() {
+        assert_eq!(holidays_of(us_federal(), 2021), vec![
+            date!(2021 - 01 - 01), date!(2021 - 01 - 18), date!(2021 - 02 - 15), date!(2021 - 05 - 31),
+            date!(2021 - 06 - 18), date!(2021 - 07 - 05), date!(2021 - 09 - 06), date!(2021 - 10 - 11),
+            date!(2021 - 11 - 11), date!(2021 - 11 - 25), date!(2021 - 12 - 24), date!(2021 - 12 - 31),
+        ]);
+        assert_eq!(holidays_of(us_nyse(), 2022), vec![
+            date!(2022 - 01 - 01), date!(2022 - 01 - 17), date!(2022 - 02 - 21), date!(2022 - 04 - 15),
+            date!(2022 - 05 - 30), date!(2022 - 06 - 20), date!(2022 - 07 - 04), date!(2022 - 09 - 05),
+            date!(2022 - 11 - 24), date!(2022 - 12 - 26),
+        ]);
+        assert_eq!(holidays_of(us_nyse(), 2021).last(), Some(&date!(2021 - 12 - 24)));
+    }
+
+    #[cfg(feature = "holidays-eu")]
+    #[test]
+    fn test_eu_calendars() {
+        assert_eq!(holidays_of(uk_england_wales(), 2021), vec![
+            date!(2021 - 01 - 01), date!(2021 - 04 - 02), date!(2021 - 04 - 05), date!(2021 - 05 - 03),
+            date!(2021 - 05 - 31), date!(2021 - 08 - 30), date!(2021 - 12 - 27), date!(2021 - 12 - 28),
+        ]);
+        assert_eq!(holidays_of(target2(), 2024), vec![
+            date!(2024 - 01 - 01), date!(2024 - 03 - 29), date!(2024 - 04 - 01), date!(2024 - 05 - 01),
+            date!(2024 - 12 - 25), date!(2024 - 12 - 26),
+        ]);
+        assert_eq!(holidays_of(germany(), 2024), vec![
+            date!(2024 - 01 - 01), date!(2024 - 03 - 29), date!(2024 - 04 - 01), date!(2024 - 05 - 01),
+            date!(2024 - 05 - 09), date!(2024 - 05 - 20), date!(2024 - 10 - 03), date!(2024 - 12 - 25),
+            date!(2024 - 12 - 26),
+        ]);
+        assert_eq!(holidays_of(spain(), 2024).len(), 10);
+        assert_eq!(holidays_of(spain(), 2024)[2], date!(2024 - 03 - 29));
+        // Fixed dates are typed patterns, not cron strings
+        assert!(format!("{:#}", target2()).contains("AnnualDate(December, 25)"));
+        assert!(!format!("{:#}", target2()).contains("CronPattern"));
+    }
+
+    #[test]
+    fn test_any_year() {
+        #[cfg(feature = "holidays-us")]
+        assert_eq!(holidays_of(us_federal(), 9999).len(), 11);
+        #[cfg(feature = "holidays-eu")]
+        assert_eq!(holidays_of(target2(), 1).len(), 6);
+    }
+}
@@ -3,17 +3,44 @@
 mod generic;
 pub use crate::generic::*;
 
+mod errors;
+pub use crate::errors::*;
+
 mod operations;
 pub use crate::operations::*;
 
 mod core;
 pub use crate::core::*;
+
+mod expressions;
+pub use crate::expressions::*;
+
+mod packed;
+pub use crate::packed::*;
+
+mod finance;
+pub use crate::finance::*;
+
+mod icalendar;
+pub use crate::icalendar::*;
+
+mod astronomy;
+pub use crate::astronomy::*;
+
+mod foreign;
+pub use crate::foreign::*;
+
+#[cfg(any(feature = "holidays-us", feature = "holidays-eu"))]
+mod jurisdictions;
+#[cfg(any(feature = "holidays-us", feature = "holidays-eu"))]
+pub use crate::jurisdictions::*;
+
+#[cfg(feature = "serde")]
+mod serialization;
 // GRCOV_EXCL_STOP
 
 #[cfg(test)]
 mod test {
-    use super::*;
-
     #[test]
     fn test_dummy_imports() { /* Dummy */ }
 }
@@ -1,9 +1,11 @@
 /// The `utils` module contains simple but general functions that can support the development of dayendar business logic. 
 pub mod utils {
 
-    use crate::types::{Month, Year, BiDay};
+    use crate::types::{Month, Year, Day, BiDay, Date, Duration, Computus, Weekday};
+    use crate::error::DayendarError;
 
     /// `is_leap` Determines whether a year is a leap year
+    #[allow(clippy::manual_is_multiple_of)]
     pub fn is_leap(year: Year) -> bool {
         (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
     }
@@ -37,6 +39,73 @@ pub mod utils {
         Some(n_days)
     }
 
+    /// Gets the number of days in a given month, failing when the year is out of range
+    pub(crate) fn checked_days_in_month(year: Year, month: Month) -> Result<u8, DayendarError> {
+        days_in_month(year, month).ok_or(DayendarError::YearOutOfRange(year.into()))
+    }
+
+    /// Builds the `Date` of a day, failing when the day does not exist
+    pub(crate) fn checked_date(year: Year, month: Month, day: Day) -> Result<Date, DayendarError> {
+        let invalid = || DayendarError::InvalidDate { year: year.into(), month: month.to_index(), day };
+        checked_days_in_month(year, month)?;
+        Date::from_calendar_date(year.into(), month.to_time_month()?, day).map_err(|_| invalid())
+    }
+
+    /// Gets the first and last dates of a month
+    pub(crate) fn month_bounds(year: Year, month: Month) -> Result<(Date, Date), DayendarError> {
+        Ok((checked_date(year, month, 1)?, checked_date(year, month, checked_days_in_month(year, month)?)?))
+    }
+
+    /// Calls `check` on every day from 2019 to 2030, the range covered by the round trip tests
+    #[cfg(test)]
+    pub(crate) fn for_each_test_day(mut check: impl FnMut(Date)) {
+        let mut date: Date = checked_date(2019, Month::January, 1).unwrap();
+        while date.year() < 2031 {
+            check(date);
+            date = date.next_day().unwrap();
+        }
+    }
+
+    /// Gets the date of Easter Sunday of a year with the given computus
+    pub fn easter_sunday(year: Year, computus: Computus) -> Result<Date, DayendarError> {
+        let march_first: Date = checked_date(year, Month::March, 1)?;
+        let y = i32::from(year);
+        // Day of March of Easter Sunday, days past the 31st fall in April
+        let march_day: i32 = match computus {
+            Computus::Western => {
+                // Meeus/Jones/Butcher algorithm
+                let (a, b, c) = (y % 19, y / 100, y % 100);
+                let (d, e) = (b / 4, b % 4);
+                let g = (b - (b + 8) / 25 + 1) / 3;
+                let h = (19 * a + b - d - g + 15) % 30;
+                let l = (32 + 2 * e + 2 * (c / 4) - h - c % 4) % 7;
+                let m = (a + 11 * h + 22 * l) / 451;
+                h + l - 7 * m + 22
+            },
+            Computus::Orthodox => {
+                // Meeus Julian algorithm, shifted by the difference between the Julian and Gregorian calendars
+                let d = (19 * (y % 19) + 15) % 30;
+                let e = (2 * (y % 4) + 4 * (y % 7) - d + 34) % 7;
+                d + e + 22 + y / 100 - y / 400 - 2
+            },
+        };
+        Ok(march_first + Duration::days(i64::from(march_day - 1)))
+    }
+
+    /// Gets the day of the n-th occurrence of a weekday in a month, negative `n` counts from the end of the month
+    pub fn nth_weekday_of_month(year: Year, month: Month, n: i8, weekday: Weekday) -> Result<Option<Day>, DayendarError> {
+        let n_days: u8 = checked_days_in_month(year, month)?;
+        let target: u8 = weekday.number_days_from_monday();
+        let day: i16 = if n > 0 {
+            let first: u8 = checked_date(year, month, 1)?.weekday().number_days_from_monday();
+            1 + i16::from((target + 7 - first) % 7) + 7 * (i16::from(n) - 1)
+        } else {
+            let last: u8 = checked_date(year, month, n_days)?.weekday().number_days_from_monday();
+            i16::from(n_days) - i16::from((last + 7 - target) % 7) - 7 * (-i16::from(n) - 1)
+        };
+        Ok(if n != 0 && (1..=i16::from(n_days)).contains(&day) { Some(day as Day) } else { None })
+    }
+
     /// Creates a vector of given size filled with a BiDay value
     pub fn generate_vec_days(size: usize, value: BiDay) -> Vec<BiDay> {
         let mut vec = Vec::with_capacity(size);
@@ -52,7 +121,8 @@ pub mod utils {
 ///
 pub mod binary {
 
-    use crate::utils::days_in_month;
+    use crate::utils::checked_days_in_month;
+    use crate::error::DayendarError;
     use crate::types::{
         Year, Month, BiDay,
         Date, Duration, MIN_DATE, MAX_DATE
@@ -210,12 +280,12 @@ pub mod binary {
     }
 
     /// Normalizes a BiDay vector to a specific month and year
-    pub fn normalize_biday(days: &[BiDay], year: Year, month: Month) -> Vec<BiDay> {
-        let n = days_in_month(year, month).unwrap().into();
+    pub fn normalize_biday(days: &[BiDay], year: Year, month: Month) -> Result<Vec<BiDay>, DayendarError> {
+        let n: usize = checked_days_in_month(year, month)?.into();
         if days.len() >= n {
-            days[..n].to_vec()
+            Ok(days[..n].to_vec())
         } else {
-            complete_biday(n, BiDay::Zero, days.to_vec())
+            Ok(complete_biday(n, BiDay::Zero, days.to_vec()))
         }
     }
 
@@ -250,6 +320,7 @@ pub mod binary {
 
 
     #[cfg(test)]
+    #[allow(clippy::bool_assert_comparison, clippy::useless_vec)]
     mod tests_binary_ops {
         
         use crate::types::*;
@@ -344,13 +415,45 @@ pub mod binary {
 
         #[test]
         fn test_normalize_biday_short() {
-            let result = normalize_biday(&vec![BiDay::One, BiDay::Zero], 2000, Month::February);
+            let result = normalize_biday(&vec![BiDay::One, BiDay::Zero], 2000, Month::February).unwrap();
             assert_eq!(result.len(), 29);
             assert_eq!(result[0], BiDay::One);
             assert_eq!(result[1], BiDay::Zero);
             assert_eq!(result[28], BiDay::Zero);
         }
 
+        #[test]
+        fn test_normalize_biday_invalid_year() {
+            assert_eq!(
+                normalize_biday(&[BiDay::One], 0, Month::January),
+                Err(crate::error::DayendarError::YearOutOfRange(0))
+            );
+        }
+
+        #[test]
+        fn test_easter_sunday() {
+            assert_eq!(easter_sunday(2024, Computus::Western), Ok(date!(2024 - 03 - 31)));
+            assert_eq!(easter_sunday(2025, Computus::Western), Ok(date!(2025 - 04 - 20)));
+            assert_eq!(easter_sunday(2038, Computus::Western), Ok(date!(2038 - 04 - 25)));
+            assert_eq!(easter_sunday(2285, Computus::Western), Ok(date!(2285 - 03 - 22)));
+            assert_eq!(easter_sunday(2024, Computus::Orthodox), Ok(date!(2024 - 05 - 05)));
+            assert_eq!(easter_sunday(2025, Computus::Orthodox), Ok(date!(2025 - 04 - 20)));
+            assert_eq!(easter_sunday(2023, Computus::Orthodox), Ok(date!(2023 - 04 - 16)));
+            assert_eq!(easter_sunday(0, Computus::Western), Err(crate::error::DayendarError::YearOutOfRange(0)));
+        }
+
+        #[test]
+        fn test_nth_weekday_of_month() {
+            assert_eq!(nth_weekday_of_month(2024, Month::November, 4, Weekday::Thursday), Ok(Some(28)));
+            assert_eq!(nth_weekday_of_month(2024, Month::May, -1, Weekday::Monday), Ok(Some(27)));
+            assert_eq!(nth_weekday_of_month(2024, Month::May, 1, Weekday::Monday), Ok(Some(6)));
+            assert_eq!(nth_weekday_of_month(2024, Month::February, 5, Weekday::Thursday), Ok(Some(29)));
+            assert_eq!(nth_weekday_of_month(2024, Month::February, 5, Weekday::Friday), Ok(None));
+            assert_eq!(nth_weekday_of_month(2024, Month::February, -5, Weekday::Thursday), Ok(Some(1)));
+            assert_eq!(nth_weekday_of_month(2024, Month::February, 0, Weekday::Thursday), Ok(None));
+            assert!(nth_weekday_of_month(0, Month::February, 1, Weekday::Thursday).is_err());
+        }
+
         #[test]
         fn test_replicate_pattern() {
             let pattern = vec![BiDay::One, BiDay::Zero];
@@ -0,0 +1,529 @@
+/// The `bitcalendar` module provides a packed representation of `DaysCalendar<BiDay>`.
+///
+/// Every month is stored as a single `u32` mask where bit `n - 1` is set when day `n`
+/// is included, so logical operations between months are single word operations and
+/// counting days is a popcount.
+pub mod bitcalendar {
+
+    use crate::types::{Year, Month, Day, BiDay, Date};
+    use crate::utils::days_in_month;
+    use crate::calendar::DaysCalendar;
+
+    /// Packed calendar with one bitmask per year month.
+    ///
+    /// Months are kept sorted by year and month without duplicates, and bits beyond
+    /// the last day of a month are always zero.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+    pub struct BitCalendar {
+        months: Vec<(Year, Month, u32)>,
+    }
+
+    /// Gets the mask with the bits of every day of a month set
+    pub fn month_mask(year: Year, month: Month) -> u32 {
+        match days_in_month(year, month) {
+            Some(n_days) => (1u32 << n_days) - 1,
+            None => 0,
+        }
+    }
+
+    /// Packs a BiDay vector into a bitmask, days beyond the month length are dropped
+    pub fn pack_biday(days: &[BiDay], year: Year, month: Month) -> u32 {
+        let bits: u32 = days.iter()
+            .take(32)
+            .enumerate()
+            .filter(|(_, day)| **day == BiDay::One)
+            .fold(0, |bits, (index, _)| bits | (1 << index));
+        bits & month_mask(year, month)
+    }
+
+    /// Unpacks a bitmask into a BiDay vector with the length of the month
+    pub fn unpack_biday(bits: u32, year: Year, month: Month) -> Vec<BiDay> {
+        let n_days: u8 = days_in_month(year, month).unwrap_or(0);
+        (0..n_days)
+            .map(|index| if bits & (1 << index) != 0 { BiDay::One } else { BiDay::Zero })
+            .collect()
+    }
+
+    impl BitCalendar {
+        /// Create an empty BitCalendar
+        pub fn empty() -> Self {
+            BitCalendar { months: Vec::new() }
+        }
+
+        /// Creates a BitCalendar from raw (year, month, bitmask) entries.
+        /// Entries are sorted, duplicated months are merged with the OR operator and
+        /// invalid months or bits beyond the end of the month are dropped.
+        pub fn from_masks(mut data: Vec<(Year, Month, u32)>) -> Self {
+            data.sort_by_key(|&(year, month, _)| (year, month));
+            let mut months: Vec<(Year, Month, u32)> = Vec::with_capacity(data.len());
+            for (year, month, bits) in data {
+                let mask = month_mask(year, month);
+                if mask == 0 {
+                    continue;
+                }
+                match months.last_mut() {
+                    Some(last) if last.0 == year && last.1 == month => last.2 |= bits & mask,
+                    _ => months.push((year, month, bits & mask)),
+                }
+            }
+            BitCalendar { months }
+        }
+
+        /// Creates a BitCalendar from the `Vec<(Year, Month, Vec<BiDay>)>` form of a `DaysCalendar`
+        pub fn from_vec(data: &[(Year, Month, Vec<BiDay>)]) -> Self {
+            Self::from_masks(
+                data.iter()
+                    .map(|(year, month, days)| (*year, *month, pack_biday(days, *year, *month)))
+                    .collect(),
+            )
+        }
+
+        /// Converts back to the `Vec<(Year, Month, Vec<BiDay>)>` form of a `DaysCalendar`
+        pub fn to_vec(&self) -> Vec<(Year, Month, Vec<BiDay>)> {
+            self.months.iter()
+                .map(|&(year, month, bits)| (year, month, unpack_biday(bits, year, month)))
+                .collect()
+        }
+
+        /// Converts a `DaysCalendar` into its packed form
+        pub fn from_days_calendar(calendar: &DaysCalendar<BiDay>) -> Self {
+            Self::from_vec(&calendar.days_calendar)
+        }
+
+        /// Converts back into a `DaysCalendar`
+        pub fn to_days_calendar(&self) -> DaysCalendar<BiDay> {
+            DaysCalendar { days_calendar: self.to_vec() }
+        }
+
+        /// Gets the packed (year, month, bitmask) entries
+        pub fn months(&self) -> &[(Year, Month, u32)] {
+            &self.months
+        }
+
+        /// Gets the bitmask of a given month
+        pub fn get_mask(&self, year: Year, month: Month) -> Option<u32> {
+            self.months
+                .binary_search_by_key(&(year, month), |&(y, m, _)| (y, m))
+                .ok()
+                .map(|index| self.months[index].2)
+        }
+
+        /// Determines whether a day is included in the calendar
+        pub fn contains(&self, year: Year, month: Month, day: Day) -> bool {
+            (1..=31).contains(&day) && self.get_mask(year, month).is_some_and(|bits| bits & (1 << (day - 1)) != 0)
+        }
+
+        /// Determines whether a date is included in the calendar
+        pub fn contains_date(&self, date: Date) -> bool {
+            match (Year::try_from(date.year()), Month::from_index(date.month() as u8)) {
+                (Ok(year), Some(month)) => self.contains(year, month, date.day()),
+                _ => false,
+            }
+        }
+
+        /// Number of months in the calendar
+        pub fn len_months(&self) -> usize {
+            self.months.len()
+        }
+
+        /// Determines whether the calendar has no months
+        pub fn is_empty(&self) -> bool {
+            self.months.is_empty()
+        }
+
+        /// Counts the included days of the calendar
+        pub fn count(&self) -> u32 {
+            self.months.iter().map(|&(_, _, bits)| bits.count_ones()).sum()
+        }
+
+        /// Counts the included days of a given month
+        pub fn count_month(&self, year: Year, month: Month) -> u32 {
+            self.get_mask(year, month).map_or(0, u32::count_ones)
+        }
+
+        /// Combines two calendars month by month.
+        /// Months present in only one of the calendars are kept unchanged, as `DaysCalendar` does.
+        pub fn combine<F>(&self, other: &Self, op: F) -> BitCalendar
+        where
+            F: Fn(u32, u32, u32) -> u32,
+        {
+            let mut months: Vec<(Year, Month, u32)> = Vec::with_capacity(self.months.len().max(other.months.len()));
+            let (mut i, mut j) = (0, 0);
+            while i < self.months.len() && j < other.months.len() {
+                let (y1, m1, a) = self.months[i];
+                let (y2, m2, b) = other.months[j];
+                match (y1, m1).cmp(&(y2, m2)) {
+                    std::cmp::Ordering::Less => {
+                        months.push((y1, m1, a));
+                        i += 1;
+                    },
+                    std::cmp::Ordering::Greater => {
+                        months.push((y2, m2, b));
+                        j += 1;
+                    },
+                    std::cmp::Ordering::Equal => {
+                        months.push((y1, m1, op(a, b, month_mask(y1, m1))));
+                        i += 1;
+                        j += 1;
+                    },
+                }
+            }
+            months.extend_from_slice(&self.months[i..]);
+            months.extend_from_slice(&other.months[j..]);
+            BitCalendar { months }
+        }
+
+        /// Combines two BitCalendar types based on the OR operator
+        pub fn or(&self, other: &Self) -> BitCalendar {
+            self.combine(other, |a, b, _| a | b)
+        }
+
+        /// Combines two BitCalendar types based on the AND operator
+        pub fn and(&self, other: &Self) -> BitCalendar {
+            self.combine(other, |a, b, _| a & b)
+        }
+
+        /// Combines two BitCalendar types based on the SUSTRACT operator
+        pub fn minus(&self, other: &Self) -> BitCalendar {
+            self.combine(other, |a, b, _| a & !b)
+        }
+
+        /// Combines two BitCalendar types based on calendar matches
+        pub fn r#match(&self, other: &Self) -> BitCalendar {
+            self.combine(other, |a, b, mask| !(a ^ b) & mask)
+        }
+
+        /// Combines two BitCalendar types based on calendar mismatches
+        pub fn nomatch(&self, other: &Self) -> BitCalendar {
+            self.combine(other, |a, b, _| a ^ b)
+        }
+
+        /// Inverts the days of every month of the calendar
+        pub fn invert(&self) -> BitCalendar {
+            let months = self.months.iter()
+                .map(|&(year, month, bits)| (year, month, !bits & month_mask(year, month)))
+                .collect();
+            BitCalendar { months }
+        }
+    }
+
+    impl From<&DaysCalendar<BiDay>> for BitCalendar {
+        fn from(calendar: &DaysCalendar<BiDay>) -> Self {
+            BitCalendar::from_days_calendar(calendar)
+        }
+    }
+
+    impl From<DaysCalendar<BiDay>> for BitCalendar {
+        fn from(calendar: DaysCalendar<BiDay>) -> Self {
+            BitCalendar::from_days_calendar(&calendar)
+        }
+    }
+
+    impl From<&BitCalendar> for DaysCalendar<BiDay> {
+        fn from(calendar: &BitCalendar) -> Self {
+            calendar.to_days_calendar()
+        }
+    }
+
+    impl From<BitCalendar> for DaysCalendar<BiDay> {
+        fn from(calendar: BitCalendar) -> Self {
+            calendar.to_days_calendar()
+        }
+    }
+
+}
+
+/// The `index` module provides `CalendarIndex`, a read-only index over a calendar that
+/// answers navigation and counting queries in logarithmic time in the number of months.
+pub mod index {
+
+    use crate::types::{BiDay, Date};
+    use crate::utils::checked_date;
+    use crate::calendar::DaysCalendar;
+    use crate::bitcalendar::BitCalendar;
+
+    /// Sorted month index with cumulative counts of included days.
+    ///
+    /// The index is a snapshot: it owns its data, so later changes to the source calendar
+    /// are not seen until the index is rebuilt with `CalendarIndex::rebuild`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct CalendarIndex {
+        bits: BitCalendar,
+        /// `cumulative[i]` is the number of included days in the months before month `i`
+        cumulative: Vec<u32>,
+    }
+
+    /// Sort key of a date's month
+    fn month_key(date: Date) -> (i32, u8) {
+        (date.year(), date.month() as u8)
+    }
+
+    impl CalendarIndex {
+        /// Creates the index of a packed calendar
+        pub fn new(bits: BitCalendar) -> Self {
+            let mut cumulative: Vec<u32> = Vec::with_capacity(bits.len_months() + 1);
+            cumulative.push(0);
+            for &(_, _, mask) in bits.months() {
+                let last = cumulative[cumulative.len() - 1];
+                cumulative.push(last + mask.count_ones());
+            }
+            CalendarIndex { bits, cumulative }
+        }
+
+        /// Replaces the indexed data with a new version of the calendar
+        pub fn rebuild(&mut self, calendar: &DaysCalendar<BiDay>) {
+            *self = Self::from(calendar);
+        }
+
+        /// Gets the packed calendar behind the index
+        pub fn bits(&self) -> &BitCalendar {
+            &self.bits
+        }
+
+        /// Counts the included days of the calendar
+        pub fn count(&self) -> u32 {
+            self.cumulative[self.cumulative.len() - 1]
+        }
+
+        /// Counts the included days strictly before a given date
+        pub fn rank(&self, date: Date) -> u32 {
+            let months = self.bits.months();
+            let key = month_key(date);
+            let position = months.partition_point(|&(year, month, _)| (i32::from(year), month.to_index()) < key);
+            let mut rank = self.cumulative[position];
+            if let Some(&(year, month, bits)) = months.get(position) {
+                if (i32::from(year), month.to_index()) == key {
+                    rank += (bits & ((1 << (date.day() - 1)) - 1)).count_ones();
+                }
+            }
+            rank
+        }
+
+        /// Gets the included date with a given 0-based rank
+        pub fn select(&self, rank: u32) -> Option<Date> {
+            if rank >= self.count() {
+                return None;
+            }
+            let position = self.cumulative.partition_point(|&count| count <= rank) - 1;
+            let (year, month, mut bits) = self.bits.months()[position];
+            for _ in self.cumulative[position]..rank {
+                bits &= bits - 1;
+            }
+            checked_date(year, month, bits.trailing_zeros() as u8 + 1).ok()
+        }
+
+        /// Determines whether a date is included in the calendar
+        pub fn contains(&self, date: Date) -> bool {
+            self.bits.contains_date(date)
+        }
+
+        /// Counts the included days from `start` to `end`, both inclusive
+        pub fn count_between(&self, start: Date, end: Date) -> u32 {
+            if start > end {
+                return 0;
+            }
+            self.rank_through(end) - self.rank(start)
+        }
+
+        /// Finds the next day on the calendar after a given date
+        pub fn next_day(&self, date: Date) -> Option<Date> {
+            self.select(self.rank_through(date))
+        }
+
+        /// Finds the previous day in the calendar before a given date
+        pub fn previous_day(&self, date: Date) -> Option<Date> {
+            self.select(self.rank(date).checked_sub(1)?)
+        }
+
+        /// Searches for the n-th day after/before a given date, as `DaysCalendar::seek_nth_day` does
+        pub fn seek_nth_day(&self, date: Date, n: isize) -> Option<Date> {
+            let steps = u32::try_from(n.unsigned_abs()).ok()?;
+            match n.cmp(&0) {
+                std::cmp::Ordering::Greater => self.select(self.rank_through(date).checked_add(steps - 1)?),
+                std::cmp::Ordering::Less => self.select(self.rank(date).checked_sub(steps)?),
+                std::cmp::Ordering::Equal => Some(date),
+            }
+        }
+
+        /// Counts the included days on or before a given date
+        fn rank_through(&self, date: Date) -> u32 {
+            self.rank(date) + u32::from(self.contains(date))
+        }
+    }
+
+    impl Default for CalendarIndex {
+        fn default() -> Self {
+            CalendarIndex::new(BitCalendar::empty())
+        }
+    }
+
+    impl From<BitCalendar> for CalendarIndex {
+        fn from(bits: BitCalendar) -> Self {
+            CalendarIndex::new(bits)
+        }
+    }
+
+    impl From<&DaysCalendar<BiDay>> for CalendarIndex {
+        fn from(calendar: &DaysCalendar<BiDay>) -> Self {
+            CalendarIndex::new(BitCalendar::from(calendar))
+        }
+    }
+
+    impl From<&CalendarIndex> for DaysCalendar<BiDay> {
+        fn from(index: &CalendarIndex) -> Self {
+            index.bits.to_days_calendar()
+        }
+    }
+
+}
+
+
+// =========================================
+// SECTION FOR UNIT TEST CODE ...
+// =========================================
+#[cfg(test)]
+mod tests_bitcalendar {
+
+    use crate::bitcalendar::*;
+    use crate::calendar::*;
+    use crate::types::*;
+
+    fn sample(first_pattern: [BiDay; 2], months: &[(Year, Month)]) -> DaysCalendar<BiDay> {
+        let base = DaysCalendar {
+            days_calendar: months.iter()
+                .map(|&(year, month)| (year, month, vec![BiDay::One; crate::utils::days_in_month(year, month).unwrap() as usize]))
+                .collect(),
+        };
+        replicate::<BiDay>(&first_pattern, base)
+    }
+
+    #[test]
+    fn test_pack_unpack() {
+        let days = vec![BiDay::One, BiDay::Zero, BiDay::One];
+        let bits = pack_biday(&days, 2023, Month::February);
+        assert_eq!(bits, 0b101);
+        let unpacked = unpack_biday(bits, 2023, Month::February);
+        assert_eq!(unpacked.len(), 28);
+        assert_eq!(&unpacked[..3], &days[..]);
+        assert_eq!(month_mask(2024, Month::February).count_ones(), 29);
+        assert_eq!(month_mask(0, Month::February), 0);
+    }
+
+    #[test]
+    fn test_lossless_round_trip() {
+        let calendar = sample([BiDay::One, BiDay::Zero], &[(2023, Month::January), (2023, Month::February), (2024, Month::February)]);
+        let packed = BitCalendar::from(&calendar);
+        assert_eq!(packed.len_months(), 3);
+        assert_eq!(DaysCalendar::from(&packed), calendar);
+        assert_eq!(BitCalendar::from_vec(&packed.to_vec()), packed);
+    }
+
+    #[test]
+    fn test_from_masks_normalizes() {
+        let packed = BitCalendar::from_masks(vec![
+            (2024, Month::March, 0b10),
+            (2023, Month::February, u32::MAX),
+            (2024, Month::March, 0b01),
+        ]);
+        assert_eq!(packed.months(), &[(2023, Month::February, (1 << 28) - 1), (2024, Month::March, 0b11)]);
+        assert_eq!(packed.count(), 30);
+    }
+
+    #[test]
+    fn test_operations_match_days_calendar() {
+        let months = [(2023, Month::December), (2024, Month::January), (2024, Month::February)];
+        let a = sample([BiDay::One, BiDay::Zero], &months);
+        let b = sample([BiDay::One, BiDay::One], &months[1..]).and_weekdays(
+            [Weekday::Monday, Weekday::Tuesday, Weekday::Friday].into_iter().collect(),
+        ).unwrap();
+        let (pa, pb) = (BitCalendar::from(&a), BitCalendar::from(&b));
+
+        assert_eq!(pa.or(&pb).to_days_calendar(), a.or(&b));
+        assert_eq!(pa.and(&pb).to_days_calendar(), a.and(&b));
+        assert_eq!(pa.minus(&pb).to_days_calendar(), a.minus(&b));
+        assert_eq!(pa.r#match(&pb).to_days_calendar(), a.r#match(&b));
+        assert_eq!(pa.nomatch(&pb).to_days_calendar(), a.nomatch(&b));
+        assert_eq!(pa.invert().to_days_calendar(), a.invert_biday());
+    }
+
+    #[test]
+    fn test_counts_and_contains() {
+        let packed = BitCalendar::from(sample([BiDay::Zero, BiDay::One], &[(2024, Month::February)]));
+        assert_eq!(packed.count(), 14);
+        assert_eq!(packed.count_month(2024, Month::February), 14);
+        assert_eq!(packed.count_month(2024, Month::March), 0);
+        assert!(packed.contains(2024, Month::February, 2));
+        assert!(!packed.contains(2024, Month::February, 1));
+        assert!(!packed.contains(2024, Month::February, 0));
+        assert!(packed.contains_date(date!(2024 - 02 - 28)));
+        assert!(!packed.contains_date(date!(2024 - 03 - 02)));
+        assert!(BitCalendar::empty().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests_index {
+
+    use crate::bitcalendar::BitCalendar;
+    use crate::calendar::*;
+    use crate::index::*;
+    use crate::types::*;
+
+    fn decades_calendar() -> DaysCalendar<BiDay> {
+        let mut calendar = DaysCalendar::empty();
+        for year in 1990..=2040 {
+            for month in 1..=12 {
+                calendar = calendar.append(&DaysCalendar::singleton(year, Month::from_index(month).unwrap()).unwrap());
+            }
+        }
+        replicate::<BiDay>(&[BiDay::One, BiDay::Zero, BiDay::Zero], calendar)
+    }
+
+    #[test]
+    fn test_navigation_matches_days_calendar() {
+        let calendar = decades_calendar();
+        let index = CalendarIndex::from(&calendar);
+        for (year, month, day) in [(1989, Month::December, 31), (2020, Month::June, 15), (2024, Month::February, 29), (2040, Month::December, 31)] {
+            let date = crate::utils::checked_date(year, month, day).unwrap();
+            assert_eq!(index.next_day(date), calendar.next_day(year, month, day));
+            assert_eq!(index.previous_day(date), calendar.previous_day(year, month, day));
+            for n in [-40, -1, 0, 1, 7, 100] {
+                assert_eq!(Ok(index.seek_nth_day(date, n)), calendar.seek_nth_day(year, month, day, n));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rank_select_and_counts() {
+        let calendar = decades_calendar();
+        let index = CalendarIndex::from(&calendar);
+        let dates = to_date(calendar);
+        assert_eq!(index.count() as usize, dates.len());
+        assert_eq!(index.select(0), Some(dates[0]));
+        assert_eq!(index.select(1000), Some(dates[1000]));
+        assert_eq!(index.select(index.count()), None);
+        assert_eq!(index.rank(dates[1000]), 1000);
+        assert!(index.contains(dates[1000]));
+        assert!(!index.contains(dates[1000].next_day().unwrap()));
+
+        assert_eq!(index.count_between(dates[10], dates[20]), 11);
+        assert_eq!(index.count_between(date!(1900 - 01 - 01), date!(2100 - 01 - 01)), index.count());
+        assert_eq!(index.count_between(dates[20], dates[10]), 0);
+    }
+
+    #[test]
+    fn test_empty_and_rebuild() {
+        let mut index = CalendarIndex::default();
+        assert_eq!(index.count(), 0);
+        assert_eq!(index.next_day(date!(2024 - 01 - 01)), None);
+        assert_eq!(index.previous_day(date!(2024 - 01 - 01)), None);
+
+        let calendar = DaysCalendar::singleton(2024, Month::March).unwrap();
+        index.rebuild(&calendar);
+        assert_eq!(index.count(), 31);
+        assert_eq!(index.seek_nth_day(date!(2024 - 02 - 10), 3), Some(date!(2024 - 03 - 03)));
+        assert_eq!(index.seek_nth_day(date!(2024 - 03 - 10), isize::MIN), None);
+        assert_eq!(DaysCalendar::from(&index), calendar);
+        assert_eq!(index.bits(), &BitCalendar::from(&calendar));
+    }
+}
@@ -0,0 +1,329 @@
+//! Serialize and Deserialize implementations, enabled by the `serde` feature.
+//!
+//! Calendars use a compact day-list encoding: every month is written as a
+//! `[year, month, [days]]` triple listing only the days included in the calendar.
+//! Calendar patterns and abstract calendars are written in the text syntax of the `dsl` module.
+//! Deserialization runs the same validation as the constructors.
+
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+use serde::de::Error as DeError;
+use serde::ser::Error as SerError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::abstracto::{AbstractCalendar, CalendarOperation, CalendarPattern};
+use crate::calendar::{DaysCalendar, from_day};
+use crate::types::{BiDay, Day, Month, Year, Date, YearSpec, MonthSpec, YearMonthSpec, DateSpec, DateSpan};
+use crate::fiscal::{FiscalCalendar, FiscalPeriod};
+
+/// Raw form of the day-list encoding
+type DayList = Vec<(Year, Month, Vec<Day>)>;
+
+/// Checks a day-list the way `from_day` and `DaysCalendar::try_new` do
+fn validated<E: DeError>(days_calendar: DayList) -> Result<DaysCalendar<BiDay>, E> {
+    let calendar = from_day(DaysCalendar { days_calendar }).map_err(E::custom)?;
+    calendar.validate().map_err(E::custom)?;
+    Ok(calendar)
+}
+
+fn custom_error<E: DeError>(error: impl Display) -> E {
+    E::custom(error)
+}
+
+impl Serialize for BiDay {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.to_u8())
+    }
+}
+
+impl<'de> Deserialize<'de> for BiDay {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = u8::deserialize(deserializer)?;
+        BiDay::from_u8(value).ok_or_else(|| D::Error::custom(format!("invalid BiDay {}, expected 0 or 1", value)))
+    }
+}
+
+impl Serialize for DaysCalendar<BiDay> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.days_calendar.iter().map(|(year, month, days)| {
+            let included: Vec<Day> = days.iter()
+                .enumerate()
+                .filter(|(_, day)| **day == BiDay::One)
+                .map(|(index, _)| index as Day + 1)
+                .collect();
+            (year, month, included)
+        }))
+    }
+}
+
+impl<'de> Deserialize<'de> for DaysCalendar<BiDay> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        validated(DayList::deserialize(deserializer)?)
+    }
+}
+
+impl Serialize for DaysCalendar<Day> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.days_calendar.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DaysCalendar<Day> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let days_calendar = DayList::deserialize(deserializer)?;
+        validated::<D::Error>(days_calendar.clone())?;
+        Ok(DaysCalendar { days_calendar })
+    }
+}
+
+/// Raw forms of the specs, checked with their `validate` method once read
+#[derive(Deserialize)]
+#[serde(rename = "YearSpec")]
+enum YearSpecRepr {
+    Single(Year),
+    Range(RangeInclusive<Year>),
+    List(HashSet<Year>),
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "MonthSpec")]
+enum MonthSpecRepr {
+    Single(Month),
+    Range(RangeInclusive<Month>),
+    List(HashSet<Month>),
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "YearMonthSpec")]
+struct YearMonthSpecRepr(HashSet<(YearSpec, MonthSpec)>);
+
+#[derive(Deserialize)]
+#[serde(rename = "DateSpec")]
+enum DateSpecRepr {
+    Single(Date),
+    Range(Date, Date),
+    List(HashSet<Date>),
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "DateSpan")]
+enum DateSpanRepr {
+    Year(YearSpec),
+    YearMonth(YearMonthSpec),
+    Date(DateSpec),
+    Fiscal(FiscalCalendar, FiscalPeriod),
+}
+
+impl<'de> Deserialize<'de> for YearSpec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let spec = match YearSpecRepr::deserialize(deserializer)? {
+            YearSpecRepr::Single(year) => YearSpec::Single(year),
+            YearSpecRepr::Range(range) => YearSpec::Range(range),
+            YearSpecRepr::List(years) => YearSpec::List(years),
+        };
+        spec.validate().map_err(custom_error)?;
+        Ok(spec)
+    }
+}
+
+impl<'de> Deserialize<'de> for MonthSpec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let spec = match MonthSpecRepr::deserialize(deserializer)? {
+            MonthSpecRepr::Single(month) => MonthSpec::Single(month),
+            MonthSpecRepr::Range(range) => MonthSpec::Range(range),
+            MonthSpecRepr::List(months) => MonthSpec::List(months),
+        };
+        spec.validate().map_err(custom_error)?;
+        Ok(spec)
+    }
+}
+
+impl<'de> Deserialize<'de> for YearMonthSpec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let spec = YearMonthSpec(YearMonthSpecRepr::deserialize(deserializer)?.0);
+        spec.validate().map_err(custom_error)?;
+        Ok(spec)
+    }
+}
+
+impl<'de> Deserialize<'de> for DateSpec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let spec = match DateSpecRepr::deserialize(deserializer)? {
+            DateSpecRepr::Single(date) => DateSpec::Single(date),
+            DateSpecRepr::Range(start, end) => DateSpec::Range(start, end),
+            DateSpecRepr::List(dates) => DateSpec::List(dates),
+        };
+        spec.validate().map_err(custom_error)?;
+        Ok(spec)
+    }
+}
+
+impl<'de> Deserialize<'de> for DateSpan {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let span = match DateSpanRepr::deserialize(deserializer)? {
+            DateSpanRepr::Year(spec) => DateSpan::Year(spec),
+            DateSpanRepr::YearMonth(spec) => DateSpan::YearMonth(spec),
+            DateSpanRepr::Date(spec) => DateSpan::Date(spec),
+            DateSpanRepr::Fiscal(calendar, period) => DateSpan::Fiscal(calendar, period),
+        };
+        span.validate().map_err(custom_error)?;
+        Ok(span)
+    }
+}
+
+/// Determines whether the calendar uses a `CustomFunction`, which has no text form
+fn has_custom_function(calendar: &AbstractCalendar) -> bool {
+    match calendar {
+        AbstractCalendar::Pattern(pattern) => matches!(pattern, CalendarPattern::CustomFunction(_)),
+        AbstractCalendar::Operation(
+            CalendarOperation::Invert(cal) | CalendarOperation::Observed(cal, _, _)
+        ) => has_custom_function(cal),
+        AbstractCalendar::Operation(
+            CalendarOperation::And(cal1, cal2)
+            | CalendarOperation::Or(cal1, cal2)
+            | CalendarOperation::Minus(cal1, cal2)
+        ) => has_custom_function(cal1) || has_custom_function(cal2),
+    }
+}
+
+impl Serialize for CalendarPattern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if let CalendarPattern::CustomFunction(_) = self {
+            return Err(S::Error::custom("custom functions can not be serialized"));
+        }
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for CalendarPattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(custom_error)
+    }
+}
+
+impl Serialize for AbstractCalendar {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if has_custom_function(self) {
+            return Err(S::Error::custom("custom functions can not be serialized"));
+        }
+        serializer.collect_str(&format_args!("{:#}", self))
+    }
+}
+
+impl<'de> Deserialize<'de> for AbstractCalendar {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(custom_error)
+    }
+}
+
+// =========================================
+// SECTION FOR UNIT TEST CODE ...
+// =========================================
+#[cfg(test)]
+mod tests_serde {
+
+    use crate::abstracto::*;
+    use crate::calendar::*;
+    use crate::types::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_days_calendar_day_list_encoding() {
+        let calendar = DaysCalendar::singleton(2024, Month::February).unwrap().downward_step();
+        let json = serde_json::to_string(&calendar).unwrap();
+        assert!(json.starts_with("[[2024,\"February\",[1,3,5,"));
+
+        let parsed: DaysCalendar<BiDay> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.days_calendar, calendar.days_calendar);
+
+        let days: DaysCalendar<Day> = serde_json::from_str("[[2023,\"March\",[1,31]]]").unwrap();
+        assert_eq!(days.days_calendar, vec![(2023, Month::March, vec![1, 31])]);
+        assert_eq!(serde_json::to_string(&days).unwrap(), "[[2023,\"March\",[1,31]]]");
+    }
+
+    #[test]
+    fn test_days_calendar_rejects_invalid_input() {
+        assert!(serde_json::from_str::<DaysCalendar<BiDay>>("[[2023,\"February\",[29]]]").is_err());
+        assert!(serde_json::from_str::<DaysCalendar<BiDay>>("[[2023,\"January\",[0]]]").is_err());
+        assert!(serde_json::from_str::<DaysCalendar<BiDay>>("[[0,\"January\",[1]]]").is_err());
+        assert!(serde_json::from_str::<DaysCalendar<BiDay>>("[[2023,\"Janvier\",[1]]]").is_err());
+        let unsorted = "[[2023,\"March\",[1]],[2023,\"January\",[1]]]";
+        let error = serde_json::from_str::<DaysCalendar<Day>>(unsorted).unwrap_err();
+        assert!(error.to_string().contains("calendar months are not sorted"));
+    }
+
+    #[test]
+    fn test_biday() {
+        assert_eq!(serde_json::to_string(&vec![BiDay::One, BiDay::Zero]).unwrap(), "[1,0]");
+        assert_eq!(serde_json::from_str::<BiDay>("1").unwrap(), BiDay::One);
+        assert!(serde_json::from_str::<BiDay>("2").is_err());
+    }
+
+    #[test]
+    fn test_specs_round_trip() {
+        let mut dates = HashSet::new();
+        dates.insert(date!(2024 - 02 - 29));
+        let spans = vec![
+            DateSpan::Year(YearSpec::Range(2020..=2024)),
+            DateSpan::YearMonth(YearMonthSpec::parse("2023-July").unwrap()),
+            DateSpan::Date(DateSpec::List(dates)),
+            DateSpan::Date(DateSpec::Range(date!(2024 - 01 - 01), date!(2024 - 03 - 31))),
+        ];
+        for span in spans {
+            let json = serde_json::to_string(&span).unwrap();
+            assert_eq!(serde_json::from_str::<DateSpan>(&json).unwrap(), span);
+        }
+        assert!(serde_json::from_str::<DateSpec>("{\"Single\":\"2023-02-29\"}").is_err());
+        assert_eq!(serde_json::to_string(&MonthSpec::Single(Month::May)).unwrap(), "{\"Single\":\"May\"}");
+    }
+
+    #[test]
+    fn test_invalid_specs_are_rejected() {
+        assert!(serde_json::from_str::<YearSpec>("{\"Single\":0}").is_err());
+        assert!(serde_json::from_str::<YearSpec>("{\"Single\":10000}").is_err());
+        assert!(serde_json::from_str::<YearSpec>("{\"Range\":{\"start\":2025,\"end\":2023}}").is_err());
+        assert!(serde_json::from_str::<YearSpec>("{\"List\":[]}").is_err());
+        assert!(serde_json::from_str::<MonthSpec>("{\"Range\":{\"start\":\"May\",\"end\":\"March\"}}").is_err());
+        assert!(serde_json::from_str::<MonthSpec>("{\"List\":[]}").is_err());
+        assert!(serde_json::from_str::<YearMonthSpec>("[]").is_err());
+        assert!(serde_json::from_str::<DateSpec>("{\"Range\":[\"2024-03-01\",\"2024-01-01\"]}").is_err());
+        assert!(serde_json::from_str::<DateSpec>("{\"List\":[]}").is_err());
+        assert!(serde_json::from_str::<DateSpan>("{\"Year\":{\"List\":[]}}").is_err());
+        assert!(serde_json::from_str::<DateSpan>("{\"YearMonth\":[[{\"Single\":0},{\"Single\":\"May\"}]]}").is_err());
+        assert_eq!(
+            serde_json::from_str::<YearSpec>("{\"Range\":{\"start\":2023,\"end\":2025}}").unwrap(),
+            YearSpec::Range(2023..=2025)
+        );
+    }
+
+    #[test]
+    fn test_abstract_calendar_round_trip() {
+        let weekends = AbstractCalendar::Pattern(CalendarPattern::Weekdays(vec![Weekday::Saturday, Weekday::Sunday]));
+        let holidays = AbstractCalendar::Pattern(CalendarPattern::FixedHolidays(vec![date!(2024 - 12 - 25)]));
+        let workdays = weekends.or(holidays).invert();
+
+        let json = serde_json::to_string(&workdays).unwrap();
+        assert_eq!(json, "\"Invert(Or(Weekdays([Saturday, Sunday]), FixedHolidays([2024-12-25])))\"");
+        assert_eq!(serde_json::from_str::<AbstractCalendar>(&json).unwrap(), workdays);
+
+        let pattern = CalendarPattern::CustomDays(vec![1, 15]);
+        let json = serde_json::to_string(&pattern).unwrap();
+        assert_eq!(serde_json::from_str::<CalendarPattern>(&json).unwrap(), pattern);
+        assert!(serde_json::from_str::<CalendarPattern>("\"CustomDays([256])\"").is_err());
+
+        let pattern = CalendarPattern::CustomDays(vec![0, 5]);
+        let json = serde_json::to_string(&pattern).unwrap();
+        assert_eq!(serde_json::from_str::<CalendarPattern>(&json).unwrap(), pattern);
+    }
+
+    #[test]
+    fn test_custom_function_is_not_serializable() {
+        fn first(_: Year, _: Month, day: Day) -> BiDay {
+            if day == 1 { BiDay::One } else { BiDay::Zero }
+        }
+        let calendar = AbstractCalendar::Pattern(CalendarPattern::CustomFunction(first)).invert();
+        assert!(serde_json::to_string(&calendar).is_err());
+    }
+}